@@ -1,7 +1,10 @@
+use rosu_map::section::general::GameMode;
+
 use crate::{
     catch::{CatchDifficultyAttributes, CatchPerformanceAttributes},
     mania::{ManiaDifficultyAttributes, ManiaPerformanceAttributes},
     osu::{OsuDifficultyAttributes, OsuPerformanceAttributes},
+    osu_2019,
     taiko::{TaikoDifficultyAttributes, TaikoPerformanceAttributes},
 };
 
@@ -18,6 +21,10 @@ pub enum DifficultyAttributes {
     Catch(CatchDifficultyAttributes),
     /// osu!mania difficulty calculation result.
     Mania(ManiaDifficultyAttributes),
+    /// osu!standard difficulty calculation result of the 2019 algorithm.
+    ///
+    /// See [`Variant::Relax2019`](crate::any::Variant::Relax2019).
+    Osu2019(osu_2019::OsuDifficultyAttributes),
 }
 
 impl DifficultyAttributes {
@@ -28,6 +35,7 @@ impl DifficultyAttributes {
             Self::Taiko(attrs) => attrs.stars,
             Self::Catch(attrs) => attrs.stars,
             Self::Mania(attrs) => attrs.stars,
+            Self::Osu2019(attrs) => attrs.stars,
         }
     }
 
@@ -38,6 +46,7 @@ impl DifficultyAttributes {
             Self::Taiko(attrs) => attrs.max_combo,
             Self::Catch(attrs) => attrs.max_combo(),
             Self::Mania(attrs) => attrs.max_combo,
+            Self::Osu2019(attrs) => attrs.max_combo as u32,
         }
     }
 
@@ -58,9 +67,23 @@ pub enum PerformanceAttributes {
     Catch(CatchPerformanceAttributes),
     /// osu!mania performance calculation result.
     Mania(ManiaPerformanceAttributes),
+    /// osu!standard performance calculation result of the 2019 algorithm.
+    ///
+    /// See [`Variant::Relax2019`](crate::any::Variant::Relax2019).
+    Osu2019(osu_2019::OsuPerformanceAttributes),
 }
 
 impl PerformanceAttributes {
+    /// The mode of the performance calculation.
+    pub const fn mode(&self) -> GameMode {
+        match self {
+            Self::Osu(_) | Self::Osu2019(_) => GameMode::Osu,
+            Self::Taiko(_) => GameMode::Taiko,
+            Self::Catch(_) => GameMode::Catch,
+            Self::Mania(_) => GameMode::Mania,
+        }
+    }
+
     /// The pp value.
     pub const fn pp(&self) -> f64 {
         match self {
@@ -68,6 +91,7 @@ impl PerformanceAttributes {
             Self::Taiko(attrs) => attrs.pp,
             Self::Catch(attrs) => attrs.pp,
             Self::Mania(attrs) => attrs.pp,
+            Self::Osu2019(attrs) => attrs.pp,
         }
     }
 
@@ -78,6 +102,7 @@ impl PerformanceAttributes {
             Self::Taiko(attrs) => attrs.stars(),
             Self::Catch(attrs) => attrs.stars(),
             Self::Mania(attrs) => attrs.stars(),
+            Self::Osu2019(attrs) => attrs.difficulty.stars,
         }
     }
 
@@ -88,6 +113,7 @@ impl PerformanceAttributes {
             Self::Taiko(attrs) => DifficultyAttributes::Taiko(attrs.difficulty.clone()),
             Self::Catch(attrs) => DifficultyAttributes::Catch(attrs.difficulty.clone()),
             Self::Mania(attrs) => DifficultyAttributes::Mania(attrs.difficulty.clone()),
+            Self::Osu2019(attrs) => DifficultyAttributes::Osu2019(attrs.difficulty.clone()),
         }
     }
 
@@ -98,6 +124,7 @@ impl PerformanceAttributes {
             Self::Taiko(attrs) => attrs.difficulty.max_combo,
             Self::Catch(attrs) => attrs.difficulty.max_combo(),
             Self::Mania(attrs) => attrs.difficulty.max_combo,
+            Self::Osu2019(attrs) => attrs.difficulty.max_combo as u32,
         }
     }
 
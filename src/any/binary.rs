@@ -0,0 +1,676 @@
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+};
+
+use rosu_map::section::general::GameMode;
+
+use crate::{
+    catch::CatchDifficultyAttributes, mania::ManiaDifficultyAttributes,
+    osu::OsuDifficultyAttributes, osu_2019::OsuDifficultyAttributes as Osu2019DifficultyAttributes,
+    taiko::TaikoDifficultyAttributes, Difficulty,
+};
+
+use super::{
+    difficulty::{ModsDependent, RelaxProfile, Variant},
+    DifficultyAttributes,
+};
+
+/// The version of the difficulty and performance algorithms.
+///
+/// Binary encoded attributes of a different version are rejected when
+/// decoding. The version is increased whenever the calculated attributes
+/// change.
+pub const ALGORITHM_VERSION: u16 = 5;
+
+const MAGIC: [u8; 4] = *b"AKPP";
+
+/// Metadata of binary encoded attributes.
+///
+/// See [`DifficultyAttributes::to_bytes`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct AttributesHeader {
+    /// The [`ALGORITHM_VERSION`] at the time of encoding.
+    pub version: u16,
+    /// The mode of the attributes.
+    pub mode: GameMode,
+    /// The osu!standard algorithm the attributes were calculated with.
+    pub variant: Variant,
+    /// The bitflags of the mods that were used for the calculation.
+    pub mods: u32,
+    /// The clock rate that was used for the calculation.
+    pub clock_rate: f64,
+    /// Hash of all other settings of the [`Difficulty`] that affect the
+    /// attributes, such as attribute overrides, passed objects, the relax
+    /// profile, or whether the calculation was for osu!lazer.
+    ///
+    /// The hash is stable across platforms and Rust versions.
+    pub settings: u64,
+}
+
+impl AttributesHeader {
+    fn new(mode: GameMode, variant: Variant, difficulty: &Difficulty) -> Self {
+        Self {
+            version: ALGORITHM_VERSION,
+            mode,
+            variant,
+            mods: difficulty.get_mods().bits(),
+            clock_rate: difficulty.get_clock_rate(),
+            settings: settings_hash(difficulty),
+        }
+    }
+
+    /// Whether the encoded attributes were calculated with the given
+    /// [`Difficulty`], i.e. with the same mods, clock rate, variant, and
+    /// all other settings.
+    ///
+    /// The variant is only compared for osu!standard attributes since it
+    /// does not apply to other modes.
+    pub fn matches(&self, difficulty: &Difficulty) -> bool {
+        self.mods == difficulty.get_mods().bits()
+            && self.clock_rate.to_bits() == difficulty.get_clock_rate().to_bits()
+            && (self.mode != GameMode::Osu || self.variant == difficulty.get_variant())
+            && self.settings == settings_hash(difficulty)
+    }
+
+    fn encode(&self, writer: &mut Writer) {
+        writer.bytes(&MAGIC);
+        writer.bytes(&self.version.to_le_bytes());
+        writer.u8(self.mode as u8);
+
+        writer.u8(match self.variant {
+            Variant::Default => 0,
+            Variant::Relax2019 => 1,
+        });
+
+        writer.u32(self.mods);
+        writer.f64(self.clock_rate);
+        writer.u64(self.settings);
+    }
+
+    fn decode(reader: &mut Reader<'_>) -> Result<Self, DecodeError> {
+        if reader.bytes(MAGIC.len())? != MAGIC {
+            return Err(DecodeError::Magic);
+        }
+
+        let version = u16::from_le_bytes(reader.array()?);
+
+        if version != ALGORITHM_VERSION {
+            return Err(DecodeError::Version { version });
+        }
+
+        let mode = match reader.u8()? {
+            0 => GameMode::Osu,
+            1 => GameMode::Taiko,
+            2 => GameMode::Catch,
+            3 => GameMode::Mania,
+            mode => return Err(DecodeError::Mode { mode }),
+        };
+
+        let variant = match reader.u8()? {
+            0 => Variant::Default,
+            1 if mode == GameMode::Osu => Variant::Relax2019,
+            variant => return Err(DecodeError::Variant { variant }),
+        };
+
+        Ok(Self {
+            version,
+            mode,
+            variant,
+            mods: reader.u32()?,
+            clock_rate: reader.f64()?,
+            settings: reader.u64()?,
+        })
+    }
+}
+
+/// FNV-1a hash of the [`Difficulty`] settings that are not stored in the
+/// [`AttributesHeader`] otherwise.
+fn settings_hash(difficulty: &Difficulty) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    let mut writer = Writer::default();
+    writer.u64(difficulty.get_passed_objects() as u64);
+    writer.bool(difficulty.get_hardrock_offsets());
+    writer.bool(difficulty.get_lazer());
+    writer.bool(difficulty.get_stack_leniency());
+
+    let overrides = [
+        difficulty.get_ar(),
+        difficulty.get_cs(),
+        difficulty.get_hp(),
+        difficulty.get_od(),
+    ];
+
+    for attr in overrides {
+        writer.bool(attr.is_some());
+
+        if let Some(ModsDependent { value, with_mods }) = attr {
+            writer.f64(f64::from(value));
+            writer.bool(with_mods);
+        }
+    }
+
+    let mods = difficulty.get_mods();
+    let mod_settings = [mods.ar(), mods.cs(), mods.hp(), mods.od()];
+
+    for value in mod_settings {
+        writer.bool(value.is_some());
+        writer.f64(value.unwrap_or(0.0));
+    }
+
+    let RelaxProfile {
+        rx_aim_multiplier,
+        rx_speed_multiplier,
+        rx_flashlight_multiplier,
+        rx_stream_aim_multiplier,
+        ap_aim_multiplier,
+        ap_flashlight_multiplier,
+        rx_miss_od,
+        rx_n100_exponent,
+        rx_n50_exponent,
+        rx_no_aim_ar_bonus,
+        ap_no_tapping_ar_bonus,
+        ap_no_aim_value,
+        rx_no_speed_value,
+        rx_no_acc_value,
+    } = *difficulty.get_relax_profile();
+
+    let profile_values = [
+        rx_aim_multiplier,
+        rx_speed_multiplier,
+        rx_flashlight_multiplier,
+        rx_stream_aim_multiplier,
+        ap_aim_multiplier,
+        ap_flashlight_multiplier,
+        rx_miss_od,
+        rx_n100_exponent,
+        rx_n50_exponent,
+    ];
+
+    let profile_flags = [
+        rx_no_aim_ar_bonus,
+        ap_no_tapping_ar_bonus,
+        ap_no_aim_value,
+        rx_no_speed_value,
+        rx_no_acc_value,
+    ];
+
+    for value in profile_values {
+        writer.f64(value);
+    }
+
+    for flag in profile_flags {
+        writer.bool(flag);
+    }
+
+    writer.buf.iter().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(PRIME)
+    })
+}
+
+/// Error when decoding binary encoded attributes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The bytes do not start with the expected magic bytes.
+    Magic,
+    /// The attributes were encoded by a different algorithm version.
+    Version { version: u16 },
+    /// Invalid mode
+    Mode { mode: u8 },
+    /// Invalid variant for the mode
+    Variant { variant: u8 },
+    /// The encoded attributes are of a different kind than requested.
+    Kind { mode: GameMode, variant: Variant },
+    /// The bytes ended unexpectedly.
+    UnexpectedEof,
+    /// The bytes continue after the attributes.
+    TrailingBytes,
+    /// Invalid UTF-8 in an encoded string.
+    Utf8,
+}
+
+impl Error for DecodeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        None
+    }
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            DecodeError::Magic => f.write_str("Missing magic bytes"),
+            DecodeError::Version { version } => write!(
+                f,
+                "Algorithm version {version} does not match the current version {ALGORITHM_VERSION}"
+            ),
+            DecodeError::Mode { mode } => write!(f, "Invalid mode {mode}"),
+            DecodeError::Variant { variant } => write!(f, "Invalid variant {variant}"),
+            DecodeError::Kind { mode, variant } => {
+                write!(f, "Unexpected attributes for {mode:?} ({variant:?})")
+            }
+            DecodeError::UnexpectedEof => f.write_str("Unexpected end of bytes"),
+            DecodeError::TrailingBytes => f.write_str("Unexpected bytes after the attributes"),
+            DecodeError::Utf8 => f.write_str("Invalid UTF-8"),
+        }
+    }
+}
+
+impl DifficultyAttributes {
+    /// Encode the attributes into a compact binary format.
+    ///
+    /// The encoding starts with an [`AttributesHeader`] that contains the
+    /// [`ALGORITHM_VERSION`] as well as the mods, clock rate, and a hash of
+    /// the other settings of the given [`Difficulty`], which should be the one
+    /// the attributes were calculated with.
+    pub fn to_bytes(&self, difficulty: &Difficulty) -> Vec<u8> {
+        let (mode, variant) = match self {
+            Self::Osu(_) => (GameMode::Osu, Variant::Default),
+            Self::Taiko(_) => (GameMode::Taiko, Variant::Default),
+            Self::Catch(_) => (GameMode::Catch, Variant::Default),
+            Self::Mania(_) => (GameMode::Mania, Variant::Default),
+            Self::Osu2019(_) => (GameMode::Osu, Variant::Relax2019),
+        };
+
+        let mut writer = Writer::default();
+        AttributesHeader::new(mode, variant, difficulty).encode(&mut writer);
+
+        match self {
+            Self::Osu(attrs) => attrs.encode(&mut writer),
+            Self::Taiko(attrs) => attrs.encode(&mut writer),
+            Self::Catch(attrs) => attrs.encode(&mut writer),
+            Self::Mania(attrs) => attrs.encode(&mut writer),
+            Self::Osu2019(attrs) => attrs.encode(&mut writer),
+        }
+
+        writer.buf
+    }
+
+    /// Decode attributes that were encoded through [`DifficultyAttributes::to_bytes`].
+    ///
+    /// Fails if the attributes were encoded by a different
+    /// [`ALGORITHM_VERSION`]. Use [`AttributesHeader::matches`] to check
+    /// whether they belong to the expected [`Difficulty`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<(AttributesHeader, Self), DecodeError> {
+        let mut reader = Reader::new(bytes);
+        let header = AttributesHeader::decode(&mut reader)?;
+
+        let attrs = match (header.mode, header.variant) {
+            (GameMode::Osu, Variant::Default) => Self::Osu(Decode::decode(&mut reader)?),
+            (GameMode::Osu, Variant::Relax2019) => Self::Osu2019(Decode::decode(&mut reader)?),
+            (GameMode::Taiko, _) => Self::Taiko(Decode::decode(&mut reader)?),
+            (GameMode::Catch, _) => Self::Catch(Decode::decode(&mut reader)?),
+            (GameMode::Mania, _) => Self::Mania(Decode::decode(&mut reader)?),
+        };
+
+        reader.finish()?;
+
+        Ok((header, attrs))
+    }
+}
+
+impl Osu2019DifficultyAttributes {
+    /// Encode the attributes into a compact binary format.
+    ///
+    /// See [`DifficultyAttributes::to_bytes`].
+    pub fn to_bytes(&self, difficulty: &Difficulty) -> Vec<u8> {
+        let mut writer = Writer::default();
+        AttributesHeader::new(GameMode::Osu, Variant::Relax2019, difficulty).encode(&mut writer);
+        self.encode(&mut writer);
+
+        writer.buf
+    }
+
+    /// Decode attributes that were encoded through [`Self::to_bytes`].
+    ///
+    /// See [`DifficultyAttributes::from_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<(AttributesHeader, Self), DecodeError> {
+        let mut reader = Reader::new(bytes);
+        let header = AttributesHeader::decode(&mut reader)?;
+
+        if header.variant != Variant::Relax2019 {
+            return Err(DecodeError::Kind {
+                mode: header.mode,
+                variant: header.variant,
+            });
+        }
+
+        let attrs = Self::decode(&mut reader)?;
+        reader.finish()?;
+
+        Ok((header, attrs))
+    }
+}
+
+#[derive(Default)]
+struct Writer {
+    buf: Vec<u8>,
+}
+
+impl Writer {
+    fn bytes(&mut self, bytes: &[u8]) {
+        self.buf.extend_from_slice(bytes);
+    }
+
+    fn u8(&mut self, n: u8) {
+        self.buf.push(n);
+    }
+
+    fn bool(&mut self, b: bool) {
+        self.u8(u8::from(b));
+    }
+
+    fn u32(&mut self, n: u32) {
+        self.bytes(&n.to_le_bytes());
+    }
+
+    fn i32(&mut self, n: i32) {
+        self.bytes(&n.to_le_bytes());
+    }
+
+    fn u64(&mut self, n: u64) {
+        self.bytes(&n.to_le_bytes());
+    }
+
+    fn f64(&mut self, n: f64) {
+        self.bytes(&n.to_le_bytes());
+    }
+
+    fn str(&mut self, s: &str) {
+        self.u32(s.len() as u32);
+        self.bytes(s.as_bytes());
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    const fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    const fn bytes(&mut self, len: usize) -> Result<&'a [u8], DecodeError> {
+        if self.bytes.len() < len {
+            return Err(DecodeError::UnexpectedEof);
+        }
+
+        let (bytes, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+
+        Ok(bytes)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], DecodeError> {
+        self.bytes(N)
+            .map(|bytes| bytes.try_into().expect("length was checked"))
+    }
+
+    fn u8(&mut self) -> Result<u8, DecodeError> {
+        self.array().map(|[n]| n)
+    }
+
+    fn bool(&mut self) -> Result<bool, DecodeError> {
+        self.u8().map(|n| n != 0)
+    }
+
+    fn u32(&mut self) -> Result<u32, DecodeError> {
+        self.array().map(u32::from_le_bytes)
+    }
+
+    fn i32(&mut self) -> Result<i32, DecodeError> {
+        self.array().map(i32::from_le_bytes)
+    }
+
+    fn u64(&mut self) -> Result<u64, DecodeError> {
+        self.array().map(u64::from_le_bytes)
+    }
+
+    fn f64(&mut self) -> Result<f64, DecodeError> {
+        self.array().map(f64::from_le_bytes)
+    }
+
+    fn string(&mut self) -> Result<String, DecodeError> {
+        let len = self.u32()? as usize;
+        let bytes = self.bytes(len)?;
+
+        String::from_utf8(bytes.to_owned()).map_err(|_| DecodeError::Utf8)
+    }
+
+    const fn finish(self) -> Result<(), DecodeError> {
+        if self.bytes.is_empty() {
+            Ok(())
+        } else {
+            Err(DecodeError::TrailingBytes)
+        }
+    }
+}
+
+trait Decode: Sized {
+    fn decode(reader: &mut Reader<'_>) -> Result<Self, DecodeError>;
+}
+
+/// Implements the binary encoding of an attributes struct by writing and
+/// reading its fields in the specified order.
+///
+/// Changing the fields or their order requires increasing the
+/// [`ALGORITHM_VERSION`].
+macro_rules! binary_attributes {
+    ( $ty:ident { $( $field:ident: $kind:ident, )* } ) => {
+        impl $ty {
+            fn encode(&self, writer: &mut Writer) {
+                $( binary_attributes!(@write writer, self.$field, $kind); )*
+            }
+        }
+
+        impl Decode for $ty {
+            fn decode(reader: &mut Reader<'_>) -> Result<Self, DecodeError> {
+                Ok(Self {
+                    $( $field: binary_attributes!(@read reader, $kind), )*
+                })
+            }
+        }
+    };
+    ( @write $writer:ident, $value:expr, f64 ) => { $writer.f64($value) };
+    ( @write $writer:ident, $value:expr, u32 ) => { $writer.u32($value) };
+    ( @write $writer:ident, $value:expr, i32 ) => { $writer.i32($value) };
+    ( @write $writer:ident, $value:expr, bool ) => { $writer.bool($value) };
+    ( @write $writer:ident, $value:expr, usize ) => { $writer.u32($value as u32) };
+    ( @write $writer:ident, $value:expr, string ) => { $writer.str(&$value) };
+    ( @read $reader:ident, f64 ) => { $reader.f64()? };
+    ( @read $reader:ident, u32 ) => { $reader.u32()? };
+    ( @read $reader:ident, i32 ) => { $reader.i32()? };
+    ( @read $reader:ident, bool ) => { $reader.bool()? };
+    ( @read $reader:ident, usize ) => { $reader.u32()? as usize };
+    ( @read $reader:ident, string ) => { $reader.string()? };
+}
+
+binary_attributes!(OsuDifficultyAttributes {
+    aim: f64,
+    speed: f64,
+    flashlight: f64,
+    autopilot: f64,
+    relax_stream_aim: f64,
+    slider_factor: f64,
+    speed_note_count: f64,
+    aim_difficult_strain_count: f64,
+    speed_difficult_strain_count: f64,
+    autopilot_difficult_strain_count: f64,
+    ar: f64,
+    od: f64,
+    hp: f64,
+    n_circles: u32,
+    n_sliders: u32,
+    n_large_ticks: u32,
+    n_spinners: u32,
+    stars: f64,
+    max_combo: u32,
+});
+
+binary_attributes!(TaikoDifficultyAttributes {
+    stamina: f64,
+    rhythm: f64,
+    color: f64,
+    peak: f64,
+    great_hit_window: f64,
+    ok_hit_window: f64,
+    mono_stamina_factor: f64,
+    stars: f64,
+    max_combo: u32,
+    is_convert: bool,
+    is_relax: bool,
+});
+
+binary_attributes!(CatchDifficultyAttributes {
+    stars: f64,
+    ar: f64,
+    n_fruits: u32,
+    n_droplets: u32,
+    n_tiny_droplets: u32,
+    is_convert: bool,
+    is_relax: bool,
+});
+
+binary_attributes!(ManiaDifficultyAttributes {
+    stars: f64,
+    hit_window: f64,
+    n_objects: u32,
+    n_hold_notes: u32,
+    max_combo: u32,
+    is_convert: bool,
+});
+
+binary_attributes!(Osu2019DifficultyAttributes {
+    aim_strain: f64,
+    speed_strain: f64,
+    flashlight_strain: f64,
+    ar: f64,
+    od: f64,
+    hp: f64,
+    cs: f64,
+    n_circles: usize,
+    n_sliders: usize,
+    n_spinners: usize,
+    stars: f64,
+    max_combo: usize,
+    aim_difficult_strain_count: f64,
+    speed_difficult_strain_count: f64,
+    beatmap_id: i32,
+    beatmap_creator: string,
+});
+
+#[cfg(test)]
+mod tests {
+    use crate::Beatmap;
+
+    use super::*;
+
+    fn roundtrip(difficulty: &Difficulty, path: &str) {
+        let map = Beatmap::from_path(path).unwrap();
+        let attrs = difficulty.calculate(&map);
+        let bytes = attrs.to_bytes(difficulty);
+
+        let (header, decoded) = DifficultyAttributes::from_bytes(&bytes).unwrap();
+
+        assert_eq!(decoded, attrs);
+        assert_eq!(header.version, ALGORITHM_VERSION);
+        assert_eq!(header.mode, map.mode);
+        assert!(header.matches(difficulty));
+        assert!(!header.matches(&Difficulty::new().mods(2048)));
+    }
+
+    #[test]
+    fn matches_settings() {
+        let difficulty = Difficulty::new().mods(64);
+        let header = AttributesHeader::new(GameMode::Osu, Variant::Default, &difficulty);
+
+        assert!(header.matches(&difficulty.clone()));
+        assert!(!header.matches(&difficulty.clone().variant(Variant::Relax2019)));
+        assert!(!header.matches(&difficulty.clone().lazer(false)));
+        assert!(!header.matches(&difficulty.clone().ar(9.0, false)));
+        assert!(!header.matches(&difficulty.clone().passed_objects(100)));
+        assert!(!header.matches(&difficulty.clone().stack_leniency(true)));
+
+        let profile = RelaxProfile {
+            rx_aim_multiplier: 0.95,
+            ..RelaxProfile::default()
+        };
+
+        assert!(!header.matches(&difficulty.clone().relax_profile(profile)));
+
+        // The variant does not apply to other modes
+        let header = AttributesHeader::new(GameMode::Taiko, Variant::Default, &difficulty);
+        assert!(header.matches(&difficulty.variant(Variant::Relax2019)));
+    }
+
+    #[test]
+    fn roundtrip_all_modes() {
+        let difficulty = Difficulty::new().mods(64 + 8);
+
+        roundtrip(&difficulty, "./resources/2785319.osu");
+        roundtrip(&difficulty, "./resources/1028484.osu");
+        roundtrip(&difficulty, "./resources/2118524.osu");
+        roundtrip(&difficulty, "./resources/1638954.osu");
+        roundtrip(
+            &difficulty.variant(Variant::Relax2019),
+            "./resources/2785319.osu",
+        );
+    }
+
+    #[test]
+    fn osu_2019() {
+        let difficulty = Difficulty::new().variant(Variant::Relax2019);
+        let attrs = Osu2019DifficultyAttributes {
+            stars: 6.5,
+            max_combo: 1234,
+            beatmap_creator: "peppy".to_owned(),
+            ..Default::default()
+        };
+
+        let bytes = attrs.to_bytes(&difficulty);
+        let (header, decoded) = Osu2019DifficultyAttributes::from_bytes(&bytes).unwrap();
+
+        assert_eq!(decoded, attrs);
+        assert_eq!(header.variant, Variant::Relax2019);
+
+        let bytes = DifficultyAttributes::Osu(OsuDifficultyAttributes::default())
+            .to_bytes(&Difficulty::new());
+
+        assert!(matches!(
+            Osu2019DifficultyAttributes::from_bytes(&bytes),
+            Err(DecodeError::Kind { .. })
+        ));
+    }
+
+    #[test]
+    fn reject_invalid() {
+        let attrs = DifficultyAttributes::Mania(ManiaDifficultyAttributes::default());
+        let mut bytes = attrs.to_bytes(&Difficulty::new());
+
+        assert_eq!(
+            DifficultyAttributes::from_bytes(&bytes[..bytes.len() - 1]),
+            Err(DecodeError::UnexpectedEof)
+        );
+
+        bytes.push(0);
+
+        assert_eq!(
+            DifficultyAttributes::from_bytes(&bytes),
+            Err(DecodeError::TrailingBytes)
+        );
+
+        bytes[MAGIC.len()..MAGIC.len() + 2].copy_from_slice(&0_u16.to_le_bytes());
+
+        assert_eq!(
+            DifficultyAttributes::from_bytes(&bytes),
+            Err(DecodeError::Version { version: 0 })
+        );
+
+        assert_eq!(
+            DifficultyAttributes::from_bytes(b"osu!"),
+            Err(DecodeError::Magic)
+        );
+    }
+}
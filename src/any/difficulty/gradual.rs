@@ -1,11 +1,12 @@
 use rosu_map::section::general::GameMode;
 
 use crate::{
-    any::DifficultyAttributes,
+    any::{DifficultyAttributes, Variant},
     catch::{Catch, CatchGradualDifficulty},
     mania::{Mania, ManiaGradualDifficulty},
     model::mode::{ConvertError, IGameMode},
     osu::{Osu, OsuGradualDifficulty},
+    osu_2019::Osu2019GradualDifficulty,
     taiko::{Taiko, TaikoGradualDifficulty},
     Beatmap, Difficulty,
 };
@@ -47,6 +48,7 @@ pub enum GradualDifficulty {
     Taiko(TaikoGradualDifficulty),
     Catch(CatchGradualDifficulty),
     Mania(ManiaGradualDifficulty),
+    Osu2019(Osu2019GradualDifficulty),
 }
 
 impl GradualDifficulty {
@@ -63,12 +65,62 @@ impl GradualDifficulty {
         mode: GameMode,
     ) -> Result<Self, ConvertError> {
         match mode {
+            GameMode::Osu if difficulty.get_variant() == Variant::Relax2019 => {
+                Osu2019GradualDifficulty::new(difficulty, map).map(Self::Osu2019)
+            }
             GameMode::Osu => Osu::gradual_difficulty(difficulty, map).map(Self::Osu),
             GameMode::Taiko => Taiko::gradual_difficulty(difficulty, map).map(Self::Taiko),
             GameMode::Catch => Catch::gradual_difficulty(difficulty, map).map(Self::Catch),
             GameMode::Mania => Mania::gradual_difficulty(difficulty, map).map(Self::Mania),
         }
     }
+
+    /// Store the state after every `interval` objects so that seeking
+    /// backwards with [`seek`] has to process at most `interval` objects.
+    ///
+    /// Checkpoints are stored while objects are being processed. Each one only
+    /// contains the current state of the skills and the lengths of their
+    /// strain histories so its size does not grow with the amount of processed
+    /// objects.
+    ///
+    /// [`seek`]: GradualDifficulty::seek
+    #[must_use]
+    pub fn checkpoints(self, interval: usize) -> Self {
+        match self {
+            Self::Osu(gradual) => Self::Osu(gradual.checkpoints(interval)),
+            Self::Taiko(gradual) => Self::Taiko(gradual.checkpoints(interval)),
+            Self::Catch(gradual) => Self::Catch(gradual.checkpoints(interval)),
+            Self::Mania(gradual) => Self::Mania(gradual.checkpoints(interval)),
+            Self::Osu2019(gradual) => Self::Osu2019(gradual.checkpoints(interval)),
+        }
+    }
+
+    /// Move to the object at index `idx`, which may be before or after the
+    /// current one, and return the difficulty attributes after processing it.
+    ///
+    /// The attributes are the same as for `nth(idx)` on a new iterator and
+    /// [`Iterator::next`] will continue with the object at `idx + 1`.
+    ///
+    /// Seeking backwards restores the latest checkpoint before `idx` or,
+    /// without [`checkpoints`], processes all objects from the start again.
+    /// Seeking forwards always processes all objects between the current one
+    /// and `idx` because restoring a checkpoint discards the strain history
+    /// after it.
+    ///
+    /// Returns `None` if `idx` is out of bounds.
+    ///
+    /// [`checkpoints`]: GradualDifficulty::checkpoints
+    pub fn seek(&mut self, idx: usize) -> Option<DifficultyAttributes> {
+        match self {
+            GradualDifficulty::Osu(gradual) => gradual.seek(idx).map(DifficultyAttributes::Osu),
+            GradualDifficulty::Taiko(gradual) => gradual.seek(idx).map(DifficultyAttributes::Taiko),
+            GradualDifficulty::Catch(gradual) => gradual.seek(idx).map(DifficultyAttributes::Catch),
+            GradualDifficulty::Mania(gradual) => gradual.seek(idx).map(DifficultyAttributes::Mania),
+            GradualDifficulty::Osu2019(gradual) => {
+                gradual.seek(idx).map(DifficultyAttributes::Osu2019)
+            }
+        }
+    }
 }
 
 impl Iterator for GradualDifficulty {
@@ -80,6 +132,9 @@ impl Iterator for GradualDifficulty {
             GradualDifficulty::Taiko(gradual) => gradual.next().map(DifficultyAttributes::Taiko),
             GradualDifficulty::Catch(gradual) => gradual.next().map(DifficultyAttributes::Catch),
             GradualDifficulty::Mania(gradual) => gradual.next().map(DifficultyAttributes::Mania),
+            GradualDifficulty::Osu2019(gradual) => {
+                gradual.next().map(DifficultyAttributes::Osu2019)
+            }
         }
     }
 
@@ -89,6 +144,7 @@ impl Iterator for GradualDifficulty {
             GradualDifficulty::Taiko(gradual) => gradual.size_hint(),
             GradualDifficulty::Catch(gradual) => gradual.size_hint(),
             GradualDifficulty::Mania(gradual) => gradual.size_hint(),
+            GradualDifficulty::Osu2019(gradual) => gradual.size_hint(),
         }
     }
 
@@ -98,6 +154,9 @@ impl Iterator for GradualDifficulty {
             GradualDifficulty::Taiko(gradual) => gradual.nth(n).map(DifficultyAttributes::Taiko),
             GradualDifficulty::Catch(gradual) => gradual.nth(n).map(DifficultyAttributes::Catch),
             GradualDifficulty::Mania(gradual) => gradual.nth(n).map(DifficultyAttributes::Mania),
+            GradualDifficulty::Osu2019(gradual) => {
+                gradual.nth(n).map(DifficultyAttributes::Osu2019)
+            }
         }
     }
 }
@@ -109,6 +168,7 @@ impl ExactSizeIterator for GradualDifficulty {
             GradualDifficulty::Taiko(gradual) => gradual.len(),
             GradualDifficulty::Catch(gradual) => gradual.len(),
             GradualDifficulty::Mania(gradual) => gradual.len(),
+            GradualDifficulty::Osu2019(gradual) => gradual.len(),
         }
     }
 }
@@ -1,6 +1,6 @@
 use crate::{model::mods::GameMods, Difficulty};
 
-use super::ModsDependent;
+use super::{ModsDependent, RelaxProfile, Variant};
 
 /// [`Difficulty`] but all fields are public for inspection.
 #[derive(Clone, Debug, Default, PartialEq)]
@@ -32,6 +32,14 @@ pub struct InspectDifficulty {
     ///
     /// Defaults to `true`.
     pub lazer: Option<bool>,
+    /// The algorithm for osu!standard calculations.
+    pub variant: Variant,
+    /// Whether the positional offsets of stacked notes should be considered.
+    ///
+    /// Only relevant for [`Variant::Relax2019`].
+    pub stack_leniency: Option<bool>,
+    /// Tuning values of the relax and autopilot adjustments.
+    pub relax_profile: RelaxProfile,
 }
 
 impl InspectDifficulty {
@@ -47,9 +55,15 @@ impl InspectDifficulty {
             od,
             hardrock_offsets,
             lazer,
+            variant,
+            stack_leniency,
+            relax_profile,
         } = self;
 
-        let mut difficulty = Difficulty::new().mods(mods);
+        let mut difficulty = Difficulty::new()
+            .mods(mods)
+            .variant(variant)
+            .relax_profile(relax_profile);
 
         if let Some(passed_objects) = passed_objects {
             difficulty = difficulty.passed_objects(passed_objects);
@@ -83,6 +97,10 @@ impl InspectDifficulty {
             difficulty = difficulty.lazer(lazer);
         }
 
+        if let Some(stack_leniency) = stack_leniency {
+            difficulty = difficulty.stack_leniency(stack_leniency);
+        }
+
         difficulty
     }
 }
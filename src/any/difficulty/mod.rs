@@ -10,15 +10,19 @@ use crate::{
     mania::Mania,
     model::{beatmap::Beatmap, mode::ConvertError, mods::GameMods},
     osu::Osu,
+    osu_2019,
     taiko::Taiko,
     GradualDifficulty, GradualPerformance,
 };
 
 use super::{attributes::DifficultyAttributes, InspectDifficulty, Strains};
 
+pub use self::relax_profile::RelaxProfile;
+
 pub mod gradual;
 pub mod inspect;
 pub mod object;
+pub mod relax_profile;
 pub mod skills;
 
 use crate::model::mode::IGameMode;
@@ -54,6 +58,9 @@ pub struct Difficulty {
     od: Option<ModsDependent>,
     hardrock_offsets: Option<bool>,
     lazer: Option<bool>,
+    variant: Variant,
+    stack_leniency: Option<bool>,
+    relax_profile: RelaxProfile,
 }
 
 /// Wrapper for beatmap attributes in [`Difficulty`].
@@ -77,6 +84,23 @@ impl ModsDependent {
     }
 }
 
+/// The algorithm that should be used for osu!standard calculations.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Variant {
+    /// The current osu!standard algorithm of osu!lazer, including the
+    /// relax and autopilot adjustments of this crate.
+    #[default]
+    Default,
+    /// The 2019 osu!standard algorithm that is used for relax leaderboards.
+    ///
+    /// See [`osu_2019`](crate::osu_2019).
+    Relax2019,
+}
+
+impl Variant {
+    pub(crate) const DEFAULT: Self = Self::Default;
+}
+
 impl Difficulty {
     /// Create a new difficulty calculator.
     pub const fn new() -> Self {
@@ -90,6 +114,9 @@ impl Difficulty {
             od: None,
             hardrock_offsets: None,
             lazer: None,
+            variant: Variant::DEFAULT,
+            stack_leniency: None,
+            relax_profile: RelaxProfile::DEFAULT,
         }
     }
 
@@ -106,6 +133,9 @@ impl Difficulty {
             od,
             hardrock_offsets,
             lazer,
+            variant,
+            stack_leniency,
+            relax_profile,
         } = self;
 
         InspectDifficulty {
@@ -118,6 +148,9 @@ impl Difficulty {
             od,
             hardrock_offsets,
             lazer,
+            variant,
+            stack_leniency,
+            relax_profile,
         }
     }
 
@@ -265,10 +298,48 @@ impl Difficulty {
         self
     }
 
+    /// Specify the algorithm for osu!standard calculations.
+    ///
+    /// Only relevant for osu!standard maps; other modes ignore this setting.
+    ///
+    /// Defaults to [`Variant::Default`].
+    pub const fn variant(mut self, variant: Variant) -> Self {
+        self.variant = variant;
+
+        self
+    }
+
+    /// Whether the positional offsets of stacked notes should be considered.
+    ///
+    /// Only relevant for [`Variant::Relax2019`] because the default algorithm
+    /// always applies stacking. Processing stack leniency is relatively
+    /// expensive and generally has little effect on the stars.
+    ///
+    /// Defaults to `false`.
+    pub const fn stack_leniency(mut self, stack_leniency: bool) -> Self {
+        self.stack_leniency = Some(stack_leniency);
+
+        self
+    }
+
+    /// Adjust the relax and autopilot handling of osu!standard.
+    ///
+    /// Only relevant for osu!standard with [`Variant::Default`].
+    ///
+    /// Defaults to [`RelaxProfile::DEFAULT`].
+    pub const fn relax_profile(mut self, relax_profile: RelaxProfile) -> Self {
+        self.relax_profile = relax_profile;
+
+        self
+    }
+
     /// Perform the difficulty calculation.
     #[allow(clippy::missing_panics_doc)]
     pub fn calculate(&self, map: &Beatmap) -> DifficultyAttributes {
         match map.mode {
+            GameMode::Osu if self.variant == Variant::Relax2019 => {
+                DifficultyAttributes::Osu2019(osu_2019::difficulty(self, map))
+            }
             GameMode::Osu => DifficultyAttributes::Osu(
                 Osu::difficulty(self, map).expect("no conversion required"),
             ),
@@ -299,6 +370,9 @@ impl Difficulty {
     #[allow(clippy::missing_panics_doc)]
     pub fn strains(&self, map: &Beatmap) -> Strains {
         match map.mode {
+            GameMode::Osu if self.variant == Variant::Relax2019 => {
+                Strains::Osu2019(osu_2019::strains_with_difficulty(self, map))
+            }
             GameMode::Osu => Strains::Osu(Osu::strains(self, map).expect("no conversion required")),
             GameMode::Taiko => {
                 Strains::Taiko(Taiko::strains(self, map).expect("no conversion required"))
@@ -383,6 +457,23 @@ impl Difficulty {
     pub(crate) fn get_lazer(&self) -> bool {
         self.lazer.unwrap_or(true)
     }
+
+    /// Same as [`Difficulty::get_lazer`] but without the default value.
+    pub(crate) const fn get_lazer_opt(&self) -> Option<bool> {
+        self.lazer
+    }
+
+    pub(crate) const fn get_variant(&self) -> Variant {
+        self.variant
+    }
+
+    pub(crate) fn get_stack_leniency(&self) -> bool {
+        self.stack_leniency.unwrap_or(false)
+    }
+
+    pub(crate) const fn get_relax_profile(&self) -> &RelaxProfile {
+        &self.relax_profile
+    }
 }
 
 fn non_zero_u64_to_f64(n: NonZeroU64) -> f64 {
@@ -401,6 +492,9 @@ impl Debug for Difficulty {
             od,
             hardrock_offsets,
             lazer,
+            variant,
+            stack_leniency,
+            relax_profile,
         } = self;
 
         f.debug_struct("Difficulty")
@@ -413,6 +507,9 @@ impl Debug for Difficulty {
             .field("od", od)
             .field("hardrock_offsets", hardrock_offsets)
             .field("lazer", lazer)
+            .field("variant", variant)
+            .field("stack_leniency", stack_leniency)
+            .field("relax_profile", relax_profile)
             .finish()
     }
 }
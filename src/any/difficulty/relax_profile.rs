@@ -0,0 +1,83 @@
+/// Tuning values of the relax and autopilot adjustments for osu!standard.
+///
+/// The default values are the ones used by the live leaderboards. Adjusting
+/// them allows comparing reworks of the relax and autopilot handling without
+/// modifying the crate itself.
+///
+/// # Example
+///
+/// ```
+/// use akatsuki_pp::{any::RelaxProfile, Beatmap, Difficulty};
+///
+/// let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+///
+/// let profile = RelaxProfile {
+///     rx_aim_multiplier: 0.95,
+///     ..RelaxProfile::default()
+/// };
+///
+/// let attrs = Difficulty::new()
+///     .mods(128) // RX
+///     .relax_profile(profile)
+///     .calculate(&map);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RelaxProfile {
+    /// Multiplier for the aim rating on relax.
+    pub rx_aim_multiplier: f64,
+    /// Multiplier for the speed rating on relax.
+    pub rx_speed_multiplier: f64,
+    /// Multiplier for the flashlight rating on relax.
+    pub rx_flashlight_multiplier: f64,
+    /// Multiplier for the stream aim rating on relax.
+    pub rx_stream_aim_multiplier: f64,
+    /// Multiplier for the aim rating on autopilot.
+    pub ap_aim_multiplier: f64,
+    /// Multiplier for the flashlight rating on autopilot.
+    pub ap_flashlight_multiplier: f64,
+    /// The OD at which 100s and 50s are fully counted as misses on relax.
+    pub rx_miss_od: f64,
+    /// Exponent of the OD ratio when counting 100s as misses on relax.
+    pub rx_n100_exponent: f64,
+    /// Exponent of the OD ratio when counting 50s as misses on relax.
+    pub rx_n50_exponent: f64,
+    /// Whether the AR bonus of the aim value is removed on relax.
+    pub rx_no_aim_ar_bonus: bool,
+    /// Whether the AR bonus of the tapping value is removed on autopilot.
+    pub ap_no_tapping_ar_bonus: bool,
+    /// Whether the aim value is zero on autopilot.
+    pub ap_no_aim_value: bool,
+    /// Whether the speed value is zero on relax.
+    pub rx_no_speed_value: bool,
+    /// Whether the accuracy value is zero on relax.
+    pub rx_no_acc_value: bool,
+}
+
+impl RelaxProfile {
+    /// The values used by the live leaderboards.
+    ///
+    /// Same as [`RelaxProfile::default`] but usable in `const` contexts.
+    pub const DEFAULT: Self = Self {
+        rx_aim_multiplier: 0.9,
+        rx_speed_multiplier: 0.0,
+        rx_flashlight_multiplier: 0.7,
+        rx_stream_aim_multiplier: 1.0,
+        ap_aim_multiplier: 0.0,
+        ap_flashlight_multiplier: 0.4,
+        // * OD13.33 is the value at which the great hitwindow becomes 0
+        rx_miss_od: 13.33,
+        rx_n100_exponent: 1.8,
+        rx_n50_exponent: 5.0,
+        rx_no_aim_ar_bonus: true,
+        ap_no_tapping_ar_bonus: true,
+        ap_no_aim_value: true,
+        rx_no_speed_value: true,
+        rx_no_acc_value: true,
+    };
+}
+
+impl Default for RelaxProfile {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
@@ -1,3 +1,5 @@
+use std::mem;
+
 use crate::util::strains_vec::StrainsVec;
 
 pub fn strain_decay(ms: f64, strain_decay_base: f64) -> f64 {
@@ -25,6 +27,36 @@ pub trait ISkill {
     type DifficultyObjects<'a>: ?Sized;
 }
 
+/// A skill whose only growing state are its strain peaks.
+///
+/// Checkpoints of gradual calculators store clones without the strain peaks
+/// and truncate the current ones when being restored.
+pub trait StrainPeaksSkill: Clone {
+    fn strain_peaks_mut(&mut self) -> &mut StrainsVec;
+
+    /// Clone the skill without its strain peaks and return their amount
+    /// alongside.
+    fn clone_without_history(&mut self) -> (Self, usize) {
+        let strain_peaks = mem::replace(self.strain_peaks_mut(), StrainsVec::with_capacity(0));
+        let len = strain_peaks.len();
+        let clone = self.clone();
+        *self.strain_peaks_mut() = strain_peaks;
+
+        (clone, len)
+    }
+
+    /// Replace the skill with a clone of [`clone_without_history`] whose
+    /// strain peaks are the current ones truncated to `len`.
+    ///
+    /// [`clone_without_history`]: StrainPeaksSkill::clone_without_history
+    fn restore_with_history(&mut self, mut clone: Self, len: usize) {
+        let mut strain_peaks = mem::replace(self.strain_peaks_mut(), StrainsVec::with_capacity(0));
+        strain_peaks.truncate(len);
+        *clone.strain_peaks_mut() = strain_peaks;
+        *self = clone;
+    }
+}
+
 #[derive(Clone)]
 pub struct StrainSkill {
     pub curr_section_peak: f64,
@@ -55,6 +87,11 @@ impl StrainSkill {
         self.curr_section_peak = initial_strain;
     }
 
+    /// Moves the strain peaks out, leaving an empty list behind.
+    pub fn take_strain_peaks(&mut self) -> StrainsVec {
+        mem::replace(&mut self.strain_peaks, StrainsVec::with_capacity(0))
+    }
+
     pub fn get_curr_strain_peaks(self) -> StrainsVec {
         let mut strain_peaks = self.strain_peaks;
         strain_peaks.push(self.curr_section_peak);
@@ -1,19 +1,28 @@
 pub use self::{
     attributes::{DifficultyAttributes, PerformanceAttributes},
+    binary::{AttributesHeader, DecodeError, ALGORITHM_VERSION},
     difficulty::{
         gradual::GradualDifficulty, inspect::InspectDifficulty, Difficulty, ModsDependent,
+        RelaxProfile, Variant,
     },
     performance::{
         gradual::GradualPerformance,
         into::{IntoModePerformance, IntoPerformance},
-        HitResultPriority, Performance,
+        judgement::{HitResult, JudgedObject, JudgementEvent, JudgementGradualPerformance},
+        HitResultPriority, Performance, ZeroedComponents,
     },
     score_state::ScoreState,
     strains::Strains,
 };
 
+#[cfg(feature = "serde")]
+pub use self::versioned::SERDE_VERSION;
+
 mod attributes;
+mod binary;
 pub(crate) mod difficulty;
 mod performance;
 mod score_state;
 mod strains;
+#[cfg(feature = "serde")]
+mod versioned;
@@ -1,11 +1,12 @@
 use rosu_map::section::general::GameMode;
 
 use crate::{
-    any::{PerformanceAttributes, ScoreState},
+    any::{PerformanceAttributes, ScoreState, Variant},
     catch::{Catch, CatchGradualPerformance},
     mania::{Mania, ManiaGradualPerformance},
     model::mode::{ConvertError, IGameMode},
     osu::{Osu, OsuGradualPerformance},
+    osu_2019::Osu2019GradualPerformance,
     taiko::{Taiko, TaikoGradualPerformance},
     Beatmap, Difficulty,
 };
@@ -27,6 +28,9 @@ use crate::{
 ///
 /// If you only want to calculate difficulty attributes use [`GradualDifficulty`] instead.
 ///
+/// If you receive individual judgements rather than score states, use
+/// [`JudgementGradualPerformance`] instead.
+///
 /// # Example
 ///
 /// ```
@@ -88,6 +92,7 @@ use crate::{
 /// [`next`]: GradualPerformance::next
 /// [`nth`]: GradualPerformance::nth
 /// [`GradualDifficulty`]: crate::GradualDifficulty
+/// [`JudgementGradualPerformance`]: crate::any::JudgementGradualPerformance
 // 504 vs 184 bytes is an acceptable difference and the Osu variant (424 bytes)
 // is likely the most used one anyway.
 #[allow(clippy::large_enum_variant)]
@@ -96,6 +101,7 @@ pub enum GradualPerformance {
     Taiko(TaikoGradualPerformance),
     Catch(CatchGradualPerformance),
     Mania(ManiaGradualPerformance),
+    Osu2019(Osu2019GradualPerformance),
 }
 
 impl GradualPerformance {
@@ -112,6 +118,9 @@ impl GradualPerformance {
         mode: GameMode,
     ) -> Result<Self, ConvertError> {
         match mode {
+            GameMode::Osu if difficulty.get_variant() == Variant::Relax2019 => {
+                Osu2019GradualPerformance::new(difficulty, map).map(Self::Osu2019)
+            }
             GameMode::Osu => Osu::gradual_performance(difficulty, map).map(Self::Osu),
             GameMode::Taiko => Taiko::gradual_performance(difficulty, map).map(Self::Taiko),
             GameMode::Catch => Catch::gradual_performance(difficulty, map).map(Self::Catch),
@@ -150,6 +159,57 @@ impl GradualPerformance {
             GradualPerformance::Mania(gradual) => gradual
                 .nth(state.into(), n)
                 .map(PerformanceAttributes::Mania),
+            GradualPerformance::Osu2019(gradual) => gradual
+                .nth(state.into(), n)
+                .map(PerformanceAttributes::Osu2019),
+        }
+    }
+
+    /// Store the state after every `interval` objects so that seeking
+    /// backwards with [`seek`] has to process at most `interval` objects.
+    ///
+    /// See [`GradualDifficulty::checkpoints`] for more info.
+    ///
+    /// [`seek`]: GradualPerformance::seek
+    /// [`GradualDifficulty::checkpoints`]: crate::GradualDifficulty::checkpoints
+    #[must_use]
+    pub fn checkpoints(self, interval: usize) -> Self {
+        match self {
+            Self::Osu(gradual) => Self::Osu(gradual.checkpoints(interval)),
+            Self::Taiko(gradual) => Self::Taiko(gradual.checkpoints(interval)),
+            Self::Catch(gradual) => Self::Catch(gradual.checkpoints(interval)),
+            Self::Mania(gradual) => Self::Mania(gradual.checkpoints(interval)),
+            Self::Osu2019(gradual) => Self::Osu2019(gradual.checkpoints(interval)),
+        }
+    }
+
+    /// Move to the object at index `idx`, which may be before or after the
+    /// current one, and calculate the performance attributes for the
+    /// resulting score state.
+    ///
+    /// See [`GradualDifficulty::seek`] for how many objects have to be
+    /// processed.
+    ///
+    /// Returns `None` if `idx` is out of bounds.
+    ///
+    /// [`GradualDifficulty::seek`]: crate::GradualDifficulty::seek
+    pub fn seek(&mut self, state: ScoreState, idx: usize) -> Option<PerformanceAttributes> {
+        match self {
+            GradualPerformance::Osu(gradual) => gradual
+                .seek(state.into(), idx)
+                .map(PerformanceAttributes::Osu),
+            GradualPerformance::Taiko(gradual) => gradual
+                .seek(state.into(), idx)
+                .map(PerformanceAttributes::Taiko),
+            GradualPerformance::Catch(gradual) => gradual
+                .seek(state.into(), idx)
+                .map(PerformanceAttributes::Catch),
+            GradualPerformance::Mania(gradual) => gradual
+                .seek(state.into(), idx)
+                .map(PerformanceAttributes::Mania),
+            GradualPerformance::Osu2019(gradual) => gradual
+                .seek(state.into(), idx)
+                .map(PerformanceAttributes::Osu2019),
         }
     }
 
@@ -161,6 +221,7 @@ impl GradualPerformance {
             GradualPerformance::Taiko(gradual) => gradual.len(),
             GradualPerformance::Catch(gradual) => gradual.len(),
             GradualPerformance::Mania(gradual) => gradual.len(),
+            GradualPerformance::Osu2019(gradual) => gradual.len(),
         }
     }
 }
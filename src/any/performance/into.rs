@@ -3,6 +3,7 @@ use rosu_map::section::general::GameMode;
 use crate::{
     any::{DifficultyAttributes, PerformanceAttributes},
     model::mode::IGameMode,
+    osu_2019::{OsuDifficultyAttributes, OsuPP, OsuPerformanceAttributes},
     Beatmap, Performance,
 };
 
@@ -124,6 +125,7 @@ impl<'a> IntoPerformance<'a> for DifficultyAttributes {
             Self::Taiko(attrs) => Performance::Taiko(attrs.into()),
             Self::Catch(attrs) => Performance::Catch(attrs.into()),
             Self::Mania(attrs) => Performance::Mania(attrs.into()),
+            Self::Osu2019(attrs) => attrs.into_performance(),
         }
     }
 }
@@ -135,6 +137,19 @@ impl<'a> IntoPerformance<'a> for PerformanceAttributes {
             Self::Taiko(attrs) => Performance::Taiko(attrs.difficulty.into()),
             Self::Catch(attrs) => Performance::Catch(attrs.difficulty.into()),
             Self::Mania(attrs) => Performance::Mania(attrs.difficulty.into()),
+            Self::Osu2019(attrs) => attrs.into_performance(),
         }
     }
 }
+
+impl<'a> IntoPerformance<'a> for OsuDifficultyAttributes {
+    fn into_performance(self) -> Performance<'a> {
+        Performance::Osu2019(OsuPP::from_attributes(self))
+    }
+}
+
+impl<'a> IntoPerformance<'a> for OsuPerformanceAttributes {
+    fn into_performance(self) -> Performance<'a> {
+        Performance::Osu2019(OsuPP::from_attributes(self.difficulty))
+    }
+}
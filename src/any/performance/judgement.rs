@@ -0,0 +1,445 @@
+use rosu_map::section::general::GameMode;
+
+use crate::{
+    any::{DifficultyAttributes, GradualDifficulty, PerformanceAttributes, ScoreState},
+    catch,
+    model::{hit_object::HitObject, mode::ConvertError},
+    Beatmap, Difficulty, Performance,
+};
+
+/// The kind of judgement that a hit object received.
+///
+/// Which kinds are relevant depends on the mode:
+///
+/// | Mode      | Relevant results |
+/// | --------- | ---------------- |
+/// | osu!standard | [`Great`], [`Ok`], [`Meh`], [`Miss`] for objects; [`LargeTickHit`] for slider ticks and repeats (and heads with `CL`); [`SliderTailHit`] (or [`SmallTickHit`] with `CL`) for slider ends |
+/// | osu!taiko | [`Great`], [`Ok`], [`Miss`] |
+/// | osu!catch | [`Great`], [`Miss`] for fruits; [`LargeTickHit`], [`LargeTickMiss`] for droplets; [`SmallTickHit`], [`SmallTickMiss`] for tiny droplets |
+/// | osu!mania | [`Perfect`], [`Great`], [`Good`], [`Ok`], [`Meh`], [`Miss`] |
+///
+/// Results that are irrelevant for a mode are ignored.
+///
+/// [`Perfect`]: HitResult::Perfect
+/// [`Great`]: HitResult::Great
+/// [`Good`]: HitResult::Good
+/// [`Ok`]: HitResult::Ok
+/// [`Meh`]: HitResult::Meh
+/// [`Miss`]: HitResult::Miss
+/// [`LargeTickHit`]: HitResult::LargeTickHit
+/// [`LargeTickMiss`]: HitResult::LargeTickMiss
+/// [`SmallTickHit`]: HitResult::SmallTickHit
+/// [`SmallTickMiss`]: HitResult::SmallTickMiss
+/// [`SliderTailHit`]: HitResult::SliderTailHit
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum HitResult {
+    /// n320 in osu!mania.
+    Perfect,
+    /// 300s, fruits in osu!catch.
+    Great,
+    /// n200 in osu!mania.
+    Good,
+    /// 100s, 150s in osu!taiko.
+    Ok,
+    /// 50s.
+    Meh,
+    /// A missed object.
+    Miss,
+    /// A hit slider tick or repeat, or droplet in osu!catch.
+    LargeTickHit,
+    /// A missed slider tick or repeat, or droplet in osu!catch.
+    LargeTickMiss,
+    /// A hit tiny droplet in osu!catch, or slider end in osu!standard with
+    /// `CL`.
+    SmallTickHit,
+    /// A missed tiny droplet in osu!catch, or slider end in osu!standard with
+    /// `CL`.
+    SmallTickMiss,
+    /// A hit slider end in osu!standard without `CL`.
+    SliderTailHit,
+}
+
+impl HitResult {
+    /// Whether the result concludes one of the objects that gradual
+    /// calculators process one by one.
+    const fn concludes_object(self, mode: GameMode) -> bool {
+        match mode {
+            GameMode::Osu => matches!(self, Self::Great | Self::Ok | Self::Meh | Self::Miss),
+            GameMode::Taiko => matches!(self, Self::Great | Self::Ok | Self::Miss),
+            GameMode::Catch => matches!(
+                self,
+                Self::Great | Self::Miss | Self::LargeTickHit | Self::LargeTickMiss
+            ),
+            GameMode::Mania => matches!(
+                self,
+                Self::Perfect | Self::Great | Self::Good | Self::Ok | Self::Meh | Self::Miss
+            ),
+        }
+    }
+
+    /// Add the result to the given [`ScoreState`].
+    const fn apply(self, mode: GameMode, state: &mut ScoreState) {
+        match (mode, self) {
+            (GameMode::Mania, Self::Perfect) => state.n_geki += 1,
+            (GameMode::Mania, Self::Good) => state.n_katu += 1,
+            (GameMode::Osu | GameMode::Taiko | GameMode::Catch | GameMode::Mania, Self::Great) => {
+                state.n300 += 1;
+            }
+            (GameMode::Osu | GameMode::Taiko | GameMode::Mania, Self::Ok)
+            | (GameMode::Catch, Self::LargeTickHit) => state.n100 += 1,
+            (GameMode::Osu | GameMode::Mania, Self::Meh)
+            | (GameMode::Catch, Self::SmallTickHit) => {
+                state.n50 += 1;
+            }
+            (GameMode::Catch, Self::SmallTickMiss) => state.n_katu += 1,
+            (_, Self::Miss) | (GameMode::Catch, Self::LargeTickMiss) => state.misses += 1,
+            (GameMode::Osu, Self::LargeTickHit) => state.osu_large_tick_hits += 1,
+            (GameMode::Osu, Self::SliderTailHit | Self::SmallTickHit) => {
+                state.slider_end_hits += 1;
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The object that a [`JudgementEvent`] belongs to.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum JudgedObject {
+    /// Zero-based index among the objects that gradual calculators process
+    /// one by one, i.e. hit objects in osu!standard and osu!mania, circles in
+    /// osu!taiko, and fruits and droplets in osu!catch.
+    ///
+    /// Nested objects such as slider ticks or tiny droplets should use the
+    /// index of the last concluded object.
+    Index(usize),
+    /// The start time of the judged object in milliseconds, *not* adjusted by
+    /// the clock rate.
+    ///
+    /// For nested objects such as slider ticks or tiny droplets, their own
+    /// start time may be used.
+    Time(f64),
+}
+
+/// A single judgement of a play, e.g. received from a spectated score.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct JudgementEvent {
+    /// The object that was judged.
+    pub object: JudgedObject,
+    /// The judgement's result.
+    pub result: HitResult,
+    /// The current combo after the judgement.
+    pub combo: u32,
+}
+
+/// Gradually calculate the performance attributes on maps of any mode based
+/// on a stream of [`JudgementEvent`]s.
+///
+/// Unlike [`GradualPerformance`], this struct accumulates the [`ScoreState`]
+/// itself and keeps track of which object needs to be processed next.
+///
+/// Events that conclude an object, e.g. a slider's [`HitResult::Great`] or a
+/// droplet's [`HitResult::LargeTickHit`], process all objects up to the
+/// event's [`JudgedObject`]. Other events, e.g. slider ticks or tiny droplets,
+/// only update the score state.
+///
+/// # Example
+///
+/// ```
+/// use akatsuki_pp::{Beatmap, Difficulty};
+/// use akatsuki_pp::any::{
+///     HitResult, JudgedObject, JudgementEvent, JudgementGradualPerformance,
+/// };
+///
+/// let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+/// let difficulty = Difficulty::new().mods(64); // DT
+/// let mut gradual = JudgementGradualPerformance::new(difficulty, &map);
+///
+/// // The first object is hit as 300
+/// let event = JudgementEvent {
+///     object: JudgedObject::Index(0),
+///     result: HitResult::Great,
+///     combo: 1,
+/// };
+///
+/// let attrs = gradual.process(event).unwrap();
+/// println!("PP: {}", attrs.pp());
+///
+/// // The next object at 1234ms is missed
+/// let event = JudgementEvent {
+///     object: JudgedObject::Time(1234.0),
+///     result: HitResult::Miss,
+///     combo: 0,
+/// };
+///
+/// let attrs = gradual.process(event).unwrap();
+/// println!("PP: {}", attrs.pp());
+///
+/// assert_eq!(gradual.state().n300, 1);
+/// assert_eq!(gradual.state().misses, 1);
+/// ```
+///
+/// [`GradualPerformance`]: crate::GradualPerformance
+pub struct JudgementGradualPerformance {
+    mode: GameMode,
+    difficulty: Difficulty,
+    gradual: GradualDifficulty,
+    /// The start time of each object that is processed one by one
+    object_times: Box<[f64]>,
+    /// The amount of processed objects
+    processed: usize,
+    /// The amount of received events that concluded an object
+    concluded: usize,
+    attrs: Option<DifficultyAttributes>,
+    state: ScoreState,
+}
+
+impl JudgementGradualPerformance {
+    /// Create a [`JudgementGradualPerformance`] for a map of any mode.
+    #[allow(clippy::missing_panics_doc)]
+    pub fn new(difficulty: Difficulty, map: &Beatmap) -> Self {
+        Self::new_with_mode(difficulty, map, map.mode).expect("no conversion required")
+    }
+
+    /// Create a [`JudgementGradualPerformance`] for a [`Beatmap`] on a
+    /// specific [`GameMode`].
+    pub fn new_with_mode(
+        difficulty: Difficulty,
+        map: &Beatmap,
+        mode: GameMode,
+    ) -> Result<Self, ConvertError> {
+        let gradual = GradualDifficulty::new_with_mode(difficulty.clone(), map, mode)?;
+        let map = map.convert_ref(mode, difficulty.get_mods())?;
+
+        let mut object_times = match mode {
+            GameMode::Osu | GameMode::Mania => {
+                map.hit_objects.iter().map(|h| h.start_time).collect()
+            }
+            GameMode::Taiko => map
+                .hit_objects
+                .iter()
+                .filter(|h| HitObject::is_circle(h))
+                .map(|h| h.start_time)
+                .collect(),
+            GameMode::Catch => catch::palpable_object_times(&map),
+        };
+
+        object_times.truncate(gradual.len());
+
+        // Ensure the times are sorted to search through them
+        let mut max_time = f64::NEG_INFINITY;
+
+        for time in object_times.iter_mut() {
+            max_time = max_time.max(*time);
+            *time = max_time;
+        }
+
+        Ok(Self {
+            mode,
+            difficulty,
+            gradual,
+            object_times: object_times.into_boxed_slice(),
+            processed: 0,
+            concluded: 0,
+            attrs: None,
+            state: ScoreState::new(),
+        })
+    }
+
+    /// Process a judgement and calculate the performance attributes for the
+    /// resulting score state.
+    ///
+    /// Returns `None` if no object has been concluded yet.
+    pub fn process(&mut self, event: JudgementEvent) -> Option<PerformanceAttributes> {
+        self.apply(event);
+
+        self.performance()
+    }
+
+    /// Process all given judgements and calculate the performance attributes
+    /// only for the final score state.
+    ///
+    /// Returns `None` if no object has been concluded yet.
+    pub fn process_all<I>(&mut self, events: I) -> Option<PerformanceAttributes>
+    where
+        I: IntoIterator<Item = JudgementEvent>,
+    {
+        for event in events {
+            self.apply(event);
+        }
+
+        self.performance()
+    }
+
+    /// The score state accumulated from all processed judgements.
+    pub const fn state(&self) -> &ScoreState {
+        &self.state
+    }
+
+    /// Returns the amount of remaining objects.
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        self.gradual.len()
+    }
+
+    fn apply(&mut self, event: JudgementEvent) {
+        let JudgementEvent {
+            object,
+            result,
+            combo,
+        } = event;
+
+        if result.concludes_object(self.mode) {
+            self.concluded += 1;
+
+            let target = match object {
+                JudgedObject::Index(idx) => idx.saturating_add(1),
+                JudgedObject::Time(time) => self.object_times.partition_point(|&t| t <= time),
+            };
+
+            // Each concluding event accounts for at least one object
+            self.advance(target.max(self.concluded));
+        }
+
+        result.apply(self.mode, &mut self.state);
+        self.state.max_combo = self.state.max_combo.max(combo);
+    }
+
+    fn advance(&mut self, target: usize) {
+        let target = target.min(self.object_times.len());
+
+        if target > self.processed {
+            self.attrs = self.gradual.nth(target - self.processed - 1);
+            self.processed = target;
+        }
+    }
+
+    fn performance(&self) -> Option<PerformanceAttributes> {
+        let attrs = self.attrs.clone()?;
+
+        let performance = Performance::new(attrs)
+            .difficulty(self.difficulty.clone())
+            .passed_objects(self.processed as u32)
+            .state(self.state.clone())
+            .calculate();
+
+        Some(performance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::GradualPerformance;
+
+    use super::*;
+
+    const fn event(object: JudgedObject, result: HitResult, combo: u32) -> JudgementEvent {
+        JudgementEvent {
+            object,
+            result,
+            combo,
+        }
+    }
+
+    #[test]
+    fn osu_matches_gradual() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+        let difficulty = Difficulty::new().mods(88); // HDHRDT
+
+        let mut gradual = GradualPerformance::new(difficulty.clone(), &map);
+        let mut events = JudgementGradualPerformance::new(difficulty, &map);
+        let mut state = ScoreState::new();
+
+        for (i, h) in map.hit_objects.iter().enumerate().take(50) {
+            // Every tenth object is a miss
+            let (object, result, combo) = if i % 10 == 9 {
+                state.misses += 1;
+
+                (JudgedObject::Time(h.start_time), HitResult::Miss, 0)
+            } else {
+                state.n300 += 1;
+                state.max_combo = state.max_combo.max(i as u32 % 10 + 1);
+
+                (JudgedObject::Index(i), HitResult::Great, i as u32 % 10 + 1)
+            };
+
+            let expected = gradual.next(state.clone()).unwrap();
+            let actual = events.process(event(object, result, combo)).unwrap();
+
+            assert_eq!(actual, expected, "i={i}");
+            assert_eq!(events.state(), &state);
+        }
+
+        assert_eq!(events.len(), gradual.len());
+    }
+
+    #[test]
+    fn nested_results_and_skipping() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+        let mut gradual = JudgementGradualPerformance::new(Difficulty::new(), &map);
+        let total = gradual.len();
+
+        // No object has been concluded yet
+        let tick = event(JudgedObject::Index(0), HitResult::LargeTickHit, 1);
+        assert!(gradual.process(tick).is_none());
+        assert_eq!(gradual.len(), total);
+
+        let tail = event(JudgedObject::Index(0), HitResult::SliderTailHit, 2);
+        let head = event(JudgedObject::Index(0), HitResult::Great, 3);
+        assert!(gradual.process_all([tail, head]).is_some());
+        assert_eq!(gradual.len(), total - 1);
+
+        // Skip ahead to the tenth object
+        let time = map.hit_objects[9].start_time;
+        let attrs = gradual
+            .process(event(JudgedObject::Time(time), HitResult::Ok, 4))
+            .unwrap();
+
+        assert_eq!(gradual.len(), total - 10);
+
+        let state = gradual.state();
+        assert_eq!(state.osu_large_tick_hits, 1);
+        assert_eq!(state.slider_end_hits, 1);
+        assert_eq!((state.n300, state.n100, state.max_combo), (1, 1, 4));
+
+        let expected = Performance::new(&map)
+            .passed_objects(10)
+            .state(state.clone())
+            .calculate();
+
+        assert!((attrs.pp() - expected.pp()).abs() < 1e-9);
+
+        // A result for an already processed object does not process more
+        gradual.process(event(JudgedObject::Index(5), HitResult::Meh, 0));
+        assert_eq!(gradual.len(), total - 10);
+        assert_eq!(gradual.state().n50, 1);
+    }
+
+    #[test]
+    fn catch_droplets() {
+        let map = Beatmap::from_path("./resources/2118524.osu").unwrap();
+        let mut gradual = JudgementGradualPerformance::new(Difficulty::new(), &map);
+        let total = gradual.len();
+
+        let events = [
+            event(JudgedObject::Index(0), HitResult::Great, 1),
+            event(JudgedObject::Index(1), HitResult::LargeTickHit, 2),
+            event(JudgedObject::Index(1), HitResult::SmallTickHit, 2),
+            event(JudgedObject::Index(1), HitResult::SmallTickMiss, 2),
+            event(JudgedObject::Index(2), HitResult::LargeTickMiss, 0),
+        ];
+
+        let attrs = gradual.process_all(events).unwrap();
+
+        assert_eq!(gradual.len(), total - 3);
+
+        let state = gradual.state();
+        assert_eq!((state.n300, state.n100, state.n50), (1, 1, 1));
+        assert_eq!((state.n_katu, state.misses, state.max_combo), (1, 1, 2));
+
+        let PerformanceAttributes::Catch(attrs) = attrs else {
+            panic!("expected catch attributes");
+        };
+
+        assert_eq!(attrs.difficulty.n_fruits + attrs.difficulty.n_droplets, 3);
+    }
+}
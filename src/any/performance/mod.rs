@@ -1,16 +1,21 @@
 use rosu_map::section::general::GameMode;
 
 use crate::{
-    catch::CatchPerformance, mania::ManiaPerformance, osu::OsuPerformance, taiko::TaikoPerformance,
-    Difficulty, GameMods,
+    catch::CatchPerformance, mania::ManiaPerformance, osu::OsuPerformance, osu_2019::OsuPP,
+    taiko::TaikoPerformance, Difficulty, GameMods,
 };
 
 use self::into::IntoPerformance;
 
-use super::{attributes::PerformanceAttributes, score_state::ScoreState};
+use super::{
+    attributes::PerformanceAttributes,
+    difficulty::{RelaxProfile, Variant},
+    score_state::ScoreState,
+};
 
 pub mod gradual;
 pub mod into;
+pub mod judgement;
 
 /// Performance calculator on maps of any mode.
 #[derive(Clone, Debug, PartialEq)]
@@ -20,6 +25,10 @@ pub enum Performance<'map> {
     Taiko(TaikoPerformance<'map>),
     Catch(CatchPerformance<'map>),
     Mania(ManiaPerformance<'map>),
+    /// osu!standard performance calculator of the 2019 algorithm.
+    ///
+    /// See [`Variant::Relax2019`].
+    Osu2019(OsuPP<'map>),
 }
 
 impl<'map> Performance<'map> {
@@ -64,6 +73,7 @@ impl<'map> Performance<'map> {
             Self::Mania(m) => {
                 PerformanceAttributes::Mania(m.calculate().expect("no conversion required"))
             }
+            Self::Osu2019(o) => PerformanceAttributes::Osu2019(o.calculate()),
         }
     }
 
@@ -73,6 +83,10 @@ impl<'map> Performance<'map> {
     /// contained, i.e. if this [`Performance`] was created through attributes
     /// or [`Performance::generate_state`] was called.
     ///
+    /// [`Performance::Osu2019`] can only be converted to
+    /// [`GameMode::Osu`]; use [`Performance::variant`] first to switch
+    /// back to the default algorithm.
+    ///
     /// If the given mode should be ignored in case of an error, use
     /// [`mode_or_ignore`] instead.
     ///
@@ -82,7 +96,8 @@ impl<'map> Performance<'map> {
     pub fn try_mode(self, mode: GameMode) -> Result<Self, Self> {
         match (self, mode) {
             (Self::Osu(o), _) => o.try_mode(mode).map_err(Self::Osu),
-            (this @ Self::Taiko(_), GameMode::Taiko)
+            (this @ Self::Osu2019(_), GameMode::Osu)
+            | (this @ Self::Taiko(_), GameMode::Taiko)
             | (this @ Self::Catch(_), GameMode::Catch)
             | (this @ Self::Mania(_), GameMode::Mania) => Ok(this),
             (this, _) => Err(this),
@@ -122,16 +137,50 @@ impl<'map> Performance<'map> {
             Self::Taiko(t) => Self::Taiko(t.mods(mods)),
             Self::Catch(f) => Self::Catch(f.mods(mods)),
             Self::Mania(m) => Self::Mania(m.mods(mods)),
+            Self::Osu2019(o) => Self::Osu2019(o.mods(mods)),
         }
     }
 
     /// Use the specified settings of the given [`Difficulty`].
+    ///
+    /// For osu!standard, the [`Variant`] of the [`Difficulty`] is applied
+    /// through [`Performance::variant`].
     pub fn difficulty(self, difficulty: Difficulty) -> Self {
-        match self {
+        match self.variant(difficulty.get_variant()) {
             Self::Osu(o) => Self::Osu(o.difficulty(difficulty)),
             Self::Taiko(t) => Self::Taiko(t.difficulty(difficulty)),
             Self::Catch(f) => Self::Catch(f.difficulty(difficulty)),
             Self::Mania(m) => Self::Mania(m.difficulty(difficulty)),
+            Self::Osu2019(o) => Self::Osu2019(o.difficulty(difficulty)),
+        }
+    }
+
+    /// Specify the algorithm for osu!standard calculations.
+    ///
+    /// Switching between [`Variant`]s requires the beatmap so if this
+    /// [`Performance`] was created through attributes or
+    /// [`Performance::generate_state`] was called, nothing changes.
+    ///
+    /// Irrelevant for other modes.
+    pub fn variant(self, variant: Variant) -> Self {
+        match (self, variant) {
+            (Self::Osu(o), Variant::Relax2019) => match OsuPP::try_from(o) {
+                Ok(mut o) => {
+                    o.difficulty = o.difficulty.variant(variant);
+
+                    Self::Osu2019(o)
+                }
+                Err(o) => Self::Osu(o),
+            },
+            (Self::Osu2019(o), Variant::Default) => match OsuPerformance::try_from(o) {
+                Ok(mut o) => {
+                    o.difficulty = o.difficulty.variant(variant);
+
+                    Self::Osu(o)
+                }
+                Err(o) => Self::Osu2019(o),
+            },
+            (this, _) => this,
         }
     }
 
@@ -148,6 +197,7 @@ impl<'map> Performance<'map> {
             Self::Taiko(t) => Self::Taiko(t.passed_objects(passed_objects)),
             Self::Catch(f) => Self::Catch(f.passed_objects(passed_objects)),
             Self::Mania(m) => Self::Mania(m.passed_objects(passed_objects)),
+            Self::Osu2019(o) => Self::Osu2019(o.passed_objects(passed_objects)),
         }
     }
 
@@ -165,6 +215,7 @@ impl<'map> Performance<'map> {
             Self::Taiko(t) => Self::Taiko(t.clock_rate(clock_rate)),
             Self::Catch(f) => Self::Catch(f.clock_rate(clock_rate)),
             Self::Mania(m) => Self::Mania(m.clock_rate(clock_rate)),
+            Self::Osu2019(o) => Self::Osu2019(o.clock_rate(clock_rate)),
         }
     }
 
@@ -183,6 +234,7 @@ impl<'map> Performance<'map> {
         match self {
             Self::Osu(o) => Self::Osu(o.ar(ar, with_mods)),
             Self::Catch(c) => Self::Catch(c.ar(ar, with_mods)),
+            Self::Osu2019(o) => Self::Osu2019(o.ar(ar, with_mods)),
             Self::Taiko(_) | Self::Mania(_) => self,
         }
     }
@@ -202,6 +254,7 @@ impl<'map> Performance<'map> {
         match self {
             Self::Osu(o) => Self::Osu(o.cs(cs, with_mods)),
             Self::Catch(c) => Self::Catch(c.cs(cs, with_mods)),
+            Self::Osu2019(o) => Self::Osu2019(o.cs(cs, with_mods)),
             Self::Taiko(_) | Self::Mania(_) => self,
         }
     }
@@ -221,6 +274,7 @@ impl<'map> Performance<'map> {
             Self::Taiko(t) => Self::Taiko(t.hp(hp, with_mods)),
             Self::Catch(c) => Self::Catch(c.hp(hp, with_mods)),
             Self::Mania(m) => Self::Mania(m.hp(hp, with_mods)),
+            Self::Osu2019(o) => Self::Osu2019(o.hp(hp, with_mods)),
         }
     }
 
@@ -239,6 +293,7 @@ impl<'map> Performance<'map> {
             Self::Taiko(t) => Self::Taiko(t.od(od, with_mods)),
             Self::Catch(c) => Self::Catch(c.od(od, with_mods)),
             Self::Mania(m) => Self::Mania(m.od(od, with_mods)),
+            Self::Osu2019(o) => Self::Osu2019(o.od(od, with_mods)),
         }
     }
 
@@ -260,16 +315,22 @@ impl<'map> Performance<'map> {
             Self::Taiko(t) => Self::Taiko(t.state(state.into())),
             Self::Catch(f) => Self::Catch(f.state(state.into())),
             Self::Mania(m) => Self::Mania(m.state(state.into())),
+            Self::Osu2019(o) => Self::Osu2019(o.state(state.into())),
         }
     }
 
     /// Set the accuracy between `0.0` and `100.0`.
+    ///
+    /// For all variants, including [`Performance::Osu2019`], hitresults are
+    /// only generated once the performance is calculated so the order in
+    /// which misses and other parameters are specified does not matter.
     pub fn accuracy(self, acc: f64) -> Self {
         match self {
             Self::Osu(o) => Self::Osu(o.accuracy(acc)),
             Self::Taiko(t) => Self::Taiko(t.accuracy(acc)),
             Self::Catch(f) => Self::Catch(f.accuracy(acc)),
             Self::Mania(m) => Self::Mania(m.accuracy(acc)),
+            Self::Osu2019(o) => Self::Osu2019(o.accuracy(acc as f32)),
         }
     }
 
@@ -280,6 +341,7 @@ impl<'map> Performance<'map> {
             Self::Taiko(t) => Self::Taiko(t.misses(n_misses)),
             Self::Catch(f) => Self::Catch(f.misses(n_misses)),
             Self::Mania(m) => Self::Mania(m.misses(n_misses)),
+            Self::Osu2019(o) => Self::Osu2019(o.misses(n_misses)),
         }
     }
 
@@ -291,6 +353,7 @@ impl<'map> Performance<'map> {
             Self::Osu(o) => Self::Osu(o.combo(combo)),
             Self::Taiko(t) => Self::Taiko(t.combo(combo)),
             Self::Catch(f) => Self::Catch(f.combo(combo)),
+            Self::Osu2019(o) => Self::Osu2019(o.combo(combo)),
             Self::Mania(_) => self,
         }
     }
@@ -304,6 +367,7 @@ impl<'map> Performance<'map> {
             Self::Taiko(t) => Self::Taiko(t.hitresult_priority(priority)),
             Self::Catch(_) => self,
             Self::Mania(m) => Self::Mania(m.hitresult_priority(priority)),
+            Self::Osu2019(o) => Self::Osu2019(o.hitresult_priority(priority)),
         }
     }
 
@@ -315,12 +379,57 @@ impl<'map> Performance<'map> {
     /// This affects internal accuracy calculation because lazer considers
     /// slider heads for accuracy whereas stable does not.
     ///
-    /// Only relevant for osu!standard and osu!mania.
+    /// Only relevant for osu!standard and osu!mania. For
+    /// [`Performance::Osu2019`], this defaults to `false` instead.
     pub fn lazer(self, lazer: bool) -> Self {
         match self {
             Self::Osu(o) => Self::Osu(o.lazer(lazer)),
             Self::Taiko(_) | Self::Catch(_) => self,
             Self::Mania(m) => Self::Mania(m.lazer(lazer)),
+            Self::Osu2019(o) => Self::Osu2019(o.lazer(lazer)),
+        }
+    }
+
+    /// Adjust the relax and autopilot handling.
+    ///
+    /// Only relevant for osu!standard with [`Variant::Default`].
+    ///
+    /// Defaults to [`RelaxProfile::DEFAULT`].
+    pub fn relax_profile(self, relax_profile: RelaxProfile) -> Self {
+        match self {
+            Self::Osu(o) => Self::Osu(o.relax_profile(relax_profile)),
+            Self::Taiko(_) | Self::Catch(_) | Self::Mania(_) | Self::Osu2019(_) => self,
+        }
+    }
+
+    /// Specify the measured unstable rate of the play.
+    ///
+    /// The value must already be adjusted by the clock rate, i.e. as displayed
+    /// by osu!.
+    ///
+    /// Only relevant for osu!standard, osu!taiko, and osu!mania.
+    pub fn unstable_rate(self, unstable_rate: f64) -> Self {
+        match self {
+            Self::Osu(o) => Self::Osu(o.unstable_rate(unstable_rate)),
+            Self::Taiko(t) => Self::Taiko(t.unstable_rate(unstable_rate)),
+            Self::Mania(m) => Self::Mania(m.unstable_rate(unstable_rate)),
+            Self::Catch(_) | Self::Osu2019(_) => self,
+        }
+    }
+
+    /// Specify the hit offsets of the play in milliseconds to measure the
+    /// unstable rate.
+    ///
+    /// The offsets must be in map time, i.e. they will still be adjusted by
+    /// the clock rate.
+    ///
+    /// Only relevant for osu!standard, osu!taiko, and osu!mania.
+    pub fn hit_offsets(self, hit_offsets: impl IntoIterator<Item = f64>) -> Self {
+        match self {
+            Self::Osu(o) => Self::Osu(o.hit_offsets(hit_offsets)),
+            Self::Taiko(t) => Self::Taiko(t.hit_offsets(hit_offsets)),
+            Self::Mania(m) => Self::Mania(m.hit_offsets(hit_offsets)),
+            Self::Catch(_) | Self::Osu2019(_) => self,
         }
     }
 
@@ -335,10 +444,10 @@ impl<'map> Performance<'map> {
     /// - if set on osu!lazer *with* `CL`, this value is the amount of hit
     ///   slider heads, ticks, and repeats
     pub fn large_tick_hits(self, large_tick_hits: u32) -> Self {
-        if let Self::Osu(osu) = self {
-            Self::Osu(osu.n_large_ticks(large_tick_hits))
-        } else {
-            self
+        match self {
+            Self::Osu(o) => Self::Osu(o.n_large_ticks(large_tick_hits)),
+            Self::Osu2019(o) => Self::Osu2019(o.n_large_ticks(large_tick_hits)),
+            Self::Taiko(_) | Self::Catch(_) | Self::Mania(_) => self,
         }
     }
 
@@ -349,10 +458,10 @@ impl<'map> Performance<'map> {
     /// osu! calls this value "slider tail hits" without the classic
     /// mod and "small tick hits" with the classic mod.
     pub fn n_slider_ends(self, n_slider_ends: u32) -> Self {
-        if let Self::Osu(osu) = self {
-            Self::Osu(osu.n_slider_ends(n_slider_ends))
-        } else {
-            self
+        match self {
+            Self::Osu(o) => Self::Osu(o.n_slider_ends(n_slider_ends)),
+            Self::Osu2019(o) => Self::Osu2019(o.n_slider_ends(n_slider_ends)),
+            Self::Taiko(_) | Self::Catch(_) | Self::Mania(_) => self,
         }
     }
 
@@ -363,6 +472,7 @@ impl<'map> Performance<'map> {
             Self::Taiko(t) => Self::Taiko(t.n300(n300)),
             Self::Catch(f) => Self::Catch(f.fruits(n300)),
             Self::Mania(m) => Self::Mania(m.n300(n300)),
+            Self::Osu2019(o) => Self::Osu2019(o.n300(n300)),
         }
     }
 
@@ -373,6 +483,7 @@ impl<'map> Performance<'map> {
             Self::Taiko(t) => Self::Taiko(t.n100(n100)),
             Self::Catch(f) => Self::Catch(f.droplets(n100)),
             Self::Mania(m) => Self::Mania(m.n100(n100)),
+            Self::Osu2019(o) => Self::Osu2019(o.n100(n100)),
         }
     }
 
@@ -385,6 +496,7 @@ impl<'map> Performance<'map> {
             Self::Taiko(_) => self,
             Self::Catch(f) => Self::Catch(f.tiny_droplets(n50)),
             Self::Mania(m) => Self::Mania(m.n50(n50)),
+            Self::Osu2019(o) => Self::Osu2019(o.n50(n50)),
         }
     }
 
@@ -394,7 +506,7 @@ impl<'map> Performance<'map> {
     /// droplet misses and osu!mania for which it repesents the amount of n200.
     pub fn n_katu(self, n_katu: u32) -> Self {
         match self {
-            Self::Osu(_) | Self::Taiko(_) => self,
+            Self::Osu(_) | Self::Taiko(_) | Self::Osu2019(_) => self,
             Self::Catch(f) => Self::Catch(f.tiny_droplet_misses(n_katu)),
             Self::Mania(m) => Self::Mania(m.n200(n_katu)),
         }
@@ -406,7 +518,7 @@ impl<'map> Performance<'map> {
     /// amount of n320.
     pub fn n_geki(self, n_geki: u32) -> Self {
         match self {
-            Self::Osu(_) | Self::Taiko(_) | Self::Catch(_) => self,
+            Self::Osu(_) | Self::Taiko(_) | Self::Catch(_) | Self::Osu2019(_) => self,
             Self::Mania(m) => Self::Mania(m.n320(n_geki)),
         }
     }
@@ -419,6 +531,119 @@ impl<'map> Performance<'map> {
             Self::Taiko(t) => t.generate_state().expect("no conversion required").into(),
             Self::Catch(f) => f.generate_state().expect("no conversion required").into(),
             Self::Mania(m) => m.generate_state().expect("no conversion required").into(),
+            Self::Osu2019(o) => o.generate_state().into(),
+        }
+    }
+
+    /// Adjust the current score so that it becomes a full combo.
+    ///
+    /// Misses are turned into the mode's best judgement and all other
+    /// judgements are kept. See the mode-specific `if_fc` methods for
+    /// details, e.g. osu!catch keeps tiny droplet misses and osu!mania turns
+    /// misses into n320s.
+    #[allow(clippy::missing_panics_doc)]
+    pub fn if_fc(self) -> Self {
+        match self {
+            Self::Osu(o) => Self::Osu(o.if_fc().expect("no conversion required")),
+            Self::Taiko(t) => Self::Taiko(t.if_fc().expect("no conversion required")),
+            Self::Catch(f) => Self::Catch(f.if_fc().expect("no conversion required")),
+            Self::Mania(m) => Self::Mania(m.if_fc().expect("no conversion required")),
+            Self::Osu2019(o) => Self::Osu2019(o.if_fc()),
+        }
+    }
+
+    /// Modify the current [`ScoreState`] through the given function.
+    ///
+    /// The mode's state is converted into a [`ScoreState`] for the function
+    /// and back afterwards so fields that the mode doesn't use are ignored.
+    #[allow(clippy::missing_panics_doc)]
+    pub fn what_if<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut ScoreState),
+    {
+        fn adjust<S, F>(state: &mut S, f: F)
+        where
+            S: Clone + Into<ScoreState> + From<ScoreState>,
+            F: FnOnce(&mut ScoreState),
+        {
+            let mut any_state = state.clone().into();
+            f(&mut any_state);
+            *state = any_state.into();
+        }
+
+        match self {
+            Self::Osu(o) => Self::Osu(
+                o.what_if(|state| adjust(state, f))
+                    .expect("no conversion required"),
+            ),
+            Self::Taiko(t) => Self::Taiko(
+                t.what_if(|state| adjust(state, f))
+                    .expect("no conversion required"),
+            ),
+            Self::Catch(c) => Self::Catch(
+                c.what_if(|state| adjust(state, f))
+                    .expect("no conversion required"),
+            ),
+            Self::Mania(m) => Self::Mania(
+                m.what_if(|state| adjust(state, f))
+                    .expect("no conversion required"),
+            ),
+            Self::Osu2019(o) => Self::Osu2019(o.what_if(|state| adjust(state, f))),
+        }
+    }
+
+    /// Find the minimal accuracy between `0.0` and `100.0` that is required
+    /// to reach the given amount of pp.
+    ///
+    /// Hitresults are generated from the accuracy while all other parameters
+    /// such as mods, misses, or combo are kept as they are.
+    ///
+    /// Returns `None` if the pp can't be reached even with 100% accuracy.
+    #[allow(clippy::missing_panics_doc)]
+    pub fn accuracy_for_pp(self, pp: f64) -> Option<f64> {
+        match self {
+            Self::Osu(o) => o.accuracy_for_pp(pp).expect("no conversion required"),
+            Self::Taiko(t) => t.accuracy_for_pp(pp).expect("no conversion required"),
+            Self::Catch(f) => f.accuracy_for_pp(pp).expect("no conversion required"),
+            Self::Mania(m) => m.accuracy_for_pp(pp).expect("no conversion required"),
+            Self::Osu2019(o) => o.accuracy_for_pp(pp),
+        }
+    }
+
+    /// Find the maximal amount of misses for which the given amount of pp
+    /// can still be reached.
+    ///
+    /// Returns `None` if the pp can't be reached even without misses.
+    #[allow(clippy::missing_panics_doc)]
+    pub fn misses_for_pp(self, pp: f64) -> Option<u32> {
+        match self {
+            Self::Osu(o) => o.misses_for_pp(pp).expect("no conversion required"),
+            Self::Taiko(t) => t.misses_for_pp(pp).expect("no conversion required"),
+            Self::Catch(f) => f.misses_for_pp(pp).expect("no conversion required"),
+            Self::Mania(m) => m.misses_for_pp(pp).expect("no conversion required"),
+            Self::Osu2019(o) => o.misses_for_pp(pp),
+        }
+    }
+
+    /// Find the minimal combo that is required to reach the given amount of
+    /// pp.
+    ///
+    /// Since combo is irrelevant for osu!mania, the result will either be
+    /// `Some(0)` or `None` for it.
+    ///
+    /// Returns `None` if the pp can't be reached even with max combo.
+    #[allow(clippy::missing_panics_doc)]
+    pub fn combo_for_pp(self, pp: f64) -> Option<u32> {
+        match self {
+            Self::Osu(o) => o.combo_for_pp(pp).expect("no conversion required"),
+            Self::Taiko(t) => t.combo_for_pp(pp).expect("no conversion required"),
+            Self::Catch(f) => f.combo_for_pp(pp).expect("no conversion required"),
+            Self::Mania(m) => {
+                let attrs = m.calculate().expect("no conversion required");
+
+                (attrs.pp >= pp).then_some(0)
+            }
+            Self::Osu2019(o) => o.combo_for_pp(pp),
         }
     }
 }
@@ -442,6 +667,28 @@ impl Default for HitResultPriority {
     }
 }
 
+/// The components of an osu!taiko, osu!catch, or osu!mania performance
+/// calculation that were zeroed due to the relax or autopilot mod.
+///
+/// Relax is only special-cased in osu!taiko where it zeroes the color skill
+/// and the accuracy.
+///
+/// Autopilot scores are worth no pp at all in these modes. This is a policy
+/// decision rather than part of the official algorithms: autopilot does not
+/// exist for osu!taiko and osu!mania, and in osu!catch it takes over the
+/// movement, leaving nothing to reward.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ZeroedComponents {
+    /// Whether the difficulty portion was zeroed, leaving no pp at all.
+    pub difficulty: bool,
+    /// Whether the color skill was zeroed. Only relevant for osu!taiko.
+    pub color: bool,
+    /// Whether the accuracy was not considered.
+    pub accuracy: bool,
+}
+
 impl<'a, T: IntoPerformance<'a>> From<T> for Performance<'a> {
     fn from(into: T) -> Self {
         into.into_performance()
@@ -455,6 +702,7 @@ mod tests {
         catch::{CatchDifficultyAttributes, CatchPerformanceAttributes},
         mania::{ManiaDifficultyAttributes, ManiaPerformanceAttributes},
         osu::{OsuDifficultyAttributes, OsuPerformanceAttributes},
+        osu_2019,
         taiko::{TaikoDifficultyAttributes, TaikoPerformanceAttributes},
         Beatmap,
     };
@@ -503,5 +751,283 @@ mod tests {
 
         let _ = DifficultyAttributes::Osu(OsuDifficultyAttributes::default()).performance();
         let _ = PerformanceAttributes::Taiko(TaikoPerformanceAttributes::default()).performance();
+
+        let _ = Performance::new(osu_2019::OsuDifficultyAttributes::default());
+        let _ = Performance::new(osu_2019::OsuPerformanceAttributes::default());
+        let _ = Performance::new(DifficultyAttributes::Osu2019(
+            osu_2019::OsuDifficultyAttributes::default(),
+        ));
+    }
+
+    #[test]
+    fn relax_2019_variant() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+        let mods = 128 + 8; // RXHD
+
+        let difficulty = Difficulty::new().mods(mods).variant(Variant::Relax2019);
+
+        let DifficultyAttributes::Osu2019(attrs) = difficulty.calculate(&map) else {
+            panic!("expected osu_2019 attributes");
+        };
+
+        assert_eq!(attrs, osu_2019::stars::stars(&map, mods.into(), None));
+
+        let expected = osu_2019::OsuPP::from_map(&map)
+            .mods(mods)
+            .combo(500)
+            .misses(2)
+            .calculate();
+
+        let performance = Performance::new(&map)
+            .difficulty(difficulty.clone())
+            .combo(500)
+            .misses(2);
+
+        assert!(matches!(performance, Performance::Osu2019(_)));
+
+        let PerformanceAttributes::Osu2019(actual) = performance.calculate() else {
+            panic!("expected osu_2019 attributes");
+        };
+
+        assert_eq!(actual, expected);
+
+        let performance = Performance::new(&map)
+            .variant(Variant::Relax2019)
+            .variant(Variant::Default);
+
+        assert!(matches!(performance, Performance::Osu(_)));
+
+        let performance = Performance::new(attrs).variant(Variant::Default);
+
+        assert!(matches!(performance, Performance::Osu2019(_)));
+    }
+
+    #[test]
+    fn measured_unstable_rate() {
+        fn unstable_rates(attrs: &PerformanceAttributes) -> (Option<f64>, Option<f64>) {
+            match attrs {
+                PerformanceAttributes::Osu(attrs) => {
+                    (attrs.estimated_unstable_rate, attrs.measured_unstable_rate)
+                }
+                PerformanceAttributes::Taiko(attrs) => {
+                    (attrs.estimated_unstable_rate, attrs.measured_unstable_rate)
+                }
+                PerformanceAttributes::Mania(attrs) => {
+                    (attrs.estimated_unstable_rate, attrs.measured_unstable_rate)
+                }
+                PerformanceAttributes::Catch(_) | PerformanceAttributes::Osu2019(_) => {
+                    unreachable!()
+                }
+            }
+        }
+
+        let paths = [
+            "./resources/2785319.osu",
+            "./resources/1028484.osu",
+            "./resources/1638954.osu",
+        ];
+
+        for path in paths {
+            let map = Beatmap::from_path(path).unwrap();
+            let performance = || Performance::new(&map).lazer(true).n100(20);
+
+            let estimated = performance().calculate();
+            let consistent = performance().unstable_rate(60.0).calculate();
+            let inconsistent = performance().unstable_rate(250.0).calculate();
+
+            let (estimated_ur, measured_ur) = unstable_rates(&estimated);
+            assert!(estimated_ur.is_some(), "{path}");
+            assert_eq!(measured_ur, None, "{path}");
+
+            let (consistent_estimated_ur, measured_ur) = unstable_rates(&consistent);
+            assert_eq!(consistent_estimated_ur, estimated_ur, "{path}");
+            assert_eq!(measured_ur, Some(60.0), "{path}");
+
+            assert!(consistent.pp() > inconsistent.pp(), "{path}");
+
+            // Offsets are given in map time, the unstable rate in real time
+            let from_offsets = performance()
+                .mods(64) // DT
+                .hit_offsets([-15.0, 15.0])
+                .calculate();
+            let from_unstable_rate = performance().mods(64).unstable_rate(100.0).calculate();
+
+            let (_, measured_ur) = unstable_rates(&from_offsets);
+            let measured_ur = measured_ur.unwrap();
+            assert!((measured_ur - 100.0).abs() < 1e-9, "{path}: {measured_ur}");
+            assert!(
+                (from_offsets.pp() - from_unstable_rate.pp()).abs() < 1e-9,
+                "{path}"
+            );
+        }
+    }
+
+    #[test]
+    fn if_fc() {
+        let paths = [
+            "./resources/2785319.osu",
+            "./resources/1028484.osu",
+            "./resources/2118524.osu",
+            "./resources/1638954.osu",
+        ];
+
+        for path in paths {
+            let map = Beatmap::from_path(path).unwrap();
+
+            let mut performance = Performance::new(&map)
+                .accuracy(90.0)
+                .misses(5)
+                .combo(100)
+                .hitresult_priority(HitResultPriority::WorstCase);
+            let pp = performance.clone().calculate().pp();
+            let state = performance.generate_state();
+
+            let mut fc = performance.if_fc();
+            let fc_state = fc.generate_state();
+            let is_mania = matches!(fc, Performance::Mania(_));
+            let fc_attrs = fc.calculate();
+
+            assert_eq!(fc_state.misses, 0, "{path}");
+
+            match fc_attrs {
+                PerformanceAttributes::Osu(_) | PerformanceAttributes::Taiko(_) => {
+                    assert_eq!(fc_state.n300, state.n300 + state.misses, "{path}");
+                    assert_eq!(fc_state.n100, state.n100, "{path}");
+                    assert_eq!(fc_state.n50, state.n50, "{path}");
+                }
+                PerformanceAttributes::Catch(_) => {
+                    assert_eq!(
+                        fc_state.n300 + fc_state.n100,
+                        state.n300 + state.n100 + state.misses,
+                        "{path}"
+                    );
+                    assert_eq!(fc_state.n50, state.n50, "{path}");
+                    assert_eq!(fc_state.n_katu, state.n_katu, "{path}");
+                }
+                PerformanceAttributes::Mania(_) => {
+                    assert_eq!(fc_state.n_geki, state.n_geki + state.misses, "{path}");
+                    assert_eq!(fc_state.n_katu, state.n_katu, "{path}");
+                    assert_eq!(fc_state.n300, state.n300, "{path}");
+                    assert_eq!(fc_state.n100, state.n100, "{path}");
+                    assert_eq!(fc_state.n50, state.n50, "{path}");
+                }
+                PerformanceAttributes::Osu2019(_) => unreachable!(),
+            }
+
+            if !is_mania {
+                assert_eq!(fc_state.max_combo, fc_attrs.max_combo(), "{path}");
+            }
+
+            assert!(fc_attrs.pp() >= pp, "{path}: {} < {pp}", fc_attrs.pp());
+        }
+    }
+
+    #[test]
+    fn if_fc_relax_2019() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+
+        let mut performance = Performance::new(&map)
+            .variant(Variant::Relax2019)
+            .accuracy(90.0)
+            .misses(3)
+            .combo(200)
+            .hitresult_priority(HitResultPriority::WorstCase);
+
+        let prev_state = performance.generate_state();
+        let mut performance = performance.if_fc();
+        let state = performance.generate_state();
+
+        assert_eq!(prev_state.misses, 3);
+        assert_eq!(state.misses, 0);
+        assert_eq!(state.n300, prev_state.n300 + prev_state.misses);
+        assert_eq!(state.n100, prev_state.n100);
+        assert_eq!(state.n50, prev_state.n50);
+        assert_eq!(state.max_combo, performance.calculate().max_combo());
+    }
+
+    #[test]
+    fn what_if() {
+        let map = Beatmap::from_path("./resources/1028484.osu").unwrap();
+
+        let mut performance = Performance::new(&map).accuracy(98.0).what_if(|state| {
+            state.n300 -= 2;
+            state.misses += 2;
+        });
+
+        let expected = Performance::new(&map).accuracy(98.0).generate_state();
+        let state = performance.generate_state();
+
+        assert_eq!(state.n300 + 2, expected.n300);
+        assert_eq!(state.misses, expected.misses + 2);
+        assert_eq!(state.n100, expected.n100);
+    }
+
+    #[test]
+    fn inverse_solver() {
+        let maps = [
+            ("./resources/2785319.osu", 0),
+            ("./resources/2785319.osu", 128),  // RX
+            ("./resources/2785319.osu", 8192), // AP
+            ("./resources/1028484.osu", 0),
+            ("./resources/2118524.osu", 0),
+            ("./resources/1638954.osu", 0),
+        ];
+
+        for (path, mods) in maps {
+            let map = Beatmap::from_path(path).unwrap();
+            let attrs = Difficulty::new().mods(mods).calculate(&map);
+            let performance = Performance::new(attrs).mods(mods);
+
+            let max_pp = performance.clone().calculate().pp();
+            let target = max_pp * 0.8;
+
+            let acc = performance.clone().accuracy_for_pp(target).unwrap();
+            assert!(
+                performance.clone().accuracy(acc).calculate().pp() >= target,
+                "{path} +{mods}"
+            );
+            assert!(
+                performance.clone().accuracy(acc - 0.01).calculate().pp() < target,
+                "{path} +{mods}"
+            );
+
+            let misses = performance.clone().misses_for_pp(target).unwrap();
+            assert!(
+                performance.clone().misses(misses).calculate().pp() >= target,
+                "{path} +{mods}"
+            );
+            assert!(
+                performance.clone().misses(misses + 1).calculate().pp() < target,
+                "{path} +{mods}"
+            );
+
+            assert_eq!(performance.clone().accuracy_for_pp(max_pp * 1.1), None);
+            assert_eq!(performance.clone().misses_for_pp(max_pp * 1.1), None);
+            assert_eq!(performance.clone().combo_for_pp(max_pp * 1.1), None);
+        }
+    }
+
+    #[test]
+    fn inverse_solver_combo() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+        let performance = Performance::new(&map).lazer(false).accuracy(97.0).misses(1);
+
+        let target = performance.clone().calculate().pp() * 0.9;
+        let combo = performance.clone().combo_for_pp(target).unwrap();
+
+        assert!(performance.clone().combo(combo).calculate().pp() >= target);
+        assert!(performance.combo(combo - 1).calculate().pp() < target);
+
+        let performance = Performance::new(&map)
+            .variant(Variant::Relax2019)
+            .mods(128)
+            .accuracy(97.0)
+            .misses(1);
+
+        let target = performance.clone().calculate().pp() * 0.9;
+        let combo = performance.clone().combo_for_pp(target).unwrap();
+
+        assert!(performance.clone().combo(combo).calculate().pp() >= target);
+        assert!(performance.combo(combo - 1).calculate().pp() < target);
     }
 }
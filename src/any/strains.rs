@@ -1,4 +1,7 @@
-use crate::{catch::CatchStrains, mania::ManiaStrains, osu::OsuStrains, taiko::TaikoStrains};
+use crate::{
+    catch::CatchStrains, mania::ManiaStrains, osu::OsuStrains, osu_2019::Osu2019Strains,
+    taiko::TaikoStrains,
+};
 
 /// The result of calculating the strains on a map.
 ///
@@ -9,6 +12,7 @@ pub enum Strains {
     Taiko(TaikoStrains),
     Catch(CatchStrains),
     Mania(ManiaStrains),
+    Osu2019(Osu2019Strains),
 }
 
 impl Strains {
@@ -19,6 +23,7 @@ impl Strains {
             Strains::Taiko(_) => TaikoStrains::SECTION_LEN,
             Strains::Catch(_) => CatchStrains::SECTION_LEN,
             Strains::Mania(_) => ManiaStrains::SECTION_LEN,
+            Strains::Osu2019(strains) => strains.section_len,
         }
     }
 }
@@ -37,3 +42,4 @@ from_mode_strains!(Osu: OsuStrains);
 from_mode_strains!(Taiko: TaikoStrains);
 from_mode_strains!(Catch: CatchStrains);
 from_mode_strains!(Mania: ManiaStrains);
+from_mode_strains!(Osu2019: Osu2019Strains);
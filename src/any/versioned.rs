@@ -0,0 +1,137 @@
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{
+    catch::{CatchDifficultyAttributes, CatchPerformanceAttributes, CatchStrains},
+    mania::{ManiaDifficultyAttributes, ManiaPerformanceAttributes, ManiaStrains},
+    osu::{OsuDifficultyAttributes, OsuPerformanceAttributes, OsuStrains},
+    osu_2019::{self, Osu2019Strains},
+    taiko::{TaikoDifficultyAttributes, TaikoPerformanceAttributes, TaikoStrains},
+};
+
+use super::{DifficultyAttributes, PerformanceAttributes, Strains};
+
+/// The version of the serialized field layout.
+///
+/// [`DifficultyAttributes`], [`PerformanceAttributes`], and [`Strains`] are
+/// serialized with a `version` field next to their `mode` tag. The version
+/// is only increased if fields are renamed, removed, or change their meaning
+/// so that added fields alone keep previously serialized data readable.
+/// Data of a newer version is rejected when deserializing and data without
+/// a `version` field is considered to be of version `1`.
+///
+/// Only these three enums are versioned. The mode-specific types they wrap,
+/// e.g. [`OsuDifficultyAttributes`] or the osu!2019 attributes, carry no
+/// `version` field when serialized on their own so their data cannot be
+/// checked for compatibility. Serialize the enums instead to keep the check.
+///
+/// Note that this version only concerns the layout. Whether the values are
+/// still up to date with the calculation is tracked by
+/// [`ALGORITHM_VERSION`](crate::any::ALGORITHM_VERSION).
+pub const SERDE_VERSION: u32 = 1;
+
+const fn initial_version() -> u32 {
+    1
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "DifficultyAttributes", tag = "mode")]
+enum DifficultyAttributesDef {
+    Osu(OsuDifficultyAttributes),
+    Taiko(TaikoDifficultyAttributes),
+    Catch(CatchDifficultyAttributes),
+    Mania(ManiaDifficultyAttributes),
+    Osu2019(osu_2019::OsuDifficultyAttributes),
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "PerformanceAttributes", tag = "mode")]
+enum PerformanceAttributesDef {
+    Osu(OsuPerformanceAttributes),
+    Taiko(TaikoPerformanceAttributes),
+    Catch(CatchPerformanceAttributes),
+    Mania(ManiaPerformanceAttributes),
+    Osu2019(osu_2019::OsuPerformanceAttributes),
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "Strains", tag = "mode")]
+enum StrainsDef {
+    Osu(OsuStrains),
+    Taiko(TaikoStrains),
+    Catch(CatchStrains),
+    Mania(ManiaStrains),
+    Osu2019(Osu2019Strains),
+}
+
+macro_rules! versioned {
+    ( $( $ty:ident: $def:ident, )* ) => {
+        $(
+            impl Serialize for $ty {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    struct Tagged<'a>(&'a $ty);
+
+                    impl Serialize for Tagged<'_> {
+                        fn serialize<S: Serializer>(
+                            &self,
+                            serializer: S,
+                        ) -> Result<S::Ok, S::Error> {
+                            $def::serialize(self.0, serializer)
+                        }
+                    }
+
+                    #[derive(Serialize)]
+                    struct Versioned<'a> {
+                        version: u32,
+                        #[serde(flatten)]
+                        value: Tagged<'a>,
+                    }
+
+                    let versioned = Versioned {
+                        version: SERDE_VERSION,
+                        value: Tagged(self),
+                    };
+
+                    versioned.serialize(serializer)
+                }
+            }
+
+            impl<'de> Deserialize<'de> for $ty {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    struct Tagged($ty);
+
+                    impl<'de> Deserialize<'de> for Tagged {
+                        fn deserialize<D: Deserializer<'de>>(
+                            deserializer: D,
+                        ) -> Result<Self, D::Error> {
+                            $def::deserialize(deserializer).map(Self)
+                        }
+                    }
+
+                    #[derive(Deserialize)]
+                    struct Versioned {
+                        #[serde(default = "initial_version")]
+                        version: u32,
+                        #[serde(flatten)]
+                        value: Tagged,
+                    }
+
+                    let Versioned { version, value } = Versioned::deserialize(deserializer)?;
+
+                    if version > SERDE_VERSION {
+                        return Err(D::Error::custom(format_args!(
+                            "unsupported version {version}, expected at most {SERDE_VERSION}"
+                        )));
+                    }
+
+                    Ok(value.0)
+                }
+            }
+        )*
+    };
+}
+
+versioned! {
+    DifficultyAttributes: DifficultyAttributesDef,
+    PerformanceAttributes: PerformanceAttributesDef,
+    Strains: StrainsDef,
+}
@@ -0,0 +1,355 @@
+use std::{
+    collections::HashMap,
+    num::NonZeroUsize,
+    panic,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+};
+
+use rosu_map::section::general::GameMode;
+
+use crate::{
+    any::{DifficultyAttributes, PerformanceAttributes, ScoreState},
+    model::mode::ConvertError,
+    Beatmap, Difficulty, Performance,
+};
+
+/// A single score of a [`Batch`].
+#[derive(Clone, Debug, PartialEq)]
+#[must_use]
+pub struct BatchJob {
+    /// The map on which the score was set.
+    ///
+    /// Jobs of the same map should share the same [`Arc`] so that their
+    /// difficulty calculation can be deduplicated.
+    pub map: Arc<Beatmap>,
+    /// The difficulty settings of the score.
+    pub difficulty: Difficulty,
+    /// The mode to convert the map to, if any.
+    pub mode: Option<GameMode>,
+    /// The hitresults and combo of the score.
+    pub state: ScoreState,
+}
+
+impl BatchJob {
+    /// Create a new job without conversion.
+    pub const fn new(map: Arc<Beatmap>, difficulty: Difficulty, state: ScoreState) -> Self {
+        Self {
+            map,
+            difficulty,
+            mode: None,
+            state,
+        }
+    }
+
+    /// Convert the map to the given mode before calculating.
+    pub const fn mode(mut self, mode: GameMode) -> Self {
+        self.mode = Some(mode);
+
+        self
+    }
+}
+
+/// Performance calculation of many scores in parallel.
+///
+/// Difficulty attributes are only calculated once for jobs that share the
+/// same map, mode, and [`Difficulty`]. Both the difficulty and the
+/// performance calculations are then distributed across a pool of worker
+/// threads.
+///
+/// # Example
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use akatsuki_pp::{
+///     any::ScoreState,
+///     batch::{Batch, BatchJob},
+///     Beatmap, Difficulty,
+/// };
+///
+/// let map = Arc::new(Beatmap::from_path("./resources/2785319.osu").unwrap());
+///
+/// let state = ScoreState {
+///     max_combo: 500,
+///     n300: 580,
+///     n100: 20,
+///     misses: 1,
+///     ..Default::default()
+/// };
+///
+/// let results = Batch::new()
+///     .jobs((0..4).map(|_| BatchJob::new(Arc::clone(&map), Difficulty::new(), state.clone())))
+///     .threads(2)
+///     .calculate();
+///
+/// for result in results {
+///     println!("PP: {}", result.unwrap().pp());
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[must_use]
+pub struct Batch {
+    jobs: Vec<BatchJob>,
+    threads: Option<NonZeroUsize>,
+}
+
+impl Batch {
+    /// Create a new batch without jobs.
+    pub const fn new() -> Self {
+        Self {
+            jobs: Vec::new(),
+            threads: None,
+        }
+    }
+
+    /// Add a job to the batch.
+    pub fn job(mut self, job: BatchJob) -> Self {
+        self.jobs.push(job);
+
+        self
+    }
+
+    /// Add multiple jobs to the batch.
+    pub fn jobs(mut self, jobs: impl IntoIterator<Item = BatchJob>) -> Self {
+        self.jobs.extend(jobs);
+
+        self
+    }
+
+    /// Specify the amount of worker threads.
+    ///
+    /// If `0` or unspecified, the available parallelism will be used.
+    pub const fn threads(mut self, threads: usize) -> Self {
+        self.threads = NonZeroUsize::new(threads);
+
+        self
+    }
+
+    /// Calculate the performance attributes of all jobs.
+    ///
+    /// The results are in the same order as the jobs. If a map could not be
+    /// converted to the job's mode, the [`ConvertError`] is returned for
+    /// that job instead.
+    pub fn calculate(self) -> Vec<Result<PerformanceAttributes, ConvertError>> {
+        let threads = self
+            .threads
+            .or_else(|| thread::available_parallelism().ok())
+            .map_or(1, NonZeroUsize::get);
+
+        // Job indices of all jobs that require a difficulty calculation
+        let mut unique: Vec<usize> = Vec::new();
+        let mut job_to_unique = Vec::with_capacity(self.jobs.len());
+
+        // Identical maps are identified by their `Arc` pointer and then
+        // compared by `Difficulty` within that group.
+        let mut groups: HashMap<(usize, Option<u8>), Vec<usize>> = HashMap::new();
+
+        for (job_idx, job) in self.jobs.iter().enumerate() {
+            let key = (
+                Arc::as_ptr(&job.map) as usize,
+                job.mode.map(|mode| mode as u8),
+            );
+            let group = groups.entry(key).or_default();
+
+            let existing = group
+                .iter()
+                .copied()
+                .find(|&idx| self.jobs[unique[idx]].difficulty == job.difficulty);
+
+            let idx = existing.unwrap_or_else(|| {
+                let idx = unique.len();
+                unique.push(job_idx);
+                group.push(idx);
+
+                idx
+            });
+
+            job_to_unique.push(idx);
+        }
+
+        let attrs = par_map(&unique, threads, |_, &job_idx| {
+            let job = &self.jobs[job_idx];
+
+            difficulty_attributes(&job.map, &job.difficulty, job.mode)
+        });
+
+        par_map(&self.jobs, threads, |job_idx, job| {
+            attrs[job_to_unique[job_idx]].clone().map(|attrs| {
+                Performance::new(attrs)
+                    .difficulty(job.difficulty.clone())
+                    .state(job.state.clone())
+                    .calculate()
+            })
+        })
+    }
+}
+
+fn difficulty_attributes(
+    map: &Beatmap,
+    difficulty: &Difficulty,
+    mode: Option<GameMode>,
+) -> Result<DifficultyAttributes, ConvertError> {
+    match mode {
+        Some(mode) => {
+            let map = map.convert_ref(mode, difficulty.get_mods())?;
+
+            Ok(difficulty.calculate(&map))
+        }
+        None => Ok(difficulty.calculate(map)),
+    }
+}
+
+/// Apply `f` on all items and their index across `threads` worker threads.
+///
+/// The results are in the same order as the items.
+fn par_map<T, R, F>(items: &[T], threads: usize, f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(usize, &T) -> R + Sync,
+{
+    let next = AtomicUsize::new(0);
+    let mut results: Vec<Option<R>> = (0..items.len()).map(|_| None).collect();
+
+    thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads.min(items.len()))
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut done = Vec::new();
+
+                    loop {
+                        let idx = next.fetch_add(1, Ordering::Relaxed);
+
+                        let Some(item) = items.get(idx) else {
+                            break done;
+                        };
+
+                        done.push((idx, f(idx, item)));
+                    }
+                })
+            })
+            .collect();
+
+        for worker in workers {
+            let done = worker
+                .join()
+                .unwrap_or_else(|payload| panic::resume_unwind(payload));
+
+            for (idx, result) in done {
+                results[idx] = Some(result);
+            }
+        }
+    });
+
+    results
+        .into_iter()
+        .map(|result| result.expect("all items were processed"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use crate::any::Variant;
+
+    use super::*;
+
+    fn map(path: &str) -> Arc<Beatmap> {
+        Arc::new(Beatmap::from_path(path).unwrap())
+    }
+
+    #[test]
+    fn matches_sequential() {
+        let osu = map("./resources/2785319.osu");
+        let taiko = map("./resources/1028484.osu");
+
+        let difficulties = [
+            Difficulty::new(),
+            Difficulty::new().mods(8 + 64),
+            Difficulty::new().mods(128),
+            Difficulty::new().mods(128).variant(Variant::Relax2019),
+        ];
+
+        let mut jobs = Vec::new();
+
+        for (i, difficulty) in difficulties.iter().enumerate() {
+            for misses in 0..3 {
+                let state = ScoreState {
+                    max_combo: 300 + i as u32,
+                    n300: 500,
+                    n100: 10 * misses,
+                    misses,
+                    ..Default::default()
+                };
+
+                jobs.push(BatchJob::new(
+                    Arc::clone(&osu),
+                    difficulty.clone(),
+                    state.clone(),
+                ));
+                jobs.push(BatchJob::new(
+                    Arc::clone(&taiko),
+                    difficulty.clone(),
+                    state.clone(),
+                ));
+                jobs.push(
+                    BatchJob::new(Arc::clone(&osu), difficulty.clone(), state)
+                        .mode(GameMode::Catch),
+                );
+            }
+        }
+
+        let expected: Vec<_> = jobs
+            .iter()
+            .map(|job| {
+                let map = job
+                    .mode
+                    .map_or(Ok(Cow::Borrowed(&*job.map)), |mode| {
+                        job.map.convert_ref(mode, job.difficulty.get_mods())
+                    })
+                    .unwrap();
+
+                Performance::new(map.as_ref())
+                    .difficulty(job.difficulty.clone())
+                    .state(job.state.clone())
+                    .calculate()
+            })
+            .collect();
+
+        let actual = Batch::new().jobs(jobs).threads(3).calculate();
+
+        assert_eq!(actual.len(), expected.len());
+
+        for (actual, expected) in actual.into_iter().zip(expected) {
+            assert_eq!(actual.unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn convert_error() {
+        let mania = map("./resources/1638954.osu");
+        let state = ScoreState::default();
+
+        let results = Batch::new()
+            .job(BatchJob::new(
+                Arc::clone(&mania),
+                Difficulty::new(),
+                state.clone(),
+            ))
+            .job(BatchJob::new(mania, Difficulty::new(), state).mode(GameMode::Taiko))
+            .calculate();
+
+        assert!(matches!(results[0], Ok(PerformanceAttributes::Mania(_))));
+        assert!(matches!(results[1], Err(ConvertError::Convert { .. })));
+    }
+
+    #[test]
+    fn empty() {
+        assert!(Batch::new().calculate().is_empty());
+    }
+}
@@ -0,0 +1,417 @@
+//! Command-line calculator for difficulty and performance attributes.
+//!
+//! Run with `--help` to see all available options.
+
+use std::{
+    env,
+    fmt::Write as _,
+    io::{self, Write as _},
+    path::PathBuf,
+    process::ExitCode,
+};
+
+use akatsuki_pp::{
+    any::{Strains, Variant},
+    model::mode::GameMode,
+    Beatmap, Difficulty, GameMods, Performance,
+};
+use rosu_mods::GameModsIntermode;
+use serde_json::Value;
+
+const HELP: &str = "\
+Calculate difficulty and performance attributes of an osu! beatmap.
+
+USAGE:
+    akatsuki-pp-cli <PATH> [OPTIONS]
+
+OPTIONS:
+    -m, --mods <MODS>       Mods as bitflags or acronyms e.g. `72` or `HDDT`
+        --mode <MODE>       Convert the map to `osu`, `taiko`, `catch`, or `mania`
+        --clock-rate <F>    Custom clock rate
+        --ar <F>            Override the approach rate
+        --cs <F>            Override the circle size
+        --hp <F>            Override the drain rate
+        --od <F>            Override the overall difficulty
+        --stable            Calculate for osu!stable instead of osu!lazer
+        --relax2019         Use the osu!standard 2019 relax calculator
+    -a, --acc <F>           Accuracy between 0 and 100
+    -c, --combo <N>         Max combo of the score
+        --n-geki <N>        Amount of gekis (n320 for osu!mania)
+        --n-katu <N>        Amount of katus (n200 for osu!mania)
+        --n300 <N>          Amount of 300s
+        --n100 <N>          Amount of 100s
+        --n50 <N>           Amount of 50s
+    -x, --misses <N>        Amount of misses
+        --strains           Print the strains instead of the attributes
+        --json              Print JSON instead of a table
+    -h, --help              Print this message";
+
+fn main() -> ExitCode {
+    let args = match Args::parse(env::args().skip(1)) {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            println!("{HELP}");
+
+            return ExitCode::SUCCESS;
+        }
+        Err(err) => {
+            eprintln!("error: {err}\n\nSee `--help` for more information.");
+
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match args.run() {
+        Ok(output) => {
+            // Ignore errors such as a closed pipe
+            let _ = io::stdout().lock().write_all(output.as_bytes());
+
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("error: {err}");
+
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct Args {
+    path: PathBuf,
+    mods: Option<GameMods>,
+    mode: Option<GameMode>,
+    clock_rate: Option<f64>,
+    ar: Option<f32>,
+    cs: Option<f32>,
+    hp: Option<f32>,
+    od: Option<f32>,
+    stable: bool,
+    relax2019: bool,
+    acc: Option<f64>,
+    combo: Option<u32>,
+    n_geki: Option<u32>,
+    n_katu: Option<u32>,
+    n300: Option<u32>,
+    n100: Option<u32>,
+    n50: Option<u32>,
+    misses: Option<u32>,
+    strains: bool,
+    json: bool,
+}
+
+impl Args {
+    /// Returns `None` if the help message was requested.
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Option<Self>, String> {
+        fn value<T: std::str::FromStr>(
+            args: &mut impl Iterator<Item = String>,
+            arg: &str,
+        ) -> Result<T, String> {
+            let value = args
+                .next()
+                .ok_or_else(|| format!("missing value for `{arg}`"))?;
+
+            value
+                .parse()
+                .map_err(|_| format!("invalid value `{value}` for `{arg}`"))
+        }
+
+        let mut parsed = Self::default();
+        let mut path = None;
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-h" | "--help" => return Ok(None),
+                "-m" | "--mods" => {
+                    let mods = args
+                        .next()
+                        .ok_or_else(|| format!("missing value for `{arg}`"))?;
+
+                    parsed.mods = Some(parse_mods(&mods)?);
+                }
+                "--mode" => {
+                    let mode = args
+                        .next()
+                        .ok_or_else(|| format!("missing value for `{arg}`"))?;
+
+                    parsed.mode = Some(parse_mode(&mode)?);
+                }
+                "--clock-rate" => parsed.clock_rate = Some(value(&mut args, &arg)?),
+                "--ar" => parsed.ar = Some(value(&mut args, &arg)?),
+                "--cs" => parsed.cs = Some(value(&mut args, &arg)?),
+                "--hp" => parsed.hp = Some(value(&mut args, &arg)?),
+                "--od" => parsed.od = Some(value(&mut args, &arg)?),
+                "--stable" => parsed.stable = true,
+                "--relax2019" => parsed.relax2019 = true,
+                "-a" | "--acc" => parsed.acc = Some(value(&mut args, &arg)?),
+                "-c" | "--combo" => parsed.combo = Some(value(&mut args, &arg)?),
+                "--n-geki" => parsed.n_geki = Some(value(&mut args, &arg)?),
+                "--n-katu" => parsed.n_katu = Some(value(&mut args, &arg)?),
+                "--n300" => parsed.n300 = Some(value(&mut args, &arg)?),
+                "--n100" => parsed.n100 = Some(value(&mut args, &arg)?),
+                "--n50" => parsed.n50 = Some(value(&mut args, &arg)?),
+                "-x" | "--misses" => parsed.misses = Some(value(&mut args, &arg)?),
+                "--strains" => parsed.strains = true,
+                "--json" => parsed.json = true,
+                _ if arg.starts_with('-') => return Err(format!("unknown option `{arg}`")),
+                _ if path.is_none() => path = Some(PathBuf::from(arg)),
+                _ => return Err(format!("unexpected argument `{arg}`")),
+            }
+        }
+
+        parsed.path = path.ok_or("missing beatmap path")?;
+
+        Ok(Some(parsed))
+    }
+
+    fn difficulty(&self) -> Difficulty {
+        let mut difficulty = Difficulty::new();
+
+        if self.stable {
+            difficulty = difficulty.lazer(false);
+        }
+
+        if let Some(ref mods) = self.mods {
+            difficulty = difficulty.mods(mods.clone());
+        }
+
+        if let Some(clock_rate) = self.clock_rate {
+            difficulty = difficulty.clock_rate(clock_rate);
+        }
+
+        if let Some(ar) = self.ar {
+            difficulty = difficulty.ar(ar, false);
+        }
+
+        if let Some(cs) = self.cs {
+            difficulty = difficulty.cs(cs, false);
+        }
+
+        if let Some(hp) = self.hp {
+            difficulty = difficulty.hp(hp, false);
+        }
+
+        if let Some(od) = self.od {
+            difficulty = difficulty.od(od, false);
+        }
+
+        if self.relax2019 {
+            difficulty = difficulty.variant(Variant::Relax2019);
+        }
+
+        difficulty
+    }
+
+    fn performance<'a>(&self, mut performance: Performance<'a>) -> Performance<'a> {
+        if let Some(acc) = self.acc {
+            performance = performance.accuracy(acc);
+        }
+
+        if let Some(combo) = self.combo {
+            performance = performance.combo(combo);
+        }
+
+        if let Some(n_geki) = self.n_geki {
+            performance = performance.n_geki(n_geki);
+        }
+
+        if let Some(n_katu) = self.n_katu {
+            performance = performance.n_katu(n_katu);
+        }
+
+        if let Some(n300) = self.n300 {
+            performance = performance.n300(n300);
+        }
+
+        if let Some(n100) = self.n100 {
+            performance = performance.n100(n100);
+        }
+
+        if let Some(n50) = self.n50 {
+            performance = performance.n50(n50);
+        }
+
+        if let Some(misses) = self.misses {
+            performance = performance.misses(misses);
+        }
+
+        performance
+    }
+
+    fn run(&self) -> Result<String, String> {
+        let mut map = Beatmap::from_path(&self.path)
+            .map_err(|err| format!("failed to decode `{}`: {err}", self.path.display()))?;
+
+        if let Some(mode) = self.mode {
+            let mods = self.mods.clone().unwrap_or_default();
+
+            map.convert_mut(mode, &mods)
+                .map_err(|err| err.to_string())?;
+        }
+
+        let difficulty = self.difficulty();
+
+        let value = if self.strains {
+            let strains = difficulty.strains(&map);
+
+            if !self.json {
+                return Ok(format_strains(&strains));
+            }
+
+            serde_json::to_value(strains)
+        } else {
+            let attrs = difficulty.calculate(&map);
+            let performance = Performance::new(attrs).difficulty(difficulty);
+
+            serde_json::to_value(self.performance(performance).calculate())
+        };
+
+        let value = value.map_err(|err| err.to_string())?;
+
+        if self.json {
+            serde_json::to_string_pretty(&value)
+                .map(|json| json + "\n")
+                .map_err(|err| err.to_string())
+        } else {
+            Ok(format_table(&value))
+        }
+    }
+}
+
+fn parse_mods(s: &str) -> Result<GameMods, String> {
+    if let Ok(bits) = s.parse::<u32>() {
+        return Ok(bits.into());
+    }
+
+    GameModsIntermode::try_from_acronyms(s)
+        .map(|mods| GameMods::from(&mods))
+        .ok_or_else(|| format!("invalid mods `{s}`"))
+}
+
+fn parse_mode(s: &str) -> Result<GameMode, String> {
+    match s.to_ascii_lowercase().as_str() {
+        "0" | "osu" | "std" | "standard" => Ok(GameMode::Osu),
+        "1" | "taiko" => Ok(GameMode::Taiko),
+        "2" | "catch" | "fruits" | "ctb" => Ok(GameMode::Catch),
+        "3" | "mania" => Ok(GameMode::Mania),
+        _ => Err(format!("invalid mode `{s}`")),
+    }
+}
+
+/// Format all fields of the value with nested fields being joined by a `.`.
+fn format_table(value: &Value) -> String {
+    fn flatten(prefix: &str, value: &Value, rows: &mut Vec<(String, String)>) {
+        match value {
+            Value::Object(fields) => {
+                for (key, value) in fields {
+                    let key = if prefix.is_empty() {
+                        key.to_owned()
+                    } else {
+                        format!("{prefix}.{key}")
+                    };
+
+                    flatten(&key, value, rows);
+                }
+            }
+            Value::Null => rows.push((prefix.to_owned(), "-".to_owned())),
+            Value::String(s) => rows.push((prefix.to_owned(), s.to_owned())),
+            _ => rows.push((prefix.to_owned(), value.to_string())),
+        }
+    }
+
+    let mut rows = Vec::new();
+    flatten("", value, &mut rows);
+
+    let width = rows.iter().map(|(key, _)| key.len()).max().unwrap_or(0);
+    let mut output = String::new();
+
+    for (key, value) in rows {
+        let _ = writeln!(output, "{key:<width$}  {value}");
+    }
+
+    output
+}
+
+/// Format the strains with one column per skill and one row per section.
+fn format_strains(strains: &Strains) -> String {
+    let Ok(Value::Object(fields)) = serde_json::to_value(strains) else {
+        return String::new();
+    };
+
+    let columns: Vec<_> = fields
+        .iter()
+        .filter_map(|(name, value)| match value {
+            Value::Array(values) => {
+                let values: Vec<_> = values.iter().filter_map(Value::as_f64).collect();
+
+                Some((name, values))
+            }
+            _ => None,
+        })
+        .collect();
+
+    let n_rows = columns
+        .iter()
+        .map(|(_, values)| values.len())
+        .max()
+        .unwrap_or(0);
+
+    let mut output = format!("{:>10}", "time");
+
+    for (name, _) in columns.iter() {
+        let _ = write!(output, "  {name:>16}");
+    }
+
+    output.push('\n');
+
+    for i in 0..n_rows {
+        let _ = write!(output, "{:>10.0}", i as f64 * strains.section_len());
+
+        for (_, values) in columns.iter() {
+            let _ = match values.get(i) {
+                Some(value) => write!(output, "  {value:>16.4}"),
+                None => write!(output, "  {:>16}", "-"),
+            };
+        }
+
+        output.push('\n');
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &str) -> Result<Option<Args>, String> {
+        Args::parse(args.split_whitespace().map(str::to_owned))
+    }
+
+    #[test]
+    fn parse_args() {
+        let args = parse("map.osu -m HDDT --mode taiko --od 8.5 -a 98.5 -x 2 --json")
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(args.path, PathBuf::from("map.osu"));
+        assert_eq!(args.mods, Some(GameMods::from(72)));
+        assert_eq!(args.mode, Some(GameMode::Taiko));
+        assert_eq!(args.od, Some(8.5));
+        assert_eq!(args.acc, Some(98.5));
+        assert_eq!(args.misses, Some(2));
+        assert!(args.json);
+        assert!(!args.strains);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(parse("--help"), Ok(None));
+        assert!(parse("").is_err());
+        assert!(parse("map.osu --mods QQQ").is_err());
+        assert!(parse("map.osu --mode unknown").is_err());
+        assert!(parse("map.osu --combo").is_err());
+        assert!(parse("map.osu --unknown").is_err());
+        assert!(parse("map.osu other.osu").is_err());
+    }
+}
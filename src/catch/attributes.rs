@@ -1,9 +1,11 @@
 use std::mem;
 
-use crate::catch::performance::CatchPerformance;
+use crate::{any::ZeroedComponents, catch::performance::CatchPerformance};
 
 /// The result of a difficulty calculation on an osu!catch map.
 #[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct CatchDifficultyAttributes {
     /// The final star rating
     pub stars: f64,
@@ -19,6 +21,11 @@ pub struct CatchDifficultyAttributes {
     ///
     /// [`Beatmap`]: crate::model::beatmap::Beatmap
     pub is_convert: bool,
+    /// Whether the relax mod was enabled.
+    ///
+    /// Relax does not catch any fruits so relax scores are valued the same
+    /// as regular ones.
+    pub is_relax: bool,
 }
 
 impl CatchDifficultyAttributes {
@@ -34,6 +41,11 @@ impl CatchDifficultyAttributes {
         self.is_convert
     }
 
+    /// Whether the relax mod was enabled.
+    pub const fn is_relax(&self) -> bool {
+        self.is_relax
+    }
+
     /// Returns a builder for performance calculation.
     pub fn performance<'a>(self) -> CatchPerformance<'a> {
         self.into()
@@ -58,11 +70,18 @@ impl CatchDifficultyAttributes {
 
 /// The result of a performance calculation on an osu!catch map.
 #[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct CatchPerformanceAttributes {
     /// The difficulty attributes that were used for the performance calculation
     pub difficulty: CatchDifficultyAttributes,
     /// The final performance points.
     pub pp: f64,
+    /// The components that were zeroed due to the autopilot mod.
+    ///
+    /// Autopilot takes over all movement so all components are zeroed.
+    /// Nothing is zeroed on relax.
+    pub zeroed: ZeroedComponents,
 }
 
 impl CatchPerformanceAttributes {
@@ -88,6 +107,11 @@ impl CatchPerformanceAttributes {
         self.difficulty.is_convert
     }
 
+    /// Whether the relax mod was enabled.
+    pub const fn is_relax(&self) -> bool {
+        self.difficulty.is_relax
+    }
+
     /// Returns a builder for performance calculation.
     pub fn performance<'a>(self) -> CatchPerformance<'a> {
         self.difficulty.into()
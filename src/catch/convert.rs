@@ -28,6 +28,17 @@ pub fn convert(map: &mut Beatmap) {
     map.is_convert = true;
 }
 
+/// The start times of all fruits and droplets in the order in which gradual
+/// calculation processes them.
+pub fn palpable_object_times(map: &Beatmap) -> Vec<f64> {
+    let mut count = ObjectCountBuilder::new_regular(usize::MAX);
+
+    convert_objects(map, &mut count, Reflection::None, false, map.cs)
+        .into_iter()
+        .map(|h| h.start_time)
+        .collect()
+}
+
 pub fn convert_objects(
     map: &Beatmap,
     count: &mut ObjectCountBuilder,
@@ -3,13 +3,14 @@ use std::cmp;
 use rosu_map::section::general::GameMode;
 
 use crate::{
-    any::difficulty::skills::Skill,
+    any::difficulty::skills::{Skill, StrainPeaksSkill},
     catch::{
         attributes::{GradualObjectCount, ObjectCountBuilder},
         convert::convert_objects,
         CatchDifficultyAttributes,
     },
     model::mode::ConvertError,
+    util::checkpoints::{Checkpoints, Seek},
     Beatmap, Difficulty,
 };
 
@@ -62,6 +63,19 @@ pub struct CatchGradualDifficulty {
     count: Vec<GradualObjectCount>,
     diff_objects: Box<[CatchDifficultyObject]>,
     movement: Movement,
+    checkpoints: Checkpoints<CatchCheckpoint>,
+}
+
+/// The state after processing `idx` objects.
+///
+/// The movement skill is stored without its strain peaks. Instead,
+/// restoring truncates the current strain peaks to the stored amount.
+#[derive(Clone)]
+pub struct CatchCheckpoint {
+    idx: usize,
+    attrs: CatchDifficultyAttributes,
+    movement: Movement,
+    strain_peaks_len: usize,
 }
 
 impl CatchGradualDifficulty {
@@ -93,7 +107,15 @@ impl CatchGradualDifficulty {
         );
 
         let count = count.into_gradual();
-        let movement = Movement::new(clock_rate);
+        let mut movement = Movement::new(clock_rate);
+        let (initial_movement, strain_peaks_len) = movement.clone_without_history();
+
+        let checkpoints = Checkpoints::new(CatchCheckpoint {
+            idx: 0,
+            attrs: attrs.clone(),
+            movement: initial_movement,
+            strain_peaks_len,
+        });
 
         Ok(Self {
             idx: 0,
@@ -102,14 +124,60 @@ impl CatchGradualDifficulty {
             count,
             diff_objects,
             movement,
+            checkpoints,
         })
     }
+
+    /// Store the state after every `interval` fruits and droplets so that
+    /// seeking backwards with [`seek`] has to process at most `interval` of
+    /// them.
+    ///
+    /// Checkpoints are stored while objects are being processed. Each one only
+    /// contains the current state of the movement skill and the length of its
+    /// strain history so its size does not grow with the amount of processed
+    /// objects.
+    ///
+    /// [`seek`]: CatchGradualDifficulty::seek
+    #[must_use]
+    pub fn checkpoints(mut self, interval: usize) -> Self {
+        self.checkpoints.set_interval(interval);
+
+        self
+    }
+
+    /// Move to the fruit or droplet at index `idx`, which may be before or
+    /// after the current one, and return the difficulty attributes after
+    /// processing it.
+    ///
+    /// The attributes are the same as for `nth(idx)` on a new iterator and
+    /// [`Iterator::next`] will continue with the object at `idx + 1`.
+    ///
+    /// Seeking backwards restores the latest checkpoint before `idx` or,
+    /// without [`checkpoints`], processes all objects from the start again.
+    /// Seeking forwards always processes all objects between the current one
+    /// and `idx` because restoring a checkpoint discards the strain history
+    /// after it.
+    ///
+    /// Returns `None` if `idx` is out of bounds.
+    ///
+    /// [`checkpoints`]: CatchGradualDifficulty::checkpoints
+    pub fn seek(&mut self, idx: usize) -> Option<CatchDifficultyAttributes> {
+        if idx >= self.idx + self.len() {
+            return None;
+        }
+
+        self.seek_to(idx);
+
+        self.next()
+    }
 }
 
 impl Iterator for CatchGradualDifficulty {
     type Item = CatchDifficultyAttributes;
 
     fn next(&mut self) -> Option<Self::Item> {
+        self.record_checkpoint();
+
         // The first difficulty object belongs to the second palpable object
         // since each difficulty object requires the current and the last note.
         // Hence, if we're still on the first object, we don't have a difficulty
@@ -139,9 +207,56 @@ impl Iterator for CatchGradualDifficulty {
     }
 
     fn nth(&mut self, n: usize) -> Option<Self::Item> {
-        let skip_iter = self.diff_objects.iter().skip(self.idx.saturating_sub(1));
+        self.process(cmp::min(n, self.len().saturating_sub(1)));
+
+        self.next()
+    }
+}
+
+impl Seek for CatchGradualDifficulty {
+    type Snapshot = CatchCheckpoint;
 
-        let mut take = cmp::min(n, self.len().saturating_sub(1));
+    const RESTORES_FORWARD: bool = false;
+
+    fn idx(&self) -> usize {
+        self.idx
+    }
+
+    fn stored_checkpoints(&self) -> &Checkpoints<Self::Snapshot> {
+        &self.checkpoints
+    }
+
+    fn stored_checkpoints_mut(&mut self) -> &mut Checkpoints<Self::Snapshot> {
+        &mut self.checkpoints
+    }
+
+    fn snapshot(&mut self) -> Self::Snapshot {
+        let (movement, strain_peaks_len) = self.movement.clone_without_history();
+
+        CatchCheckpoint {
+            idx: self.idx,
+            attrs: self.attrs.clone(),
+            movement,
+            strain_peaks_len,
+        }
+    }
+
+    fn restore(&mut self, snapshot: Self::Snapshot) {
+        let CatchCheckpoint {
+            idx,
+            attrs,
+            movement,
+            strain_peaks_len,
+        } = snapshot;
+
+        self.idx = idx;
+        self.attrs = attrs;
+        self.movement
+            .restore_with_history(movement, strain_peaks_len);
+    }
+
+    fn process(&mut self, mut take: usize) {
+        let skip_iter = self.diff_objects.iter().skip(self.idx.saturating_sub(1));
 
         // The first palpable object has no difficulty object
         if self.idx == 0 && take > 0 {
@@ -158,8 +273,6 @@ impl Iterator for CatchGradualDifficulty {
             self.attrs.add_object_count(self.count[self.idx]);
             self.idx += 1;
         }
-
-        self.next()
     }
 }
 
@@ -219,4 +332,31 @@ mod tests {
             assert_eq!(next_gradual, expected);
         }
     }
+
+    #[test]
+    fn seek() {
+        let map = Beatmap::from_path("./resources/2118524.osu").unwrap();
+
+        let difficulty = Difficulty::new().mods(64); // DT
+
+        let expected: Vec<_> = CatchGradualDifficulty::new(difficulty.clone(), &map)
+            .unwrap()
+            .collect();
+
+        let mut gradual = CatchGradualDifficulty::new(difficulty, &map)
+            .unwrap()
+            .checkpoints(50);
+
+        let last = expected.len() - 1;
+
+        for idx in [300, 10, 0, 299, 301, last, 150, 51, 50, 49] {
+            assert_eq!(gradual.seek(idx).as_ref(), Some(&expected[idx]), "{idx}");
+        }
+
+        assert!(gradual.seek(last + 1).is_none());
+
+        gradual.seek(20);
+        assert_eq!(gradual.next().as_ref(), Some(&expected[21]));
+        assert_eq!(gradual.len(), last - 21);
+    }
 }
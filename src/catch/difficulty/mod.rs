@@ -50,6 +50,7 @@ impl CatchDifficultySetup {
         let attrs = CatchDifficultyAttributes {
             ar: map_attrs.ar,
             is_convert: map.is_convert,
+            is_relax: difficulty.get_mods().rx(),
             ..Default::default()
         };
 
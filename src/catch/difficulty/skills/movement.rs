@@ -1,7 +1,7 @@
 use crate::{
     any::difficulty::{
         object::IDifficultyObject,
-        skills::{strain_decay, ISkill, Skill, StrainDecaySkill},
+        skills::{strain_decay, ISkill, Skill, StrainDecaySkill, StrainPeaksSkill},
     },
     catch::difficulty::object::CatchDifficultyObject,
     util::strains_vec::StrainsVec,
@@ -18,6 +18,7 @@ const DECAY_WEIGHT: f64 = 0.94;
 
 const SECTION_LEN: f64 = 750.0;
 
+#[derive(Clone)]
 pub struct Movement {
     inner: StrainDecaySkill,
     last_player_pos: Option<f32>,
@@ -131,6 +132,12 @@ impl ISkill for Movement {
     type DifficultyObjects<'a> = [CatchDifficultyObject];
 }
 
+impl StrainPeaksSkill for Movement {
+    fn strain_peaks_mut(&mut self) -> &mut StrainsVec {
+        &mut self.inner.inner.strain_peaks
+    }
+}
+
 impl<'a> Skill<'a, Movement> {
     fn calculate_initial_strain(&mut self, time: f64, curr: &CatchDifficultyObject) -> f64 {
         let prev_start_time = curr
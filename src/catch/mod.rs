@@ -16,6 +16,8 @@ pub use self::{
     strains::CatchStrains,
 };
 
+pub(crate) use self::convert::palpable_object_times;
+
 mod attributes;
 mod catcher;
 mod convert;
@@ -130,6 +130,46 @@ impl CatchGradualPerformance {
         Some(performance)
     }
 
+    /// Store the state after every `interval` objects so that seeking
+    /// backwards with [`seek`] has to process at most `interval` objects.
+    ///
+    /// See [`CatchGradualDifficulty::checkpoints`] for more info.
+    ///
+    /// [`seek`]: CatchGradualPerformance::seek
+    #[must_use]
+    pub fn checkpoints(mut self, interval: usize) -> Self {
+        self.difficulty = self.difficulty.checkpoints(interval);
+
+        self
+    }
+
+    /// Move to the fruit or droplet at index `idx`, which may be before or after the
+    /// current one, and calculate the performance attributes for the
+    /// resulting score state.
+    ///
+    /// See [`CatchGradualDifficulty::seek`] for how many objects have to be
+    /// processed.
+    ///
+    /// Returns `None` if `idx` is out of bounds.
+    #[allow(clippy::missing_panics_doc)]
+    pub fn seek(
+        &mut self,
+        state: CatchScoreState,
+        idx: usize,
+    ) -> Option<CatchPerformanceAttributes> {
+        let performance = self
+            .difficulty
+            .seek(idx)?
+            .performance()
+            .state(state)
+            .difficulty(self.difficulty.difficulty.clone())
+            .passed_objects(self.difficulty.idx as u32)
+            .calculate()
+            .expect("no conversion required");
+
+        Some(performance)
+    }
+
     /// Returns the amount of remaining objects.
     #[allow(clippy::len_without_is_empty)]
     pub fn len(&self) -> usize {
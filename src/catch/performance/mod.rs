@@ -3,10 +3,10 @@ use std::cmp::{self, Ordering};
 use rosu_map::section::general::GameMode;
 
 use crate::{
-    any::{Difficulty, IntoModePerformance, IntoPerformance},
+    any::{Difficulty, IntoModePerformance, IntoPerformance, ZeroedComponents},
     model::{mode::ConvertError, mods::GameMods},
     osu::OsuPerformance,
-    util::map_or_attrs::MapOrAttrs,
+    util::{map_or_attrs::MapOrAttrs, search},
     Performance,
 };
 
@@ -415,6 +415,85 @@ impl<'map> CatchPerformance<'map> {
         Ok(best_state)
     }
 
+    /// Adjust the current score so that it becomes a full combo.
+    ///
+    /// All fruits and droplets are considered caught and the combo is set to
+    /// the map's max combo. Tiny droplets don't affect the combo so their
+    /// hits and misses are kept as they are.
+    pub fn if_fc(mut self) -> Result<Self, ConvertError> {
+        let state = self.generate_state()?;
+
+        self.tiny_droplets = Some(state.tiny_droplets);
+        self.tiny_droplet_misses = Some(state.tiny_droplet_misses);
+        self.fruits = None;
+        self.droplets = None;
+        self.misses = Some(0);
+        self.combo = None;
+        self.acc = None;
+
+        Ok(self)
+    }
+
+    /// Modify the current [`CatchScoreState`] through the given function.
+    ///
+    /// Fruits, droplets, and tiny droplets are generated before the function
+    /// is called so that e.g. tiny droplet misses can be shifted without
+    /// knowing the map's object counts. The adjusted counts are used as is
+    /// afterwards and the accuracy is discarded.
+    pub fn what_if<F>(mut self, f: F) -> Result<Self, ConvertError>
+    where
+        F: FnOnce(&mut CatchScoreState),
+    {
+        let mut state = self.generate_state()?;
+        f(&mut state);
+        self.acc = None;
+
+        Ok(self.state(state))
+    }
+
+    /// Find the minimal accuracy between `0.0` and `100.0` that is required
+    /// to reach the given amount of pp.
+    ///
+    /// Tiny droplet hits and misses are generated from the accuracy while all
+    /// other parameters are kept as they are.
+    ///
+    /// Returns `None` if the pp can't be reached even with 100% accuracy.
+    pub fn accuracy_for_pp(self, pp: f64) -> Result<Option<f64>, ConvertError> {
+        let (mut perf, _) = self.with_attrs()?;
+        perf.tiny_droplets = None;
+        perf.tiny_droplet_misses = None;
+
+        Ok(search::min_f64(0.0, 100.0, |acc| {
+            perf.clone().accuracy(acc).reaches_pp(pp)
+        }))
+    }
+
+    /// Find the maximal amount of misses for which the given amount of pp
+    /// can still be reached.
+    ///
+    /// Returns `None` if the pp can't be reached even without misses.
+    pub fn misses_for_pp(self, pp: f64) -> Result<Option<u32>, ConvertError> {
+        let (perf, attrs) = self.with_attrs()?;
+
+        Ok(search::max_u32(
+            0,
+            attrs.n_fruits + attrs.n_droplets,
+            |misses| perf.clone().misses(misses).reaches_pp(pp),
+        ))
+    }
+
+    /// Find the minimal combo that is required to reach the given amount of
+    /// pp.
+    ///
+    /// Returns `None` if the pp can't be reached even with max combo.
+    pub fn combo_for_pp(self, pp: f64) -> Result<Option<u32>, ConvertError> {
+        let (perf, attrs) = self.with_attrs()?;
+
+        Ok(search::min_u32(0, attrs.max_combo(), |combo| {
+            perf.clone().combo(combo).reaches_pp(pp)
+        }))
+    }
+
     /// Calculate all performance related values, including pp and stars.
     pub fn calculate(mut self) -> Result<CatchPerformanceAttributes, ConvertError> {
         let state = self.generate_state()?;
@@ -433,6 +512,26 @@ impl<'map> CatchPerformance<'map> {
         Ok(inner.calculate())
     }
 
+    /// Calculate the difficulty attributes unless they're already stored.
+    ///
+    /// Returns a copy of the attributes alongside the calculator.
+    fn with_attrs(mut self) -> Result<(Self, CatchDifficultyAttributes), ConvertError> {
+        let attrs = match self.map_or_attrs {
+            MapOrAttrs::Map(ref map) => {
+                let attrs = self.difficulty.calculate_for_mode::<Catch>(map)?;
+
+                self.map_or_attrs.insert_attrs(attrs).clone()
+            }
+            MapOrAttrs::Attrs(ref attrs) => attrs.clone(),
+        };
+
+        Ok((self, attrs))
+    }
+
+    fn reaches_pp(self, pp: f64) -> bool {
+        self.calculate().is_ok_and(|attrs| attrs.pp >= pp)
+    }
+
     pub(crate) const fn from_map_or_attrs(map_or_attrs: MapOrAttrs<'map, Catch>) -> Self {
         Self {
             map_or_attrs,
@@ -480,6 +579,7 @@ impl<'map> TryFrom<OsuPerformance<'map>> for CatchPerformance<'map> {
             n50,
             misses,
             hitresult_priority: _,
+            unstable_rate: _,
         } = osu;
 
         Ok(Self {
@@ -510,6 +610,21 @@ struct CatchPerformanceInner<'mods> {
 
 impl CatchPerformanceInner<'_> {
     fn calculate(self) -> CatchPerformanceAttributes {
+        let zeroed = ZeroedComponents {
+            difficulty: self.mods.ap(),
+            color: false,
+            accuracy: self.mods.ap(),
+        };
+
+        // Autopilot takes over the movement which is all there is to reward
+        if zeroed.difficulty {
+            return CatchPerformanceAttributes {
+                difficulty: self.attrs,
+                pp: 0.0,
+                zeroed,
+            };
+        }
+
         let attributes = &self.attrs;
         let stars = attributes.stars;
         let max_combo = attributes.max_combo();
@@ -576,6 +691,7 @@ impl CatchPerformanceInner<'_> {
         CatchPerformanceAttributes {
             difficulty: self.attrs,
             pp,
+            zeroed,
         }
     }
 
@@ -821,6 +937,33 @@ mod test {
         assert_eq!(state, expected);
     }
 
+    #[test]
+    fn relax_and_autopilot() {
+        let map = beatmap();
+
+        let calculate = |mods: u32, acc: f64| {
+            CatchPerformance::from(&map)
+                .mods(mods)
+                .accuracy(acc)
+                .calculate()
+                .unwrap()
+        };
+
+        let relax_ss = calculate(128, 100.0);
+        let relax_low_acc = calculate(128, 90.0);
+
+        // Relax does not catch the fruits so accuracy still matters
+        assert!(relax_ss.is_relax());
+        assert_eq!(relax_ss.zeroed, ZeroedComponents::default());
+        assert!(relax_low_acc.pp < relax_ss.pp);
+        assert!((relax_low_acc.pp - calculate(0, 90.0).pp).abs() < f64::EPSILON);
+
+        let autopilot = calculate(8192, 100.0);
+        assert!(autopilot.zeroed.difficulty && autopilot.zeroed.accuracy);
+        assert!(autopilot.pp.abs() < f64::EPSILON);
+        assert_eq!(calculate(0, 100.0).zeroed, ZeroedComponents::default());
+    }
+
     #[test]
     fn create() {
         let mut map = beatmap();
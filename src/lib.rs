@@ -121,8 +121,11 @@
 //! | ------------- | ------------------- | ------------
 //! | `default`     | No features enabled |
 //! | `raw_strains` | With this feature, internal strain values will be stored in a plain `Vec`. This introduces an out-of-memory risk on maliciously long maps (see [/b/3739922](https://osu.ppy.sh/b/3739922)), but comes with a ~5% gain in performance. |
-//! | `sync`        | Some gradual calculation types can only be shared across threads if this feature is enabled. This feature adds a small performance penalty. |
+//! | `sync`        | Some gradual calculation types can only be shared across threads if this feature is enabled. This feature adds a small performance penalty. Also enables the `batch` module for parallel calculations. |
 //! | `tracing`     | Any error encountered during beatmap decoding will be logged through `tracing::error`. If this feature is **not** enabled, errors will be ignored. | [`tracing`]
+//! | `serde`       | Implements `Serialize` and `Deserialize` for all attribute, score state, and strain types. Fields are only ever added so missing fields deserialize to their default value and previously serialized data stays readable. The mode-agnostic enums are tagged by a `mode` field and carry a `version` field, see `any::SERDE_VERSION`; mode-specific types are not versioned. | [`serde`]
+//! | `cli`         | Builds the `akatsuki-pp-cli` binary which prints difficulty and performance attributes, or strains, of a `.osu` file as a table or JSON. Enables the `serde` feature. | [`serde_json`]
+//! | `replay`      | Enables the `replay` module to decode `.osr` files into score states and mods. | [`lzma-rs`]
 //!
 //! ## Bindings
 //!
@@ -134,6 +137,9 @@
 //! [osu!lazer]: https://github.com/ppy/osu
 //! [osu!tools]: https://github.com/ppy/osu-tools
 //! [`tracing`]: https://docs.rs/tracing
+//! [`serde`]: https://docs.rs/serde
+//! [`serde_json`]: https://docs.rs/serde_json
+//! [`lzma-rs`]: https://docs.rs/lzma-rs
 //! [rosu-pp-js]: https://github.com/MaxOhn/rosu-pp-js
 //! [rosu-pp-py]: https://github.com/MaxOhn/rosu-pp-py
 //! [benchmark]: https://gist.github.com/MaxOhn/625af10011f6d7e13a171b08ccf959ff
@@ -183,4 +189,15 @@ pub mod osu_2019;
 /// Types used in and around this crate.
 pub mod model;
 
+/// Types for aggregating scores into profile pp.
+pub mod profile;
+
+/// Types for calculating many scores in parallel.
+#[cfg(feature = "sync")]
+pub mod batch;
+
+/// Types for decoding osu!stable replays.
+#[cfg(feature = "replay")]
+pub mod replay;
+
 mod util;
@@ -1,7 +1,9 @@
-use crate::mania::performance::ManiaPerformance;
+use crate::{any::ZeroedComponents, mania::performance::ManiaPerformance};
 
 /// The result of a difficulty calculation on an osu!mania map.
 #[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct ManiaDifficultyAttributes {
     /// The final star rating.
     pub stars: f64,
@@ -45,6 +47,8 @@ impl ManiaDifficultyAttributes {
 
 /// The result of a performance calculation on an osu!mania map.
 #[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct ManiaPerformanceAttributes {
     /// The difficulty attributes that were used for the performance calculation.
     pub difficulty: ManiaDifficultyAttributes,
@@ -52,6 +56,17 @@ pub struct ManiaPerformanceAttributes {
     pub pp: f64,
     /// The difficulty portion of the final pp.
     pub pp_difficulty: f64,
+    /// Upper bound on the player's hit deviation based on the hitresults.
+    pub estimated_unstable_rate: Option<f64>,
+    /// The unstable rate that was measured through hit offsets, if any.
+    ///
+    /// If available, it is used for the accuracy instead of the hitresults.
+    pub measured_unstable_rate: Option<f64>,
+    /// The components that were zeroed due to the autopilot mod.
+    ///
+    /// Autopilot is not available in osu!mania so all components are
+    /// zeroed. Relax is not special-cased and zeroes nothing.
+    pub zeroed: ZeroedComponents,
 }
 
 impl ManiaPerformanceAttributes {
@@ -3,9 +3,10 @@ use std::cmp;
 use rosu_map::section::general::GameMode;
 
 use crate::{
-    any::difficulty::skills::Skill,
+    any::difficulty::skills::{Skill, StrainPeaksSkill},
     mania::object::ObjectParams,
     model::{beatmap::HitWindows, hit_object::HitObject, mode::ConvertError},
+    util::checkpoints::{Checkpoints, Seek},
     Beatmap, Difficulty,
 };
 
@@ -55,9 +56,22 @@ pub struct ManiaGradualDifficulty {
     diff_objects: Box<[ManiaDifficultyObject]>,
     hit_window: f64,
     note_state: NoteState,
+    checkpoints: Checkpoints<ManiaCheckpoint>,
 }
 
-#[derive(Default)]
+/// The state after processing `idx` hit objects.
+///
+/// The strain skill is stored without its strain peaks. Instead, restoring
+/// truncates the current strain peaks to the stored amount.
+#[derive(Clone)]
+pub struct ManiaCheckpoint {
+    idx: usize,
+    strain: Strain,
+    strain_peaks_len: usize,
+    note_state: NoteState,
+}
+
+#[derive(Clone, Default)]
 struct NoteState {
     curr_combo: u32,
     n_hold_notes: u32,
@@ -86,7 +100,7 @@ impl ManiaGradualDifficulty {
 
         let diff_objects = DifficultyValues::create_difficulty_objects(clock_rate, mania_objects);
 
-        let strain = Strain::new(total_columns as usize);
+        let mut strain = Strain::new(total_columns as usize);
 
         let mut note_state = NoteState::default();
 
@@ -104,6 +118,15 @@ impl ManiaGradualDifficulty {
             );
         }
 
+        let (initial_strain, strain_peaks_len) = strain.clone_without_history();
+
+        let checkpoints = Checkpoints::new(ManiaCheckpoint {
+            idx: 0,
+            strain: initial_strain,
+            strain_peaks_len,
+            note_state: note_state.clone(),
+        });
+
         Ok(Self {
             idx: 0,
             difficulty,
@@ -113,14 +136,59 @@ impl ManiaGradualDifficulty {
             diff_objects,
             hit_window,
             note_state,
+            checkpoints,
         })
     }
+
+    /// Store the state after every `interval` hit objects so that seeking
+    /// backwards with [`seek`] has to process at most `interval` hit objects.
+    ///
+    /// Checkpoints are stored while hit objects are being processed. Each one
+    /// only contains the current state of the strain skill and the length of
+    /// its strain history so its size does not grow with the amount of
+    /// processed hit objects.
+    ///
+    /// [`seek`]: ManiaGradualDifficulty::seek
+    #[must_use]
+    pub fn checkpoints(mut self, interval: usize) -> Self {
+        self.checkpoints.set_interval(interval);
+
+        self
+    }
+
+    /// Move to the hit object at index `idx`, which may be before or after
+    /// the current one, and return the difficulty attributes after
+    /// processing it.
+    ///
+    /// The attributes are the same as for `nth(idx)` on a new iterator and
+    /// [`Iterator::next`] will continue with the hit object at `idx + 1`.
+    ///
+    /// Seeking backwards restores the latest checkpoint before `idx` or,
+    /// without [`checkpoints`], processes all hit objects from the start
+    /// again. Seeking forwards always processes all hit objects between the
+    /// current one and `idx` because restoring a checkpoint discards the
+    /// strain history after it.
+    ///
+    /// Returns `None` if `idx` is out of bounds.
+    ///
+    /// [`checkpoints`]: ManiaGradualDifficulty::checkpoints
+    pub fn seek(&mut self, idx: usize) -> Option<ManiaDifficultyAttributes> {
+        if idx >= self.idx + self.len() {
+            return None;
+        }
+
+        self.seek_to(idx);
+
+        self.next()
+    }
 }
 
 impl Iterator for ManiaGradualDifficulty {
     type Item = ManiaDifficultyAttributes;
 
     fn next(&mut self) -> Option<Self::Item> {
+        self.record_checkpoint();
+
         // The first difficulty object belongs to the second note since each
         // difficulty object requires the current and the last note. Hence, if
         // we're still on the first object, we don't have a difficulty object
@@ -159,14 +227,60 @@ impl Iterator for ManiaGradualDifficulty {
     }
 
     fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.process(cmp::min(n, self.len().saturating_sub(1)));
+
+        self.next()
+    }
+}
+
+impl Seek for ManiaGradualDifficulty {
+    type Snapshot = ManiaCheckpoint;
+
+    const RESTORES_FORWARD: bool = false;
+
+    fn idx(&self) -> usize {
+        self.idx
+    }
+
+    fn stored_checkpoints(&self) -> &Checkpoints<Self::Snapshot> {
+        &self.checkpoints
+    }
+
+    fn stored_checkpoints_mut(&mut self) -> &mut Checkpoints<Self::Snapshot> {
+        &mut self.checkpoints
+    }
+
+    fn snapshot(&mut self) -> Self::Snapshot {
+        let (strain, strain_peaks_len) = self.strain.clone_without_history();
+
+        ManiaCheckpoint {
+            idx: self.idx,
+            strain,
+            strain_peaks_len,
+            note_state: self.note_state.clone(),
+        }
+    }
+
+    fn restore(&mut self, snapshot: Self::Snapshot) {
+        let ManiaCheckpoint {
+            idx,
+            strain,
+            strain_peaks_len,
+            note_state,
+        } = snapshot;
+
+        self.idx = idx;
+        self.strain.restore_with_history(strain, strain_peaks_len);
+        self.note_state = note_state;
+    }
+
+    fn process(&mut self, mut take: usize) {
         let skip_iter = self
             .diff_objects
             .iter()
             .zip(self.objects_is_circle.iter().skip(1))
             .skip(self.idx.saturating_sub(1));
 
-        let mut take = cmp::min(n, self.len().saturating_sub(1));
-
         // The first note has no difficulty object
         if self.idx == 0 && take > 0 {
             take -= 1;
@@ -181,8 +295,6 @@ impl Iterator for ManiaGradualDifficulty {
             strain.process(curr);
             self.idx += 1;
         }
-
-        self.next()
     }
 }
 
@@ -267,4 +379,31 @@ mod tests {
             assert_eq!(next_gradual, expected);
         }
     }
+
+    #[test]
+    fn seek() {
+        let map = Beatmap::from_path("./resources/1638954.osu").unwrap();
+
+        let difficulty = Difficulty::new().mods(64); // DT
+
+        let expected: Vec<_> = ManiaGradualDifficulty::new(difficulty.clone(), &map)
+            .unwrap()
+            .collect();
+
+        let mut gradual = ManiaGradualDifficulty::new(difficulty, &map)
+            .unwrap()
+            .checkpoints(50);
+
+        let last = expected.len() - 1;
+
+        for idx in [300, 10, 0, 299, 301, last, 150, 51, 50, 49] {
+            assert_eq!(gradual.seek(idx).as_ref(), Some(&expected[idx]), "{idx}");
+        }
+
+        assert!(gradual.seek(last + 1).is_none());
+
+        gradual.seek(20);
+        assert_eq!(gradual.next().as_ref(), Some(&expected[21]));
+        assert_eq!(gradual.len(), last - 21);
+    }
 }
@@ -1,7 +1,7 @@
 use crate::{
     any::difficulty::{
         object::IDifficultyObject,
-        skills::{strain_decay, ISkill, Skill, StrainDecaySkill},
+        skills::{strain_decay, ISkill, Skill, StrainDecaySkill, StrainPeaksSkill},
     },
     mania::difficulty::object::ManiaDifficultyObject,
     util::strains_vec::StrainsVec,
@@ -14,6 +14,7 @@ const RELEASE_THRESHOLD: f64 = 30.0;
 const SKILL_MULTIPLIER: f64 = 1.0;
 const STRAIN_DECAY_BASE: f64 = 1.0;
 
+#[derive(Clone)]
 #[allow(clippy::struct_field_names)]
 pub struct Strain {
     start_times: Box<[f64]>,
@@ -145,6 +146,12 @@ impl ISkill for Strain {
     type DifficultyObjects<'a> = [ManiaDifficultyObject];
 }
 
+impl StrainPeaksSkill for Strain {
+    fn strain_peaks_mut(&mut self) -> &mut StrainsVec {
+        &mut self.inner.inner.strain_peaks
+    }
+}
+
 impl Skill<'_, Strain> {
     fn calculate_initial_strain(&mut self, offset: f64, curr: &ManiaDifficultyObject) -> f64 {
         let prev_start_time = curr
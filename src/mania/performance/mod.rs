@@ -3,10 +3,14 @@ use std::cmp;
 use rosu_map::section::general::GameMode;
 
 use crate::{
-    any::{Difficulty, HitResultPriority, IntoModePerformance, IntoPerformance},
+    any::{Difficulty, HitResultPriority, IntoModePerformance, IntoPerformance, ZeroedComponents},
     model::{mode::ConvertError, mods::GameMods},
     osu::OsuPerformance,
-    util::map_or_attrs::MapOrAttrs,
+    util::{
+        deviation::{self, MeasuredUnstableRate},
+        map_or_attrs::MapOrAttrs,
+        search,
+    },
     Performance,
 };
 
@@ -32,6 +36,7 @@ pub struct ManiaPerformance<'map> {
     misses: Option<u32>,
     acc: Option<f64>,
     hitresult_priority: HitResultPriority,
+    unstable_rate: Option<MeasuredUnstableRate>,
 }
 
 impl<'map> ManiaPerformance<'map> {
@@ -225,6 +230,29 @@ impl<'map> ManiaPerformance<'map> {
         self
     }
 
+    /// Specify the measured unstable rate of the play.
+    ///
+    /// The value must already be adjusted by the clock rate, i.e. as displayed
+    /// by osu!. If specified, the accuracy will be based on the unstable rate
+    /// instead of the hitresults.
+    pub const fn unstable_rate(mut self, unstable_rate: f64) -> Self {
+        self.unstable_rate = Some(MeasuredUnstableRate::Adjusted(unstable_rate));
+
+        self
+    }
+
+    /// Specify the hit offsets of the play in milliseconds to measure the
+    /// unstable rate.
+    ///
+    /// The offsets must be in map time, i.e. they will still be adjusted by
+    /// the clock rate. If fewer than two offsets are given, no unstable rate
+    /// will be measured.
+    pub fn hit_offsets(mut self, hit_offsets: impl IntoIterator<Item = f64>) -> Self {
+        self.unstable_rate = MeasuredUnstableRate::from_hit_offsets(hit_offsets);
+
+        self
+    }
+
     /// Provide parameters through an [`ManiaScoreState`].
     #[allow(clippy::needless_pass_by_value)]
     pub const fn state(mut self, state: ManiaScoreState) -> Self {
@@ -785,6 +813,73 @@ impl<'map> ManiaPerformance<'map> {
         })
     }
 
+    /// Adjust the current score so that it contains no misses.
+    ///
+    /// Misses are turned into n320s while all other judgements are kept as
+    /// they are. osu!mania performance does not depend on combo so nothing
+    /// else changes.
+    pub fn if_fc(mut self) -> Result<Self, ConvertError> {
+        let state = self.generate_state()?;
+
+        self.n320 = Some(state.n320 + state.misses);
+        self.n300 = Some(state.n300);
+        self.n200 = Some(state.n200);
+        self.n100 = Some(state.n100);
+        self.n50 = Some(state.n50);
+        self.misses = Some(0);
+        self.acc = None;
+
+        Ok(self)
+    }
+
+    /// Modify the current [`ManiaScoreState`] through the given function.
+    ///
+    /// All six judgement counts are generated according to the
+    /// [`HitResultPriority`] before the function is called. The adjusted
+    /// counts are used as is afterwards and the accuracy is discarded.
+    pub fn what_if<F>(mut self, f: F) -> Result<Self, ConvertError>
+    where
+        F: FnOnce(&mut ManiaScoreState),
+    {
+        let mut state = self.generate_state()?;
+        f(&mut state);
+        self.acc = None;
+
+        Ok(self.state(state))
+    }
+
+    /// Find the minimal accuracy between `0.0` and `100.0` that is required
+    /// to reach the given amount of pp.
+    ///
+    /// All hitresults except for misses are generated from the accuracy while all
+    /// other parameters are kept as they are.
+    ///
+    /// Returns `None` if the pp can't be reached even with 100% accuracy.
+    pub fn accuracy_for_pp(self, pp: f64) -> Result<Option<f64>, ConvertError> {
+        let (mut perf, _) = self.with_attrs()?;
+        perf.n320 = None;
+        perf.n300 = None;
+        perf.n200 = None;
+        perf.n100 = None;
+        perf.n50 = None;
+
+        Ok(search::min_f64(0.0, 100.0, |acc| {
+            perf.clone().accuracy(acc).reaches_pp(pp)
+        }))
+    }
+
+    /// Find the maximal amount of misses for which the given amount of pp
+    /// can still be reached.
+    ///
+    /// Returns `None` if the pp can't be reached even without misses.
+    pub fn misses_for_pp(self, pp: f64) -> Result<Option<u32>, ConvertError> {
+        let (perf, attrs) = self.with_attrs()?;
+
+        Ok(search::max_u32(0, attrs.n_objects, |misses| {
+            perf.clone().misses(misses).reaches_pp(pp)
+        }))
+    }
+
     /// Calculate all performance related values, including pp and stars.
     pub fn calculate(mut self) -> Result<ManiaPerformanceAttributes, ConvertError> {
         let state = self.generate_state()?;
@@ -796,13 +891,37 @@ impl<'map> ManiaPerformance<'map> {
 
         let inner = ManiaPerformanceInner {
             mods: self.difficulty.get_mods(),
+            hit_windows: hit_windows(&attrs, &self.difficulty),
             attrs,
             state,
+            measured_unstable_rate: self
+                .unstable_rate
+                .map(|unstable_rate| unstable_rate.get(self.difficulty.get_clock_rate())),
         };
 
         Ok(inner.calculate())
     }
 
+    /// Calculate the difficulty attributes unless they're already stored.
+    ///
+    /// Returns a copy of the attributes alongside the calculator.
+    fn with_attrs(mut self) -> Result<(Self, ManiaDifficultyAttributes), ConvertError> {
+        let attrs = match self.map_or_attrs {
+            MapOrAttrs::Map(ref map) => {
+                let attrs = self.difficulty.calculate_for_mode::<Mania>(map)?;
+
+                self.map_or_attrs.insert_attrs(attrs).clone()
+            }
+            MapOrAttrs::Attrs(ref attrs) => attrs.clone(),
+        };
+
+        Ok((self, attrs))
+    }
+
+    fn reaches_pp(self, pp: f64) -> bool {
+        self.calculate().is_ok_and(|attrs| attrs.pp >= pp)
+    }
+
     pub(crate) const fn from_map_or_attrs(map_or_attrs: MapOrAttrs<'map, Mania>) -> Self {
         Self {
             map_or_attrs,
@@ -815,6 +934,7 @@ impl<'map> ManiaPerformance<'map> {
             misses: None,
             acc: None,
             hitresult_priority: HitResultPriority::DEFAULT,
+            unstable_rate: None,
         }
     }
 }
@@ -851,6 +971,7 @@ impl<'map> TryFrom<OsuPerformance<'map>> for ManiaPerformance<'map> {
             n50,
             misses,
             hitresult_priority,
+            unstable_rate,
         } = osu;
 
         Ok(Self {
@@ -864,6 +985,7 @@ impl<'map> TryFrom<OsuPerformance<'map>> for ManiaPerformance<'map> {
             misses,
             acc,
             hitresult_priority,
+            unstable_rate,
         })
     }
 }
@@ -877,7 +999,9 @@ impl<'map, T: IntoModePerformance<'map, Mania>> From<T> for ManiaPerformance<'ma
 struct ManiaPerformanceInner<'mods> {
     attrs: ManiaDifficultyAttributes,
     mods: &'mods GameMods,
+    hit_windows: [f64; 5],
     state: ManiaScoreState,
+    measured_unstable_rate: Option<f64>,
 }
 
 impl ManiaPerformanceInner<'_> {
@@ -892,13 +1016,29 @@ impl ManiaPerformanceInner<'_> {
             multiplier *= 0.5;
         }
 
-        let difficulty_value = self.compute_difficulty_value();
+        let zeroed = ZeroedComponents {
+            difficulty: self.mods.ap(),
+            color: false,
+            accuracy: false,
+        };
+
+        let difficulty_value = if zeroed.difficulty {
+            0.0
+        } else {
+            self.compute_difficulty_value()
+        };
+
         let pp = difficulty_value * multiplier;
 
+        let estimated_unstable_rate = self.estimated_unstable_rate();
+
         ManiaPerformanceAttributes {
             difficulty: self.attrs,
             pp,
             pp_difficulty: difficulty_value,
+            estimated_unstable_rate,
+            measured_unstable_rate: self.measured_unstable_rate,
+            zeroed,
         }
     }
 
@@ -931,8 +1071,52 @@ impl ManiaPerformanceInner<'_> {
             return 0.0;
         }
 
+        if let Some(unstable_rate) = self.measured_unstable_rate {
+            return self.measured_custom_accuracy(unstable_rate / 10.0);
+        }
+
         custom_accuracy(*n320, *n300, *n200, *n100, *n50, total_hits)
     }
+
+    /// Upper bound on the unstable rate based on the hitresults.
+    fn estimated_unstable_rate(&self) -> Option<f64> {
+        let [perfect, great, ..] = self.hit_windows;
+
+        deviation::deviation_upper_bound(
+            self.total_hits(),
+            self.state.n320,
+            self.state.n320 + self.state.n300,
+            perfect,
+            great,
+        )
+        .map(|deviation| deviation * 10.0)
+    }
+
+    /// The custom accuracy based on the measured deviation, assuming normally
+    /// distributed hit offsets.
+    ///
+    /// Misses are considered separately since they're not part of the
+    /// measured hit offsets.
+    fn measured_custom_accuracy(&self, deviation: f64) -> f64 {
+        let [p320, p300, p200, p100, p50] = self
+            .hit_windows
+            .map(|hit_window| deviation::hit_probability(hit_window, deviation));
+
+        if p50 <= 0.0 {
+            return 0.0;
+        }
+
+        let timing_acc = (32.0 * p320
+            + 30.0 * (p300 - p320)
+            + 20.0 * (p200 - p300)
+            + 10.0 * (p100 - p200)
+            + 5.0 * (p50 - p100))
+            / (32.0 * p50);
+
+        let hit_ratio = 1.0 - f64::from(self.state.misses) / self.total_hits();
+
+        timing_acc * hit_ratio
+    }
 }
 
 fn custom_accuracy(n320: u32, n300: u32, n200: u32, n100: u32, n50: u32, total_hits: u32) -> f64 {
@@ -949,6 +1133,62 @@ fn accuracy(n320: u32, n300: u32, n200: u32, n100: u32, n50: u32, misses: u32) -
     f64::from(numerator) / f64::from(denominator)
 }
 
+/// The perfect, great, good, ok, and meh hit windows in real time.
+///
+/// The attributes only store the great hit window so the OD is recovered
+/// from it and all windows are derived from the OD. osu!stable keeps the
+/// windows constant in real time whereas osu!lazer defines them in map time
+/// so they're divided by the clock rate.
+fn hit_windows(attrs: &ManiaDifficultyAttributes, difficulty: &Difficulty) -> [f64; 5] {
+    let mods = difficulty.get_mods();
+    let lazer = difficulty.get_lazer();
+
+    let multiplier = if difficulty.get_od().is_some_and(|od| od.with_mods) {
+        1.0
+    } else if mods.hr() {
+        1.0 / 1.4
+    } else if mods.ez() {
+        1.4
+    } else {
+        1.0
+    };
+
+    let great = attrs.hit_window / multiplier;
+
+    let windows = if attrs.is_convert {
+        // * osu!stable only checks whether the OD of converts exceeds 4
+        if great < 40.0 {
+            [16.0, 34.0, 67.0, 97.0, 121.0]
+        } else {
+            [16.0, 47.0, 77.0, 97.0, 121.0]
+        }
+    } else {
+        let od = ((64.0 - great) / 3.0).clamp(0.0, 10.0);
+
+        let perfect = match (lazer, od > 5.0) {
+            (false, _) => 16.0,
+            (true, true) => 19.4 - 1.1 * (od - 5.0),
+            (true, false) => 22.4 - 0.6 * od,
+        };
+
+        [
+            perfect,
+            64.0 - 3.0 * od,
+            97.0 - 3.0 * od,
+            127.0 - 3.0 * od,
+            151.0 - 3.0 * od,
+        ]
+    };
+
+    let clock_rate = if lazer {
+        difficulty.get_clock_rate()
+    } else {
+        1.0
+    };
+
+    windows.map(|hit_window| hit_window * multiplier / clock_rate)
+}
+
 #[cfg(test)]
 mod tests {
     use std::{cmp::Ordering, sync::OnceLock};
@@ -1337,6 +1577,92 @@ mod tests {
         assert_eq!(state, expected);
     }
 
+    #[test]
+    fn measured_unstable_rate() {
+        let map = beatmap();
+
+        let windows = |mods: u32, lazer: bool| {
+            let difficulty = Difficulty::new().mods(mods).lazer(lazer);
+            let attrs = difficulty.calculate_for_mode::<Mania>(&map).unwrap();
+
+            hit_windows(&attrs, &difficulty)
+        };
+
+        let stable = windows(0, false);
+        let stable_dt = windows(64, false);
+        let stable_hr = windows(16, false);
+        let lazer = windows(0, true);
+        let lazer_dt = windows(64, true);
+
+        // osu!stable windows don't change with the clock rate
+        for (stable_dt, stable) in stable_dt.into_iter().zip(stable) {
+            assert!(
+                (stable_dt - stable).abs() < f64::EPSILON,
+                "{stable_dt} {stable}"
+            );
+        }
+
+        assert!((stable[0] - 16.0).abs() < f64::EPSILON);
+
+        for (stable_hr, stable) in stable_hr.into_iter().zip(stable) {
+            assert!(
+                (stable_hr - stable / 1.4).abs() < 1.0,
+                "{stable_hr} {stable}"
+            );
+        }
+
+        // osu!lazer windows are defined in map time
+        for (lazer_dt, lazer) in lazer_dt.into_iter().zip(lazer) {
+            assert!((lazer_dt - lazer / 1.5).abs() < 1e-9, "{lazer_dt} {lazer}");
+        }
+
+        // Only the perfect window differs between osu!stable and osu!lazer
+        assert!(lazer[0] > stable[0]);
+
+        for (lazer, stable) in lazer.into_iter().zip(stable).skip(1) {
+            assert!((lazer - stable).abs() < f64::EPSILON, "{lazer} {stable}");
+        }
+
+        // Only misses are considered from the hitresults
+        let calculate = |n320: u32| {
+            ManiaPerformance::from(&map)
+                .n320(n320)
+                .misses(2)
+                .unstable_rate(80.0)
+                .calculate()
+                .unwrap()
+        };
+
+        assert!((calculate(N_OBJECTS - 2).pp - calculate(100).pp).abs() < 1e-9);
+    }
+
+    #[test]
+    fn relax_and_autopilot() {
+        let map = beatmap();
+
+        let calculate = |mods: u32, n320: u32, misses: u32| {
+            ManiaPerformance::from(&map)
+                .mods(mods)
+                .n320(n320)
+                .misses(misses)
+                .hitresult_priority(HitResultPriority::WorstCase)
+                .calculate()
+                .unwrap()
+        };
+
+        let nomod = calculate(0, 100, 5);
+        let relax = calculate(128, 100, 5);
+
+        // Relax is not special-cased in osu!mania
+        assert_eq!(relax.zeroed, ZeroedComponents::default());
+        assert!((relax.pp - nomod.pp).abs() < f64::EPSILON);
+        assert_eq!(nomod.zeroed, ZeroedComponents::default());
+
+        let autopilot = calculate(8192, N_OBJECTS, 0);
+        assert!(autopilot.zeroed.difficulty);
+        assert!(autopilot.pp.abs() < f64::EPSILON);
+    }
+
     #[test]
     fn create() {
         let mut map = beatmap();
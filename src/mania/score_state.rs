@@ -1,5 +1,7 @@
 /// Aggregation for a score's current state.
 #[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct ManiaScoreState {
     /// Amount of current 320s.
     pub n320: u32,
@@ -6,6 +6,7 @@ use super::Beatmap;
 
 /// Summary struct for a [`Beatmap`]'s attributes.
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BeatmapAttributes {
     /// The approach rate.
     pub ar: f64,
@@ -23,6 +24,7 @@ pub struct BeatmapAttributes {
 
 /// AR and OD hit windows
 #[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HitWindows {
     /// Hit window for approach rate i.e. `TimePreempt` in milliseconds.
     pub ar: f64,
@@ -69,6 +69,17 @@ impl GameMods {
         }
     }
 
+    /// Returns the bitflags of all legacy mods.
+    ///
+    /// Mods without a legacy representation are ignored.
+    pub(crate) fn bits(&self) -> u32 {
+        match self.inner {
+            GameModsInner::Lazer(ref mods) => mods.bits(),
+            GameModsInner::Intermode(ref mods) => mods.bits(),
+            GameModsInner::Legacy(mods) => mods.bits(),
+        }
+    }
+
     pub(crate) fn od_ar_hp_multiplier(&self) -> f64 {
         if self.hr() {
             1.4
@@ -2,6 +2,8 @@ use crate::osu::performance::OsuPerformance;
 
 /// The result of a difficulty calculation on an osu!standard map.
 #[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct OsuDifficultyAttributes {
     /// The difficulty of the aim skill.
     pub aim: f64,
@@ -9,6 +11,16 @@ pub struct OsuDifficultyAttributes {
     pub speed: f64,
     /// The difficulty of the flashlight skill.
     pub flashlight: f64,
+    /// The difficulty of the autopilot skill i.e. the pure tapping
+    /// difficulty.
+    ///
+    /// Only calculated if the autopilot mod is enabled, `0.0` otherwise.
+    pub autopilot: f64,
+    /// The aim difficulty of streams on relax.
+    ///
+    /// Already included in [`aim`](Self::aim).
+    /// Only calculated if the relax mod is enabled, `0.0` otherwise.
+    pub relax_stream_aim: f64,
     /// The ratio of the aim strain with and without considering sliders
     pub slider_factor: f64,
     /// The number of clickable objects weighted by difficulty.
@@ -17,6 +29,8 @@ pub struct OsuDifficultyAttributes {
     pub aim_difficult_strain_count: f64,
     /// Weighted sum of speed strains.
     pub speed_difficult_strain_count: f64,
+    /// Weighted sum of autopilot strains.
+    pub autopilot_difficult_strain_count: f64,
     /// The approach rate.
     pub ar: f64,
     /// The overall difficulty
@@ -63,6 +77,8 @@ impl OsuDifficultyAttributes {
 
 /// The result of a performance calculation on an osu!standard map.
 #[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct OsuPerformanceAttributes {
     /// The difficulty attributes that were used for the performance calculation
     pub difficulty: OsuDifficultyAttributes,
@@ -76,8 +92,52 @@ pub struct OsuPerformanceAttributes {
     pub pp_flashlight: f64,
     /// The speed portion of the final pp.
     pub pp_speed: f64,
+    /// The tapping portion of the final pp.
+    ///
+    /// Replaces the speed portion on autopilot.
+    pub pp_tapping: f64,
     /// Misses including an approximated amount of slider breaks
     pub effective_miss_count: f64,
+    /// The components that make up the effective miss count.
+    pub miss_breakdown: OsuMissBreakdown,
+    /// Upper bound on the player's tap deviation based on the hitresults.
+    pub estimated_unstable_rate: Option<f64>,
+    /// The unstable rate that was measured through hit offsets, if any.
+    ///
+    /// If available, it is used for the accuracy of hit circles instead of
+    /// the hitresults.
+    pub measured_unstable_rate: Option<f64>,
+}
+
+/// The components of [`OsuPerformanceAttributes::effective_miss_count`].
+///
+/// All components sum up to the effective miss count.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct OsuMissBreakdown {
+    /// The actual misses of the score.
+    pub misses: f64,
+    /// Slider breaks approximated through the missing combo.
+    ///
+    /// Only used for scores without slider accuracy, e.g. osu!stable scores.
+    pub combo_breaks: f64,
+    /// Slider breaks approximated through the missing combo, limited by the
+    /// amount of missed slider ends and large ticks.
+    ///
+    /// Only used for osu!lazer scores with slider accuracy.
+    pub slider_breaks: f64,
+    /// 100s that are counted as misses on relax.
+    pub n100: f64,
+    /// 50s that are counted as misses on relax.
+    pub n50: f64,
+}
+
+impl OsuMissBreakdown {
+    /// Return the sum of all components i.e. the effective miss count.
+    pub fn total(&self) -> f64 {
+        self.misses + self.combo_breaks + self.slider_breaks + self.n100 + self.n50
+    }
 }
 
 impl OsuPerformanceAttributes {
@@ -9,14 +9,16 @@ use crate::{
         convert::convert_objects,
         object::{OsuObject, OsuObjectKind},
     },
+    util::checkpoints::{Checkpoints, Seek},
     Beatmap, Difficulty,
 };
 
 use self::osu_objects::OsuObjects;
 
 use super::{
-    object::OsuDifficultyObject, skills::OsuSkills, DifficultyValues, OsuDifficultyAttributes,
-    OsuDifficultySetup,
+    object::OsuDifficultyObject,
+    skills::{OsuSkills, OsuSkillsHistoryLen},
+    DifficultyValues, OsuDifficultyAttributes, OsuDifficultySetup,
 };
 
 /// Gradually calculate the difficulty attributes of an osu!standard map.
@@ -62,6 +64,7 @@ pub struct OsuGradualDifficulty {
     // `osu_objects` will immediately invalidate `diff_objects`.
     diff_objects: Box<[OsuDifficultyObject<'static>]>,
     osu_objects: OsuObjects,
+    checkpoints: Checkpoints<OsuCheckpoint>,
     // Additional safety measure that this type can't be cloned as it would
     // invalidate `diff_objects`.
     _not_clonable: NotClonable,
@@ -69,6 +72,18 @@ pub struct OsuGradualDifficulty {
 
 struct NotClonable;
 
+/// The state after processing `idx` hit objects.
+///
+/// The skills are stored without their strain histories. Instead, restoring
+/// truncates the current histories to the stored lengths.
+#[derive(Clone)]
+pub struct OsuCheckpoint {
+    idx: usize,
+    attrs: OsuDifficultyAttributes,
+    skills: OsuSkills,
+    history_len: OsuSkillsHistoryLen,
+}
+
 impl OsuGradualDifficulty {
     /// Create a new difficulty attributes iterator for osu!standard maps.
     pub fn new(difficulty: Difficulty, map: &Beatmap) -> Result<Self, ConvertError> {
@@ -109,9 +124,18 @@ impl OsuGradualDifficulty {
             osu_objects.iter_mut(),
         );
 
-        let skills = OsuSkills::new(mods, &scaling_factor, &map_attrs, time_preempt);
+        let mut skills = OsuSkills::new(mods, &scaling_factor, &map_attrs, time_preempt);
         let diff_objects = extend_lifetime(diff_objects.into_boxed_slice());
 
+        let (initial_skills, history_len) = skills.clone_without_history();
+
+        let checkpoints = Checkpoints::new(OsuCheckpoint {
+            idx: 0,
+            attrs: attrs.clone(),
+            skills: initial_skills,
+            history_len,
+        });
+
         Ok(Self {
             idx: 0,
             difficulty,
@@ -119,10 +143,53 @@ impl OsuGradualDifficulty {
             skills,
             diff_objects,
             osu_objects,
+            checkpoints,
             _not_clonable: NotClonable,
         })
     }
 
+    /// Store the state after every `interval` hit objects so that seeking
+    /// backwards with [`seek`] has to process at most `interval` hit objects.
+    ///
+    /// Checkpoints are stored while hit objects are being processed. Each one
+    /// only contains the current state of the skills and the lengths of their
+    /// strain histories so its size does not grow with the amount of processed
+    /// hit objects.
+    ///
+    /// [`seek`]: OsuGradualDifficulty::seek
+    #[must_use]
+    pub fn checkpoints(mut self, interval: usize) -> Self {
+        self.checkpoints.set_interval(interval);
+
+        self
+    }
+
+    /// Move to the hit object at index `idx`, which may be before or after
+    /// the current one, and return the difficulty attributes after
+    /// processing it.
+    ///
+    /// The attributes are the same as for `nth(idx)` on a new iterator and
+    /// [`Iterator::next`] will continue with the hit object at `idx + 1`.
+    ///
+    /// Seeking backwards restores the latest checkpoint before `idx` or,
+    /// without [`checkpoints`], processes all hit objects from the start
+    /// again. Seeking forwards always processes all hit objects between the
+    /// current one and `idx` because restoring a checkpoint discards the
+    /// strain history after it.
+    ///
+    /// Returns `None` if `idx` is out of bounds.
+    ///
+    /// [`checkpoints`]: OsuGradualDifficulty::checkpoints
+    pub fn seek(&mut self, idx: usize) -> Option<OsuDifficultyAttributes> {
+        if idx >= self.idx + self.len() {
+            return None;
+        }
+
+        self.seek_to(idx);
+
+        self.next()
+    }
+
     fn increment_combo(h: &OsuObject, attrs: &mut OsuDifficultyAttributes) {
         attrs.max_combo += 1;
 
@@ -151,6 +218,8 @@ impl Iterator for OsuGradualDifficulty {
     type Item = OsuDifficultyAttributes;
 
     fn next(&mut self) -> Option<Self::Item> {
+        self.record_checkpoint();
+
         // The first difficulty object belongs to the second note since each
         // difficulty object requires the current and the last note. Hence, if
         // we're still on the first object, we don't have a difficulty object
@@ -162,6 +231,8 @@ impl Iterator for OsuGradualDifficulty {
             Skill::new(&mut self.skills.aim_no_sliders, &self.diff_objects).process(curr);
             Skill::new(&mut self.skills.speed, &self.diff_objects).process(curr);
             Skill::new(&mut self.skills.flashlight, &self.diff_objects).process(curr);
+            Skill::new(&mut self.skills.autopilot, &self.diff_objects).process(curr);
+            Skill::new(&mut self.skills.relax_stream_aim, &self.diff_objects).process(curr);
 
             Self::increment_combo(curr.base, &mut self.attrs);
         } else if self.osu_objects.is_empty() {
@@ -172,20 +243,11 @@ impl Iterator for OsuGradualDifficulty {
 
         let mut attrs = self.attrs.clone();
 
-        let aim_difficulty_value = self.skills.aim.as_difficulty_value();
-        let aim_no_sliders_difficulty_value = self.skills.aim_no_sliders.as_difficulty_value();
-        let speed_relevant_note_count = self.skills.speed.relevant_note_count();
-        let speed_difficulty_value = self.skills.speed.as_difficulty_value();
-        let flashlight_difficulty_value = self.skills.flashlight.as_difficulty_value();
-
         DifficultyValues::eval(
             &mut attrs,
             self.difficulty.get_mods(),
-            &aim_difficulty_value,
-            &aim_no_sliders_difficulty_value,
-            &speed_difficulty_value,
-            speed_relevant_note_count,
-            flashlight_difficulty_value,
+            self.difficulty.get_relax_profile(),
+            &self.skills.as_difficulty_values(),
         );
 
         Some(attrs)
@@ -198,9 +260,55 @@ impl Iterator for OsuGradualDifficulty {
     }
 
     fn nth(&mut self, n: usize) -> Option<Self::Item> {
-        let skip_iter = self.diff_objects.iter().skip(self.idx.saturating_sub(1));
+        self.process(cmp::min(n, self.len().saturating_sub(1)));
+
+        self.next()
+    }
+}
+
+impl Seek for OsuGradualDifficulty {
+    type Snapshot = OsuCheckpoint;
+
+    const RESTORES_FORWARD: bool = false;
 
-        let mut take = cmp::min(n, self.len().saturating_sub(1));
+    fn idx(&self) -> usize {
+        self.idx
+    }
+
+    fn stored_checkpoints(&self) -> &Checkpoints<Self::Snapshot> {
+        &self.checkpoints
+    }
+
+    fn stored_checkpoints_mut(&mut self) -> &mut Checkpoints<Self::Snapshot> {
+        &mut self.checkpoints
+    }
+
+    fn snapshot(&mut self) -> Self::Snapshot {
+        let (skills, history_len) = self.skills.clone_without_history();
+
+        OsuCheckpoint {
+            idx: self.idx,
+            attrs: self.attrs.clone(),
+            skills,
+            history_len,
+        }
+    }
+
+    fn restore(&mut self, snapshot: Self::Snapshot) {
+        let OsuCheckpoint {
+            idx,
+            attrs,
+            skills,
+            history_len,
+        } = snapshot;
+
+        self.idx = idx;
+        self.attrs = attrs;
+        self.skills.restore_with_history(skills, history_len);
+    }
+
+    fn process(&mut self, mut take: usize) {
+        let skip_iter = self.diff_objects.iter().skip(self.idx.saturating_sub(1));
 
         // The first note has no difficulty object
         if self.idx == 0 && take > 0 {
@@ -212,18 +320,21 @@ impl Iterator for OsuGradualDifficulty {
         let mut aim_no_sliders = Skill::new(&mut self.skills.aim_no_sliders, &self.diff_objects);
         let mut speed = Skill::new(&mut self.skills.speed, &self.diff_objects);
         let mut flashlight = Skill::new(&mut self.skills.flashlight, &self.diff_objects);
+        let mut autopilot = Skill::new(&mut self.skills.autopilot, &self.diff_objects);
+        let mut relax_stream_aim =
+            Skill::new(&mut self.skills.relax_stream_aim, &self.diff_objects);
 
         for curr in skip_iter.take(take) {
             aim.process(curr);
             aim_no_sliders.process(curr);
             speed.process(curr);
             flashlight.process(curr);
+            autopilot.process(curr);
+            relax_stream_aim.process(curr);
 
             Self::increment_combo(curr.base, &mut self.attrs);
             self.idx += 1;
         }
-
-        self.next()
     }
 }
 
@@ -310,4 +421,31 @@ mod tests {
             assert_eq!(next_gradual, expected);
         }
     }
+
+    #[test]
+    fn seek() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+
+        let difficulty = Difficulty::new().mods(88); // HDHRDT
+
+        let expected: Vec<_> = OsuGradualDifficulty::new(difficulty.clone(), &map)
+            .unwrap()
+            .collect();
+
+        let mut gradual = OsuGradualDifficulty::new(difficulty, &map)
+            .unwrap()
+            .checkpoints(50);
+
+        let last = expected.len() - 1;
+
+        for idx in [300, 10, 0, 299, 301, last, 150, 51, 50, 49] {
+            assert_eq!(gradual.seek(idx).as_ref(), Some(&expected[idx]), "{idx}");
+        }
+
+        assert!(gradual.seek(last + 1).is_none());
+
+        gradual.seek(20);
+        assert_eq!(gradual.next().as_ref(), Some(&expected[21]));
+        assert_eq!(gradual.len(), last - 21);
+    }
 }
@@ -1,13 +1,10 @@
 use std::{cmp, pin::Pin};
 
 use rosu_map::section::general::GameMode;
-use skills::{
-    flashlight::Flashlight,
-    strain::{DifficultyValue, OsuStrainSkill, UsedOsuStrainSkills},
-};
+use skills::{flashlight::Flashlight, strain::OsuStrainSkill};
 
 use crate::{
-    any::difficulty::{skills::Skill, Difficulty},
+    any::difficulty::{skills::Skill, Difficulty, RelaxProfile},
     model::{beatmap::BeatmapAttributes, mode::ConvertError, mods::GameMods},
     osu::{
         convert::convert_objects,
@@ -18,7 +15,7 @@ use crate::{
     Beatmap,
 };
 
-use self::skills::OsuSkills;
+use self::skills::{OsuSkillValues, OsuSkills};
 
 use super::attributes::OsuDifficultyAttributes;
 
@@ -38,33 +35,13 @@ pub fn difficulty(
 ) -> Result<OsuDifficultyAttributes, ConvertError> {
     let map = map.convert_ref(GameMode::Osu, difficulty.get_mods())?;
 
-    let DifficultyValues {
-        skills:
-            OsuSkills {
-                aim,
-                aim_no_sliders,
-                speed,
-                flashlight,
-            },
-        mut attrs,
-    } = DifficultyValues::calculate(difficulty, &map);
-
-    let aim_difficulty_value = aim.difficulty_value();
-    let aim_no_sliders_difficulty_value = aim_no_sliders.difficulty_value();
-    let speed_relevant_note_count = speed.relevant_note_count();
-    let speed_difficulty_value = speed.difficulty_value();
-    let flashlight_difficulty_value = flashlight.difficulty_value();
-
-    let mods = difficulty.get_mods();
+    let DifficultyValues { skills, mut attrs } = DifficultyValues::calculate(difficulty, &map);
 
     DifficultyValues::eval(
         &mut attrs,
-        mods,
-        &aim_difficulty_value,
-        &aim_no_sliders_difficulty_value,
-        &speed_difficulty_value,
-        speed_relevant_note_count,
-        flashlight_difficulty_value,
+        difficulty.get_mods(),
+        difficulty.get_relax_profile(),
+        &skills.difficulty_values(),
     );
 
     Ok(attrs)
@@ -139,6 +116,8 @@ impl DifficultyValues {
             let mut aim_no_sliders = Skill::new(&mut skills.aim_no_sliders, &diff_objects);
             let mut speed = Skill::new(&mut skills.speed, &diff_objects);
             let mut flashlight = Skill::new(&mut skills.flashlight, &diff_objects);
+            let mut autopilot = Skill::new(&mut skills.autopilot, &diff_objects);
+            let mut relax_stream_aim = Skill::new(&mut skills.relax_stream_aim, &diff_objects);
 
             // The first hit object has no difficulty object
             let take_diff_objects = cmp::min(map.hit_objects.len(), take).saturating_sub(1);
@@ -148,6 +127,8 @@ impl DifficultyValues {
                 aim_no_sliders.process(hit_object);
                 speed.process(hit_object);
                 flashlight.process(hit_object);
+                autopilot.process(hit_object);
+                relax_stream_aim.process(hit_object);
             }
         }
 
@@ -158,17 +139,27 @@ impl DifficultyValues {
     pub fn eval(
         attrs: &mut OsuDifficultyAttributes,
         mods: &GameMods,
-        aim: &UsedOsuStrainSkills<DifficultyValue>,
-        aim_no_sliders: &UsedOsuStrainSkills<DifficultyValue>,
-        speed: &UsedOsuStrainSkills<DifficultyValue>,
-        speed_relevant_note_count: f64,
-        flashlight_difficulty_value: f64,
+        profile: &RelaxProfile,
+        values: &OsuSkillValues,
     ) {
+        let OsuSkillValues {
+            aim,
+            aim_no_sliders,
+            speed,
+            speed_relevant_note_count,
+            flashlight: flashlight_difficulty_value,
+            autopilot,
+            relax_stream_aim,
+        } = values;
+
         let mut aim_rating = aim.difficulty_value().sqrt() * DIFFICULTY_MULTIPLIER;
         let aim_rating_no_sliders =
             aim_no_sliders.difficulty_value().sqrt() * DIFFICULTY_MULTIPLIER;
         let mut speed_rating = speed.difficulty_value().sqrt() * DIFFICULTY_MULTIPLIER;
         let mut flashlight_rating = flashlight_difficulty_value.sqrt() * DIFFICULTY_MULTIPLIER;
+        let autopilot_rating = autopilot.difficulty_value().sqrt() * DIFFICULTY_MULTIPLIER;
+        let mut relax_stream_aim_rating =
+            relax_stream_aim.difficulty_value().sqrt() * DIFFICULTY_MULTIPLIER;
 
         let slider_factor = if aim_rating > 0.0 {
             aim_rating_no_sliders / aim_rating
@@ -178,25 +169,41 @@ impl DifficultyValues {
 
         let aim_difficult_strain_count = aim.count_difficult_strains();
         let speed_difficult_strain_count = speed.count_difficult_strains();
+        let autopilot_difficult_strain_count = autopilot.count_difficult_strains();
 
         if mods.td() {
             aim_rating = aim_rating.powf(0.8);
             flashlight_rating = flashlight_rating.powf(0.8);
+            relax_stream_aim_rating = relax_stream_aim_rating.powf(0.8);
         }
 
         if mods.rx() {
-            aim_rating *= 0.9;
-            speed_rating = 0.0;
-            flashlight_rating *= 0.7;
+            aim_rating *= profile.rx_aim_multiplier;
+            speed_rating *= profile.rx_speed_multiplier;
+            flashlight_rating *= profile.rx_flashlight_multiplier;
+            relax_stream_aim_rating *= profile.rx_stream_aim_multiplier;
+
+            // Streams need to be aimed on relax so their aim difficulty
+            // complements the regular aim rating. Ratings are square roots of
+            // difficulty values so `hypot` adds up both difficulty values as
+            // if they were one skill. Aim never decreases and maps without
+            // streams keep their aim rating.
+            aim_rating = aim_rating.hypot(relax_stream_aim_rating);
         }
 
         if mods.ap() {
-            aim_rating = 0.0;
-            flashlight_rating *= 0.4;
+            aim_rating *= profile.ap_aim_multiplier;
+            flashlight_rating *= profile.ap_flashlight_multiplier;
         }
 
         let base_aim_performance = OsuStrainSkill::difficulty_to_performance(aim_rating);
-        let base_speed_performance = OsuStrainSkill::difficulty_to_performance(speed_rating);
+
+        // Autopilot takes care of the movement so only the tapping matters
+        let base_speed_performance = if mods.ap() {
+            OsuStrainSkill::difficulty_to_performance(autopilot_rating)
+        } else {
+            OsuStrainSkill::difficulty_to_performance(speed_rating)
+        };
 
         let base_flashlight_performance = if mods.fl() {
             Flashlight::difficulty_to_performance(flashlight_rating)
@@ -220,11 +227,14 @@ impl DifficultyValues {
         attrs.aim = aim_rating;
         attrs.speed = speed_rating;
         attrs.flashlight = flashlight_rating;
+        attrs.autopilot = autopilot_rating;
+        attrs.relax_stream_aim = relax_stream_aim_rating;
         attrs.slider_factor = slider_factor;
         attrs.aim_difficult_strain_count = aim_difficult_strain_count;
         attrs.speed_difficult_strain_count = speed_difficult_strain_count;
+        attrs.autopilot_difficult_strain_count = autopilot_difficult_strain_count;
         attrs.stars = star_rating;
-        attrs.speed_note_count = speed_relevant_note_count;
+        attrs.speed_note_count = *speed_relevant_note_count;
     }
 
     pub fn create_difficulty_objects<'a>(
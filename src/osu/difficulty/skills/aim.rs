@@ -3,17 +3,26 @@ use std::f64::consts::{FRAC_PI_2, PI};
 use crate::{
     any::difficulty::{
         object::IDifficultyObject,
-        skills::{strain_decay, ISkill, Skill},
+        skills::{strain_decay, ISkill},
     },
     osu::difficulty::object::OsuDifficultyObject,
-    util::{float_ext::FloatExt, strains_vec::StrainsVec},
+    util::float_ext::FloatExt,
+    GameMods,
 };
 
-use super::strain::{DifficultyValue, OsuStrainSkill, UsedOsuStrainSkills};
+use super::strain::{DecayingSkill, OsuStrainSkill};
 
 const SKILL_MULTIPLIER: f64 = 25.18;
 const STRAIN_DECAY_BASE: f64 = 0.15;
 
+// Stream strains are built up by many closely spaced notes so they decay
+// slower than aim strains. The multiplier keeps stream aim a complement to the
+// aim rating instead of outweighing it; stream aim adds ~5% to the relax aim
+// rating of `resources/2785319.osu` and ~14% with DT, where its bursts become
+// streams.
+const STREAM_AIM_SKILL_MULTIPLIER: f64 = 12.0;
+const STREAM_AIM_STRAIN_DECAY_BASE: f64 = 0.3;
+
 #[derive(Clone)]
 pub struct Aim {
     with_sliders: bool,
@@ -29,84 +38,41 @@ impl Aim {
             inner: OsuStrainSkill::default(),
         }
     }
-
-    pub fn get_curr_strain_peaks(self) -> StrainsVec {
-        self.inner.get_curr_strain_peaks().strains()
-    }
-
-    pub fn difficulty_value(self) -> UsedOsuStrainSkills<DifficultyValue> {
-        Self::static_difficulty_value(self.inner)
-    }
-
-    /// Use [`difficulty_value`] instead whenever possible because
-    /// [`as_difficulty_value`] clones internally.
-    pub fn as_difficulty_value(&self) -> UsedOsuStrainSkills<DifficultyValue> {
-        Self::static_difficulty_value(self.inner.clone())
-    }
-
-    fn static_difficulty_value(skill: OsuStrainSkill) -> UsedOsuStrainSkills<DifficultyValue> {
-        skill.difficulty_value(
-            OsuStrainSkill::REDUCED_SECTION_COUNT,
-            OsuStrainSkill::REDUCED_STRAIN_BASELINE,
-            OsuStrainSkill::DECAY_WEIGHT,
-        )
-    }
 }
 
 impl ISkill for Aim {
     type DifficultyObjects<'a> = [OsuDifficultyObject<'a>];
 }
 
-impl<'a> Skill<'a, Aim> {
-    fn calculate_initial_strain(&mut self, time: f64, curr: &'a OsuDifficultyObject<'a>) -> f64 {
-        let prev_start_time = curr
-            .previous(0, self.diff_objects)
-            .map_or(0.0, |prev| prev.start_time);
+impl DecayingSkill for Aim {
+    const STRAIN_DECAY_BASE: f64 = STRAIN_DECAY_BASE;
 
-        self.inner.curr_strain * strain_decay(time - prev_start_time, STRAIN_DECAY_BASE)
+    fn strain_skill(&self) -> &OsuStrainSkill {
+        &self.inner
     }
 
-    fn curr_section_peak(&self) -> f64 {
-        self.inner.inner.inner.curr_section_peak
+    fn strain_skill_mut(&mut self) -> &mut OsuStrainSkill {
+        &mut self.inner
     }
 
-    fn curr_section_peak_mut(&mut self) -> &mut f64 {
-        &mut self.inner.inner.inner.curr_section_peak
+    fn into_strain_skill(self) -> OsuStrainSkill {
+        self.inner
     }
 
-    fn curr_section_end(&self) -> f64 {
-        self.inner.inner.inner.curr_section_end
+    fn curr_strain(&self) -> f64 {
+        self.curr_strain
     }
 
-    fn curr_section_end_mut(&mut self) -> &mut f64 {
-        &mut self.inner.inner.inner.curr_section_end
-    }
-
-    pub fn process(&mut self, curr: &'a OsuDifficultyObject<'a>) {
-        if curr.idx == 0 {
-            *self.curr_section_end_mut() = (curr.start_time / OsuStrainSkill::SECTION_LEN).ceil()
-                * OsuStrainSkill::SECTION_LEN;
-        }
-
-        while curr.start_time > self.curr_section_end() {
-            self.inner.inner.save_curr_peak();
-            let initial_strain = self.calculate_initial_strain(self.curr_section_end(), curr);
-            self.inner.inner.start_new_section_from(initial_strain);
-            *self.curr_section_end_mut() += OsuStrainSkill::SECTION_LEN;
-        }
-
-        let strain_value_at = self.strain_value_at(curr);
-        *self.curr_section_peak_mut() = strain_value_at.max(self.curr_section_peak());
-    }
-
-    fn strain_value_at(&mut self, curr: &'a OsuDifficultyObject<'a>) -> f64 {
-        self.inner.curr_strain *= strain_decay(curr.delta_time, STRAIN_DECAY_BASE);
-        self.inner.curr_strain +=
-            AimEvaluator::evaluate_diff_of(curr, self.diff_objects, self.inner.with_sliders)
-                * SKILL_MULTIPLIER;
-        self.inner.inner.object_strains.push(self.inner.curr_strain);
+    fn strain_value_at<'a>(
+        &mut self,
+        curr: &'a OsuDifficultyObject<'a>,
+        diff_objects: &'a [OsuDifficultyObject<'a>],
+    ) -> f64 {
+        self.curr_strain *= strain_decay(curr.delta_time, STRAIN_DECAY_BASE);
+        self.curr_strain += AimEvaluator::evaluate_diff_of(curr, diff_objects, self.with_sliders)
+            * SKILL_MULTIPLIER;
 
-        self.inner.curr_strain
+        self.curr_strain
     }
 }
 
@@ -271,3 +237,116 @@ impl AimEvaluator {
         1.0 - Self::calc_wide_angle_bonus(angle)
     }
 }
+
+/// Aim difficulty of streams and bursts on relax.
+///
+/// Without tapping, the difficulty of streams lies in moving the cursor
+/// along closely spaced notes at high BPM and keeping the cursor flow through
+/// changes of direction.
+///
+/// Only processes objects if the relax mod is enabled.
+#[derive(Clone)]
+pub struct StreamAim {
+    curr_strain: f64,
+    has_relax_mod: bool,
+    inner: OsuStrainSkill,
+}
+
+impl StreamAim {
+    pub fn new(mods: &GameMods) -> Self {
+        Self {
+            curr_strain: 0.0,
+            has_relax_mod: mods.rx(),
+            inner: OsuStrainSkill::default(),
+        }
+    }
+}
+
+impl ISkill for StreamAim {
+    type DifficultyObjects<'a> = [OsuDifficultyObject<'a>];
+}
+
+impl DecayingSkill for StreamAim {
+    const STRAIN_DECAY_BASE: f64 = STREAM_AIM_STRAIN_DECAY_BASE;
+
+    fn strain_skill(&self) -> &OsuStrainSkill {
+        &self.inner
+    }
+
+    fn strain_skill_mut(&mut self) -> &mut OsuStrainSkill {
+        &mut self.inner
+    }
+
+    fn into_strain_skill(self) -> OsuStrainSkill {
+        self.inner
+    }
+
+    fn curr_strain(&self) -> f64 {
+        self.curr_strain
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.has_relax_mod
+    }
+
+    fn strain_value_at<'a>(
+        &mut self,
+        curr: &'a OsuDifficultyObject<'a>,
+        diff_objects: &'a [OsuDifficultyObject<'a>],
+    ) -> f64 {
+        self.curr_strain *= strain_decay(curr.delta_time, STREAM_AIM_STRAIN_DECAY_BASE);
+        self.curr_strain +=
+            StreamAimEvaluator::evaluate_diff_of(curr, diff_objects) * STREAM_AIM_SKILL_MULTIPLIER;
+
+        self.curr_strain
+    }
+}
+
+struct StreamAimEvaluator;
+
+impl StreamAimEvaluator {
+    /// Notes further apart than this are considered jumps instead of streams.
+    const STREAM_SPACING_MAX: f64 = 125.0;
+    /// Notes slower than ~150BPM 1/4 are not considered part of a stream.
+    const STREAM_TIME_MAX: f64 = 100.0;
+    /// Notes faster than ~250BPM 1/4 get the full stream bonus.
+    const STREAM_TIME_MIN: f64 = 60.0;
+    const FLOW_BREAK_MULTIPLIER: f64 = 0.5;
+
+    fn evaluate_diff_of<'a>(
+        curr: &'a OsuDifficultyObject<'a>,
+        diff_objects: &'a [OsuDifficultyObject<'a>],
+    ) -> f64 {
+        let Some(last) = curr
+            .previous(0, diff_objects)
+            .filter(|last| !(curr.base.is_spinner() || last.base.is_spinner()))
+        else {
+            return 0.0;
+        };
+
+        let speed_factor = ((Self::STREAM_TIME_MAX - curr.strain_time)
+            / (Self::STREAM_TIME_MAX - Self::STREAM_TIME_MIN))
+            .clamp(0.0, 1.0);
+
+        if speed_factor.eq(0.0) {
+            return 0.0;
+        }
+
+        // Wide spacing is already covered by the regular aim skill
+        let dist = curr.lazy_jump_dist.min(Self::STREAM_SPACING_MAX);
+        let mut stream_strain = dist / curr.strain_time;
+
+        // Changing the direction within a stream breaks the cursor flow
+        if let Some(angle) = curr.angle {
+            let same_rhythm = curr.strain_time.max(last.strain_time)
+                < 1.25 * curr.strain_time.min(last.strain_time);
+
+            if same_rhythm {
+                let flow_break = ((PI - angle) / PI).clamp(0.0, 1.0);
+                stream_strain *= 1.0 + flow_break.powf(2.0) * Self::FLOW_BREAK_MULTIPLIER;
+            }
+        }
+
+        stream_strain * speed_factor
+    }
+}
@@ -0,0 +1,146 @@
+use crate::{
+    any::difficulty::{
+        object::IDifficultyObject,
+        skills::{strain_decay, ISkill},
+    },
+    osu::difficulty::object::OsuDifficultyObject,
+    GameMods,
+};
+
+use super::{
+    speed::RhythmEvaluator,
+    strain::{DecayingSkill, OsuStrainSkill},
+};
+
+const SKILL_MULTIPLIER: f64 = 1.430;
+const STRAIN_DECAY_BASE: f64 = 0.3;
+
+const REDUCED_SECTION_COUNT: usize = 5;
+
+/// Tapping difficulty for autopilot.
+///
+/// Since autopilot handles all cursor movement, only the rhythm complexity,
+/// the density of bursts, and the timing relative to the OD window are
+/// considered.
+///
+/// Only processes objects if the autopilot mod is enabled.
+#[derive(Clone)]
+pub struct Autopilot {
+    curr_strain: f64,
+    curr_rhythm: f64,
+    hit_window: f64,
+    has_autopilot_mod: bool,
+    inner: OsuStrainSkill,
+}
+
+impl Autopilot {
+    pub fn new(hit_window: f64, mods: &GameMods) -> Self {
+        Self {
+            curr_strain: 0.0,
+            curr_rhythm: 0.0,
+            hit_window,
+            has_autopilot_mod: mods.ap(),
+            inner: OsuStrainSkill::default(),
+        }
+    }
+}
+
+impl ISkill for Autopilot {
+    type DifficultyObjects<'a> = [OsuDifficultyObject<'a>];
+}
+
+impl DecayingSkill for Autopilot {
+    const STRAIN_DECAY_BASE: f64 = STRAIN_DECAY_BASE;
+    const REDUCED_SECTION_COUNT: usize = REDUCED_SECTION_COUNT;
+
+    fn strain_skill(&self) -> &OsuStrainSkill {
+        &self.inner
+    }
+
+    fn strain_skill_mut(&mut self) -> &mut OsuStrainSkill {
+        &mut self.inner
+    }
+
+    fn into_strain_skill(self) -> OsuStrainSkill {
+        self.inner
+    }
+
+    fn curr_strain(&self) -> f64 {
+        self.curr_strain * self.curr_rhythm
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.has_autopilot_mod
+    }
+
+    fn strain_value_at<'a>(
+        &mut self,
+        curr: &'a OsuDifficultyObject<'a>,
+        diff_objects: &'a [OsuDifficultyObject<'a>],
+    ) -> f64 {
+        self.curr_strain *= strain_decay(curr.strain_time, STRAIN_DECAY_BASE);
+        self.curr_strain += TappingEvaluator::evaluate_diff_of(curr, diff_objects, self.hit_window)
+            * SKILL_MULTIPLIER;
+        self.curr_rhythm = RhythmEvaluator::evaluate_diff_of(curr, diff_objects, self.hit_window);
+
+        self.curr_strain * self.curr_rhythm
+    }
+}
+
+struct TappingEvaluator;
+
+impl TappingEvaluator {
+    const MIN_SPEED_BONUS: f64 = 75.0; // ~200BPM
+    const SPEED_BALANCING_FACTOR: f64 = 40.0;
+    const BURST_HISTORY_MAX: usize = 16;
+    const BURST_MULTIPLIER: f64 = 0.3;
+
+    fn evaluate_diff_of<'a>(
+        curr: &'a OsuDifficultyObject<'a>,
+        diff_objects: &'a [OsuDifficultyObject<'a>],
+        hit_window: f64,
+    ) -> f64 {
+        if curr.base.is_spinner() {
+            return 0.0;
+        }
+
+        let osu_next_obj = curr.next(0, diff_objects);
+
+        let mut strain_time = curr.strain_time;
+        let doubletapness = 1.0 - curr.get_doubletapness(osu_next_obj, hit_window);
+
+        // * Cap deltatime to the OD 300 hitwindow.
+        // * 0.93 is derived from making sure 260bpm OD8 streams aren't nerfed harshly, whilst 0.92 limits the effect of the cap.
+        strain_time /= ((strain_time / hit_window) / 0.93).clamp(0.92, 1.0);
+
+        let speed_bonus = if strain_time < Self::MIN_SPEED_BONUS {
+            // * Add additional scaling bonus for streams/bursts higher than 200bpm
+            let base = (Self::MIN_SPEED_BONUS - strain_time) / Self::SPEED_BALANCING_FACTOR;
+
+            0.75 * base.powf(2.0)
+        } else {
+            0.0
+        };
+
+        // Without any movement, the difficulty of a burst mostly comes from
+        // keeping the same rhythm over many consecutive notes.
+        let delta_difference_eps = hit_window * 0.3;
+
+        let burst_len = (0..Self::BURST_HISTORY_MAX)
+            .map_while(|i| curr.previous(i, diff_objects))
+            .take_while(|prev| {
+                !prev.base.is_spinner()
+                    && (prev.strain_time - curr.strain_time).abs() < delta_difference_eps
+            })
+            .count();
+
+        let burst_bonus = (burst_len as f64 / Self::BURST_HISTORY_MAX as f64).sqrt()
+            * Self::BURST_MULTIPLIER
+            * (Self::MIN_SPEED_BONUS / strain_time).min(1.0);
+
+        let difficulty = (1.0 + speed_bonus + burst_bonus) * 1000.0 / strain_time;
+
+        // * Apply penalty if there's doubletappable doubles
+        difficulty * doubletapness
+    }
+}
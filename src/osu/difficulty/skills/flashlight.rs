@@ -3,7 +3,7 @@ use std::cmp;
 use crate::{
     any::difficulty::{
         object::IDifficultyObject,
-        skills::{strain_decay, ISkill, Skill, StrainSkill},
+        skills::{strain_decay, ISkill, Skill, StrainPeaksSkill, StrainSkill},
     },
     model::mods::GameMods,
     osu::{difficulty::object::OsuDifficultyObject, object::OsuObjectKind},
@@ -13,6 +13,7 @@ use crate::{
 const SKILL_MULTIPLIER: f64 = 0.05512;
 const STRAIN_DECAY_BASE: f64 = 0.15;
 
+#[derive(Clone)]
 pub struct Flashlight {
     curr_strain: f64,
     has_hidden_mod: bool,
@@ -59,6 +60,12 @@ impl ISkill for Flashlight {
     type DifficultyObjects<'a> = [OsuDifficultyObject<'a>];
 }
 
+impl StrainPeaksSkill for Flashlight {
+    fn strain_peaks_mut(&mut self) -> &mut StrainsVec {
+        &mut self.inner.strain_peaks
+    }
+}
+
 impl<'a> Skill<'a, Flashlight> {
     fn calculate_initial_strain(&mut self, time: f64, curr: &'a OsuDifficultyObject<'a>) -> f64 {
         let prev_start_time = curr
@@ -113,6 +120,7 @@ impl<'a> Skill<'a, Flashlight> {
     }
 }
 
+#[derive(Clone)]
 struct FlashlightEvaluator {
     scaling_factor: f64,
     time_preempt: f64,
@@ -1,22 +1,33 @@
 use crate::{
+    any::difficulty::skills::StrainPeaksSkill,
     model::{beatmap::BeatmapAttributes, mods::GameMods},
     osu::object::OsuObject,
 };
 
-use self::{aim::Aim, flashlight::Flashlight, speed::Speed};
+use self::{
+    aim::{Aim, StreamAim},
+    autopilot::Autopilot,
+    flashlight::Flashlight,
+    speed::Speed,
+    strain::{DecayingSkill, DifficultyValue, StrainHistoryLen, UsedOsuStrainSkills},
+};
 
 use super::{scaling_factor::ScalingFactor, HD_FADE_IN_DURATION_MULTIPLIER};
 
 pub mod aim;
+pub mod autopilot;
 pub mod flashlight;
 pub mod speed;
 pub mod strain;
 
+#[derive(Clone)]
 pub struct OsuSkills {
     pub aim: Aim,
     pub aim_no_sliders: Aim,
     pub speed: Speed,
     pub flashlight: Flashlight,
+    pub autopilot: Autopilot,
+    pub relax_stream_aim: StreamAim,
 }
 
 impl OsuSkills {
@@ -44,14 +55,131 @@ impl OsuSkills {
 
         let aim = Aim::new(true);
         let aim_no_sliders = Aim::new(false);
-        let speed = Speed::new(hit_window, mods);
+        let speed = Speed::new(hit_window);
         let flashlight = Flashlight::new(mods, scaling_factor.radius, time_preempt, time_fade_in);
+        let autopilot = Autopilot::new(hit_window, mods);
+        let relax_stream_aim = StreamAim::new(mods);
 
         Self {
             aim,
             aim_no_sliders,
             speed,
             flashlight,
+            autopilot,
+            relax_stream_aim,
+        }
+    }
+
+    /// Calculate the difficulty values of all skills.
+    pub fn difficulty_values(self) -> OsuSkillValues {
+        OsuSkillValues {
+            speed_relevant_note_count: self.speed.relevant_note_count(),
+            aim: self.aim.difficulty_value(),
+            aim_no_sliders: self.aim_no_sliders.difficulty_value(),
+            speed: self.speed.difficulty_value(),
+            flashlight: self.flashlight.difficulty_value(),
+            autopilot: self.autopilot.difficulty_value(),
+            relax_stream_aim: self.relax_stream_aim.difficulty_value(),
         }
     }
+
+    /// Use [`difficulty_values`] instead whenever possible because
+    /// [`as_difficulty_values`] clones internally.
+    ///
+    /// [`difficulty_values`]: OsuSkills::difficulty_values
+    /// [`as_difficulty_values`]: OsuSkills::as_difficulty_values
+    pub fn as_difficulty_values(&self) -> OsuSkillValues {
+        OsuSkillValues {
+            speed_relevant_note_count: self.speed.relevant_note_count(),
+            aim: self.aim.as_difficulty_value(),
+            aim_no_sliders: self.aim_no_sliders.as_difficulty_value(),
+            speed: self.speed.as_difficulty_value(),
+            flashlight: self.flashlight.as_difficulty_value(),
+            autopilot: self.autopilot.as_difficulty_value(),
+            relax_stream_aim: self.relax_stream_aim.as_difficulty_value(),
+        }
+    }
+
+    /// Clone the skills without their strain histories and return the
+    /// histories' lengths alongside.
+    pub fn clone_without_history(&mut self) -> (Self, OsuSkillsHistoryLen) {
+        let (aim, aim_len) = self.aim.clone_without_history();
+        let (aim_no_sliders, aim_no_sliders_len) = self.aim_no_sliders.clone_without_history();
+        let (speed, speed_len) = self.speed.clone_without_history();
+        let (flashlight, flashlight_len) = self.flashlight.clone_without_history();
+        let (autopilot, autopilot_len) = self.autopilot.clone_without_history();
+        let (relax_stream_aim, relax_stream_aim_len) =
+            self.relax_stream_aim.clone_without_history();
+
+        let skills = Self {
+            aim,
+            aim_no_sliders,
+            speed,
+            flashlight,
+            autopilot,
+            relax_stream_aim,
+        };
+
+        let len = OsuSkillsHistoryLen {
+            aim: aim_len,
+            aim_no_sliders: aim_no_sliders_len,
+            speed: speed_len,
+            flashlight: flashlight_len,
+            autopilot: autopilot_len,
+            relax_stream_aim: relax_stream_aim_len,
+        };
+
+        (skills, len)
+    }
+
+    /// Replace the skills with clones of [`clone_without_history`] whose
+    /// strain histories are the current ones truncated to `len`.
+    ///
+    /// The current skills must have processed at least as many objects as
+    /// the clones.
+    ///
+    /// [`clone_without_history`]: OsuSkills::clone_without_history
+    pub fn restore_with_history(&mut self, skills: Self, len: OsuSkillsHistoryLen) {
+        let Self {
+            aim,
+            aim_no_sliders,
+            speed,
+            flashlight,
+            autopilot,
+            relax_stream_aim,
+        } = skills;
+
+        self.aim.restore_with_history(aim, len.aim);
+        self.aim_no_sliders
+            .restore_with_history(aim_no_sliders, len.aim_no_sliders);
+        self.speed.restore_with_history(speed, len.speed);
+        self.flashlight
+            .restore_with_history(flashlight, len.flashlight);
+        self.autopilot
+            .restore_with_history(autopilot, len.autopilot);
+        self.relax_stream_aim
+            .restore_with_history(relax_stream_aim, len.relax_stream_aim);
+    }
+}
+
+/// The strain history lengths of [`OsuSkills`].
+#[derive(Copy, Clone)]
+pub struct OsuSkillsHistoryLen {
+    aim: StrainHistoryLen,
+    aim_no_sliders: StrainHistoryLen,
+    speed: StrainHistoryLen,
+    flashlight: usize,
+    autopilot: StrainHistoryLen,
+    relax_stream_aim: StrainHistoryLen,
+}
+
+/// The difficulty values of [`OsuSkills`].
+pub struct OsuSkillValues {
+    pub aim: UsedOsuStrainSkills<DifficultyValue>,
+    pub aim_no_sliders: UsedOsuStrainSkills<DifficultyValue>,
+    pub speed: UsedOsuStrainSkills<DifficultyValue>,
+    pub speed_relevant_note_count: f64,
+    pub flashlight: f64,
+    pub autopilot: UsedOsuStrainSkills<DifficultyValue>,
+    pub relax_stream_aim: UsedOsuStrainSkills<DifficultyValue>,
 }
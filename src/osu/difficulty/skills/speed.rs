@@ -6,14 +6,12 @@ use std::{
 use crate::{
     any::difficulty::{
         object::IDifficultyObject,
-        skills::{strain_decay, ISkill, Skill},
+        skills::{strain_decay, ISkill},
     },
     osu::difficulty::object::OsuDifficultyObject,
-    util::strains_vec::StrainsVec,
-    GameMods,
 };
 
-use super::strain::{DifficultyValue, OsuStrainSkill, UsedOsuStrainSkills};
+use super::strain::{DecayingSkill, OsuStrainSkill};
 
 const SKILL_MULTIPLIER: f64 = 1.430;
 const STRAIN_DECAY_BASE: f64 = 0.3;
@@ -25,43 +23,19 @@ pub struct Speed {
     curr_strain: f64,
     curr_rhythm: f64,
     hit_window: f64,
-    has_autopilot_mod: bool,
     inner: OsuStrainSkill,
 }
 
 impl Speed {
-    pub fn new(hit_window: f64, mods: &GameMods) -> Self {
+    pub fn new(hit_window: f64) -> Self {
         Self {
             curr_strain: 0.0,
             curr_rhythm: 0.0,
             hit_window,
-            has_autopilot_mod: mods.ap(),
             inner: OsuStrainSkill::default(),
         }
     }
 
-    pub fn get_curr_strain_peaks(self) -> StrainsVec {
-        self.inner.get_curr_strain_peaks().strains()
-    }
-
-    pub fn difficulty_value(self) -> UsedOsuStrainSkills<DifficultyValue> {
-        Self::static_difficulty_value(self.inner)
-    }
-
-    /// Use [`difficulty_value`] instead whenever possible because
-    /// [`as_difficulty_value`] clones internally.
-    pub fn as_difficulty_value(&self) -> UsedOsuStrainSkills<DifficultyValue> {
-        Self::static_difficulty_value(self.inner.clone())
-    }
-
-    fn static_difficulty_value(skill: OsuStrainSkill) -> UsedOsuStrainSkills<DifficultyValue> {
-        skill.difficulty_value(
-            REDUCED_SECTION_COUNT,
-            OsuStrainSkill::REDUCED_STRAIN_BASELINE,
-            OsuStrainSkill::DECAY_WEIGHT,
-        )
-    }
-
     pub fn relevant_note_count(&self) -> f64 {
         self.inner
             .object_strains
@@ -81,64 +55,37 @@ impl ISkill for Speed {
     type DifficultyObjects<'a> = [OsuDifficultyObject<'a>];
 }
 
-impl<'a> Skill<'a, Speed> {
-    fn calculate_initial_strain(&mut self, time: f64, curr: &'a OsuDifficultyObject<'a>) -> f64 {
-        let prev_start_time = curr
-            .previous(0, self.diff_objects)
-            .map_or(0.0, |prev| prev.start_time);
-
-        (self.inner.curr_strain * self.inner.curr_rhythm)
-            * strain_decay(time - prev_start_time, STRAIN_DECAY_BASE)
-    }
+impl DecayingSkill for Speed {
+    const STRAIN_DECAY_BASE: f64 = STRAIN_DECAY_BASE;
+    const REDUCED_SECTION_COUNT: usize = REDUCED_SECTION_COUNT;
 
-    fn curr_section_peak(&self) -> f64 {
-        self.inner.inner.inner.curr_section_peak
+    fn strain_skill(&self) -> &OsuStrainSkill {
+        &self.inner
     }
 
-    fn curr_section_peak_mut(&mut self) -> &mut f64 {
-        &mut self.inner.inner.inner.curr_section_peak
+    fn strain_skill_mut(&mut self) -> &mut OsuStrainSkill {
+        &mut self.inner
     }
 
-    fn curr_section_end(&self) -> f64 {
-        self.inner.inner.inner.curr_section_end
+    fn into_strain_skill(self) -> OsuStrainSkill {
+        self.inner
     }
 
-    fn curr_section_end_mut(&mut self) -> &mut f64 {
-        &mut self.inner.inner.inner.curr_section_end
+    fn curr_strain(&self) -> f64 {
+        self.curr_strain * self.curr_rhythm
     }
 
-    pub fn process(&mut self, curr: &'a OsuDifficultyObject<'a>) {
-        if curr.idx == 0 {
-            *self.curr_section_end_mut() = (curr.start_time / OsuStrainSkill::SECTION_LEN).ceil()
-                * OsuStrainSkill::SECTION_LEN;
-        }
-
-        while curr.start_time > self.curr_section_end() {
-            self.inner.inner.save_curr_peak();
-            let initial_strain = self.calculate_initial_strain(self.curr_section_end(), curr);
-            self.inner.inner.start_new_section_from(initial_strain);
-            *self.curr_section_end_mut() += OsuStrainSkill::SECTION_LEN;
-        }
-
-        let strain_value_at = self.strain_value_at(curr);
-        *self.curr_section_peak_mut() = strain_value_at.max(self.curr_section_peak());
-    }
+    fn strain_value_at<'a>(
+        &mut self,
+        curr: &'a OsuDifficultyObject<'a>,
+        diff_objects: &'a [OsuDifficultyObject<'a>],
+    ) -> f64 {
+        self.curr_strain *= strain_decay(curr.strain_time, STRAIN_DECAY_BASE);
+        self.curr_strain += SpeedEvaluator::evaluate_diff_of(curr, diff_objects, self.hit_window)
+            * SKILL_MULTIPLIER;
+        self.curr_rhythm = RhythmEvaluator::evaluate_diff_of(curr, diff_objects, self.hit_window);
 
-    fn strain_value_at(&mut self, curr: &'a OsuDifficultyObject<'a>) -> f64 {
-        self.inner.curr_strain *= strain_decay(curr.strain_time, STRAIN_DECAY_BASE);
-        self.inner.curr_strain += SpeedEvaluator::evaluate_diff_of(
-            curr,
-            self.diff_objects,
-            self.inner.hit_window,
-            self.inner.has_autopilot_mod,
-        ) * SKILL_MULTIPLIER;
-        self.inner.curr_rhythm =
-            RhythmEvaluator::evaluate_diff_of(curr, self.diff_objects, self.inner.hit_window);
-
-        let total_strain = self.inner.curr_strain * self.inner.curr_rhythm;
-        self.inner.inner.object_strains.push(total_strain);
-
-        total_strain
+        self.curr_strain * self.curr_rhythm
     }
 }
 
@@ -154,7 +101,6 @@ impl SpeedEvaluator {
         curr: &'a OsuDifficultyObject<'a>,
         diff_objects: &'a [OsuDifficultyObject<'a>],
         hit_window: f64,
-        has_autopilot_mod: bool,
     ) -> f64 {
         if curr.base.is_spinner() {
             return 0.0;
@@ -186,14 +132,9 @@ impl SpeedEvaluator {
         };
 
         let travel_dist = osu_prev_obj.map_or(0.0, |obj| obj.travel_dist);
-        let mut dist = if has_autopilot_mod {
-            0.0
-        } else {
-            travel_dist + osu_curr_obj.min_jump_dist
-        };
 
         // * Cap distance at single_spacing_threshold
-        dist = Self::SINGLE_SPACING_THRESHOLD.min(dist);
+        let dist = Self::SINGLE_SPACING_THRESHOLD.min(travel_dist + osu_curr_obj.min_jump_dist);
 
         // * Max distance bonus is 1 * `distance_multiplier` at single_spacing_threshold
         let dist_bonus = (dist / Self::SINGLE_SPACING_THRESHOLD).powf(3.95) * Self::DIST_MULTIPLIER;
@@ -206,7 +147,7 @@ impl SpeedEvaluator {
     }
 }
 
-struct RhythmEvaluator;
+pub(super) struct RhythmEvaluator;
 
 impl RhythmEvaluator {
     const HISTORY_TIME_MAX: u32 = 5 * 1000; // 5 seconds
@@ -215,7 +156,7 @@ impl RhythmEvaluator {
     const RHYTHM_RATIO_MULTIPLIER: f64 = 12.0;
 
     #[allow(clippy::too_many_lines)]
-    fn evaluate_diff_of<'a>(
+    pub(super) fn evaluate_diff_of<'a>(
         curr: &'a OsuDifficultyObject<'a>,
         diff_objects: &'a [OsuDifficultyObject<'a>],
         hit_window: f64,
@@ -246,11 +187,10 @@ impl RhythmEvaluator {
 
         while curr
             .previous(rhythm_start, diff_objects)
-            .filter(|prev| {
+            .is_some_and(|prev| {
                 rhythm_start + 2 < historical_note_count
                     && curr.start_time - prev.start_time < f64::from(Self::HISTORY_TIME_MAX)
             })
-            .is_some()
         {
             rhythm_start += 1;
         }
@@ -1,4 +1,13 @@
-use crate::{any::difficulty::skills::StrainSkill, util::strains_vec::StrainsVec};
+use std::mem;
+
+use crate::{
+    any::difficulty::{
+        object::IDifficultyObject,
+        skills::{strain_decay, ISkill, Skill, StrainSkill},
+    },
+    osu::difficulty::object::OsuDifficultyObject,
+    util::strains_vec::StrainsVec,
+};
 
 #[derive(Clone)]
 pub struct OsuStrainSkill {
@@ -31,6 +40,38 @@ impl OsuStrainSkill {
         self.inner.start_new_section_from(initial_strain);
     }
 
+    pub fn history_len(&self) -> StrainHistoryLen {
+        StrainHistoryLen {
+            object_strains: self.object_strains.len(),
+            strain_peaks: self.inner.strain_peaks.len(),
+        }
+    }
+
+    /// Moves the strain history out, leaving an empty one behind.
+    pub fn take_history(&mut self) -> StrainHistory {
+        StrainHistory {
+            object_strains: mem::take(&mut self.object_strains),
+            strain_peaks: self.inner.take_strain_peaks(),
+        }
+    }
+
+    /// Puts back a strain history that was moved out through
+    /// [`take_history`], truncated to the given lengths.
+    ///
+    /// [`take_history`]: OsuStrainSkill::take_history
+    pub fn put_history(&mut self, history: StrainHistory, len: StrainHistoryLen) {
+        let StrainHistory {
+            mut object_strains,
+            mut strain_peaks,
+        } = history;
+
+        object_strains.truncate(len.object_strains);
+        strain_peaks.truncate(len.strain_peaks);
+
+        self.object_strains = object_strains;
+        self.inner.strain_peaks = strain_peaks;
+    }
+
     pub fn get_curr_strain_peaks(self) -> UsedOsuStrainSkills<StrainsVec> {
         UsedOsuStrainSkills {
             value: self.inner.get_curr_strain_peaks(),
@@ -78,6 +119,148 @@ impl OsuStrainSkill {
     }
 }
 
+/// The strain history of an [`OsuStrainSkill`].
+pub struct StrainHistory {
+    object_strains: Vec<f64>,
+    strain_peaks: StrainsVec,
+}
+
+/// The lengths of an [`OsuStrainSkill`]'s strain history.
+#[derive(Copy, Clone)]
+pub struct StrainHistoryLen {
+    object_strains: usize,
+    strain_peaks: usize,
+}
+
+/// An osu!standard skill whose strain decays exponentially between objects
+/// and whose peaks are tracked through an [`OsuStrainSkill`].
+///
+/// Implementors only evaluate the strain of each object; splitting the map
+/// into sections and calculating the difficulty value is shared.
+pub trait DecayingSkill: ISkill {
+    const STRAIN_DECAY_BASE: f64;
+    const REDUCED_SECTION_COUNT: usize = OsuStrainSkill::REDUCED_SECTION_COUNT;
+
+    fn strain_skill(&self) -> &OsuStrainSkill;
+
+    fn strain_skill_mut(&mut self) -> &mut OsuStrainSkill;
+
+    fn into_strain_skill(self) -> OsuStrainSkill;
+
+    /// The strain after the previous object, before decaying any further.
+    fn curr_strain(&self) -> f64;
+
+    /// Update the strain with the current object and return the new value.
+    fn strain_value_at<'a>(
+        &mut self,
+        curr: &'a OsuDifficultyObject<'a>,
+        diff_objects: &'a [OsuDifficultyObject<'a>],
+    ) -> f64;
+
+    /// Whether objects should be processed at all.
+    fn is_enabled(&self) -> bool {
+        true
+    }
+
+    fn get_curr_strain_peaks(self) -> StrainsVec
+    where
+        Self: Sized,
+    {
+        self.into_strain_skill().get_curr_strain_peaks().strains()
+    }
+
+    fn difficulty_value(self) -> UsedOsuStrainSkills<DifficultyValue>
+    where
+        Self: Sized,
+    {
+        self.into_strain_skill().difficulty_value(
+            Self::REDUCED_SECTION_COUNT,
+            OsuStrainSkill::REDUCED_STRAIN_BASELINE,
+            OsuStrainSkill::DECAY_WEIGHT,
+        )
+    }
+
+    /// Use [`difficulty_value`] instead whenever possible because
+    /// [`as_difficulty_value`] clones internally.
+    ///
+    /// [`difficulty_value`]: DecayingSkill::difficulty_value
+    /// [`as_difficulty_value`]: DecayingSkill::as_difficulty_value
+    fn as_difficulty_value(&self) -> UsedOsuStrainSkills<DifficultyValue> {
+        self.strain_skill().clone().difficulty_value(
+            Self::REDUCED_SECTION_COUNT,
+            OsuStrainSkill::REDUCED_STRAIN_BASELINE,
+            OsuStrainSkill::DECAY_WEIGHT,
+        )
+    }
+
+    /// Clone the skill without its strain history and return the history's
+    /// lengths alongside.
+    fn clone_without_history(&mut self) -> (Self, StrainHistoryLen)
+    where
+        Self: Clone,
+    {
+        let len = self.strain_skill().history_len();
+        let history = self.strain_skill_mut().take_history();
+        let clone = self.clone();
+        self.strain_skill_mut().put_history(history, len);
+
+        (clone, len)
+    }
+
+    /// Replace the skill with a clone of [`clone_without_history`] whose
+    /// strain history is the current one truncated to `len`.
+    ///
+    /// [`clone_without_history`]: DecayingSkill::clone_without_history
+    fn restore_with_history(&mut self, mut clone: Self, len: StrainHistoryLen)
+    where
+        Self: Sized,
+    {
+        let history = self.strain_skill_mut().take_history();
+        clone.strain_skill_mut().put_history(history, len);
+        *self = clone;
+    }
+}
+
+impl<'a, S> Skill<'a, S>
+where
+    S: DecayingSkill<DifficultyObjects<'a> = [OsuDifficultyObject<'a>]>,
+{
+    pub fn process(&mut self, curr: &'a OsuDifficultyObject<'a>) {
+        if !self.inner.is_enabled() {
+            return;
+        }
+
+        let prev_start_time = curr
+            .previous(0, self.diff_objects)
+            .map_or(0.0, |prev| prev.start_time);
+
+        let curr_strain = self.inner.curr_strain();
+        let skill = self.inner.strain_skill_mut();
+
+        if curr.idx == 0 {
+            skill.inner.curr_section_end = (curr.start_time / OsuStrainSkill::SECTION_LEN).ceil()
+                * OsuStrainSkill::SECTION_LEN;
+        }
+
+        while curr.start_time > skill.inner.curr_section_end {
+            skill.save_curr_peak();
+            let initial_strain = curr_strain
+                * strain_decay(
+                    skill.inner.curr_section_end - prev_start_time,
+                    S::STRAIN_DECAY_BASE,
+                );
+            skill.start_new_section_from(initial_strain);
+            skill.inner.curr_section_end += OsuStrainSkill::SECTION_LEN;
+        }
+
+        let strain_value_at = self.inner.strain_value_at(curr, self.diff_objects);
+
+        let skill = self.inner.strain_skill_mut();
+        skill.object_strains.push(strain_value_at);
+        skill.inner.curr_section_peak = strain_value_at.max(skill.inner.curr_section_peak);
+    }
+}
+
 fn lerp(start: f64, end: f64, amount: f64) -> f64 {
     start + (end - start) * amount
 }
@@ -9,7 +9,7 @@ use crate::{
 };
 
 pub use self::{
-    attributes::{OsuDifficultyAttributes, OsuPerformanceAttributes},
+    attributes::{OsuDifficultyAttributes, OsuMissBreakdown, OsuPerformanceAttributes},
     difficulty::gradual::OsuGradualDifficulty,
     performance::{gradual::OsuGradualPerformance, OsuPerformance},
     score_state::{OsuScoreOrigin, OsuScoreState},
@@ -17,9 +17,9 @@ pub use self::{
 };
 
 mod attributes;
-mod convert;
-mod difficulty;
-mod object;
+pub(crate) mod convert;
+pub(crate) mod difficulty;
+pub(crate) mod object;
 mod performance;
 mod score_state;
 mod strains;
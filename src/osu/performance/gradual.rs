@@ -121,6 +121,43 @@ impl OsuGradualPerformance {
         Some(performance)
     }
 
+    /// Store the state after every `interval` hit objects so that seeking
+    /// backwards with [`seek`] has to process at most `interval` hit objects.
+    ///
+    /// See [`OsuGradualDifficulty::checkpoints`] for more info.
+    ///
+    /// [`seek`]: OsuGradualPerformance::seek
+    #[must_use]
+    pub fn checkpoints(mut self, interval: usize) -> Self {
+        self.difficulty = self.difficulty.checkpoints(interval);
+
+        self
+    }
+
+    /// Move to the hit object at index `idx`, which may be before or after the
+    /// current one, and calculate the performance attributes for the
+    /// resulting score state.
+    ///
+    /// See [`OsuGradualDifficulty::seek`] for how many hit objects have to be
+    /// processed.
+    ///
+    /// Returns `None` if `idx` is out of bounds.
+    #[allow(clippy::missing_panics_doc)]
+    pub fn seek(&mut self, state: OsuScoreState, idx: usize) -> Option<OsuPerformanceAttributes> {
+        let performance = self
+            .difficulty
+            .seek(idx)?
+            .performance()
+            .lazer(self.lazer)
+            .state(state)
+            .difficulty(self.difficulty.difficulty.clone())
+            .passed_objects(self.difficulty.idx as u32)
+            .calculate()
+            .expect("no conversion required");
+
+        Some(performance)
+    }
+
     /// Returns the amount of remaining objects.
     #[allow(clippy::len_without_is_empty)]
     pub fn len(&self) -> usize {
@@ -181,4 +218,34 @@ mod tests {
             assert_eq!(next_gradual, expected);
         }
     }
+
+    #[test]
+    fn seek() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+
+        let difficulty = Difficulty::new().mods(88); // HDHRDT
+
+        let mut gradual = OsuGradualPerformance::new(difficulty.clone(), &map)
+            .unwrap()
+            .checkpoints(50);
+
+        let state = OsuScoreState {
+            max_combo: 100,
+            n300: 80,
+            n100: 10,
+            n50: 5,
+            misses: 3,
+            ..Default::default()
+        };
+
+        for idx in [300, 10, 120, 99, 100, 101] {
+            let expected = OsuGradualPerformance::new(difficulty.clone(), &map)
+                .unwrap()
+                .nth(state, idx);
+
+            assert_eq!(gradual.seek(state, idx), expected, "idx={idx}");
+        }
+
+        assert!(gradual.seek(state, map.hit_objects.len()).is_none());
+    }
 }
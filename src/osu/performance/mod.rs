@@ -3,17 +3,25 @@ use std::{borrow::Cow, cmp};
 use rosu_map::section::general::GameMode;
 
 use crate::{
-    any::{Difficulty, HitResultPriority, IntoModePerformance, IntoPerformance, Performance},
+    any::{
+        Difficulty, HitResultPriority, IntoModePerformance, IntoPerformance, Performance,
+        RelaxProfile,
+    },
     catch::CatchPerformance,
     mania::ManiaPerformance,
     model::{mode::ConvertError, mods::GameMods},
     taiko::TaikoPerformance,
-    util::{float_ext::FloatExt, map_or_attrs::MapOrAttrs},
+    util::{
+        deviation::{self, MeasuredUnstableRate},
+        float_ext::FloatExt,
+        map_or_attrs::MapOrAttrs,
+        search,
+    },
     Beatmap,
 };
 
 use super::{
-    attributes::{OsuDifficultyAttributes, OsuPerformanceAttributes},
+    attributes::{OsuDifficultyAttributes, OsuMissBreakdown, OsuPerformanceAttributes},
     difficulty::skills::{flashlight::Flashlight, strain::OsuStrainSkill},
     score_state::{OsuScoreOrigin, OsuScoreState},
     Osu,
@@ -36,6 +44,7 @@ pub struct OsuPerformance<'map> {
     pub(crate) n50: Option<u32>,
     pub(crate) misses: Option<u32>,
     pub(crate) hitresult_priority: HitResultPriority,
+    pub(crate) unstable_rate: Option<MeasuredUnstableRate>,
 }
 
 impl<'map> OsuPerformance<'map> {
@@ -165,6 +174,15 @@ impl<'map> OsuPerformance<'map> {
         self
     }
 
+    /// Adjust the relax and autopilot handling.
+    ///
+    /// Defaults to [`RelaxProfile::DEFAULT`].
+    pub fn relax_profile(mut self, relax_profile: RelaxProfile) -> Self {
+        self.difficulty = self.difficulty.relax_profile(relax_profile);
+
+        self
+    }
+
     /// Specify the amount of "large tick" hits.
     ///
     /// The meaning depends on the kind of score:
@@ -313,6 +331,29 @@ impl<'map> OsuPerformance<'map> {
         self
     }
 
+    /// Specify the measured unstable rate of the play.
+    ///
+    /// The value must already be adjusted by the clock rate, i.e. as displayed
+    /// by osu!. If specified, the accuracy of hit circles will be based on
+    /// the unstable rate instead of the hitresults.
+    pub const fn unstable_rate(mut self, unstable_rate: f64) -> Self {
+        self.unstable_rate = Some(MeasuredUnstableRate::Adjusted(unstable_rate));
+
+        self
+    }
+
+    /// Specify the hit offsets of the play in milliseconds to measure the
+    /// unstable rate.
+    ///
+    /// The offsets must be in map time, i.e. they will still be adjusted by
+    /// the clock rate. If fewer than two offsets are given, no unstable rate
+    /// will be measured.
+    pub fn hit_offsets(mut self, hit_offsets: impl IntoIterator<Item = f64>) -> Self {
+        self.unstable_rate = MeasuredUnstableRate::from_hit_offsets(hit_offsets);
+
+        self
+    }
+
     /// Provide parameters through an [`OsuScoreState`].
     #[allow(clippy::needless_pass_by_value)]
     pub const fn state(mut self, state: OsuScoreState) -> Self {
@@ -645,6 +686,85 @@ impl<'map> OsuPerformance<'map> {
         })
     }
 
+    /// Adjust the current score so that it becomes a full combo.
+    ///
+    /// Misses are turned into n300s while n100s and n50s are kept as they
+    /// are. On lazer, all slider ends and large ticks are considered hit.
+    /// The combo is set to the map's max combo.
+    pub fn if_fc(mut self) -> Result<Self, ConvertError> {
+        let state = self.generate_state()?;
+
+        self.n300 = Some(state.n300 + state.misses);
+        self.n100 = Some(state.n100);
+        self.n50 = Some(state.n50);
+        self.misses = Some(0);
+        self.combo = None;
+        self.large_tick_hits = None;
+        self.slider_end_hits = None;
+        self.acc = None;
+
+        Ok(self)
+    }
+
+    /// Modify the current [`OsuScoreState`] through the given function.
+    ///
+    /// Unspecified hitresults are generated before the function is called so
+    /// it always sees complete n300, n100, n50, and miss counts. All adjusted
+    /// values, including slider end and large tick hits, are used as is and
+    /// a previously specified accuracy is discarded.
+    pub fn what_if<F>(mut self, f: F) -> Result<Self, ConvertError>
+    where
+        F: FnOnce(&mut OsuScoreState),
+    {
+        let mut state = self.generate_state()?;
+        f(&mut state);
+        self.acc = None;
+
+        Ok(self.state(state))
+    }
+
+    /// Find the minimal accuracy between `0.0` and `100.0` that is required
+    /// to reach the given amount of pp.
+    ///
+    /// All hitresults except for misses are generated from the accuracy while all
+    /// other parameters are kept as they are.
+    ///
+    /// Returns `None` if the pp can't be reached even with 100% accuracy.
+    pub fn accuracy_for_pp(self, pp: f64) -> Result<Option<f64>, ConvertError> {
+        let (mut perf, _) = self.with_attrs()?;
+        perf.n300 = None;
+        perf.n100 = None;
+        perf.n50 = None;
+
+        Ok(search::min_f64(0.0, 100.0, |acc| {
+            perf.clone().accuracy(acc).reaches_pp(pp)
+        }))
+    }
+
+    /// Find the maximal amount of misses for which the given amount of pp
+    /// can still be reached.
+    ///
+    /// Returns `None` if the pp can't be reached even without misses.
+    pub fn misses_for_pp(self, pp: f64) -> Result<Option<u32>, ConvertError> {
+        let (perf, attrs) = self.with_attrs()?;
+
+        Ok(search::max_u32(0, attrs.n_objects(), |misses| {
+            perf.clone().misses(misses).reaches_pp(pp)
+        }))
+    }
+
+    /// Find the minimal combo that is required to reach the given amount of
+    /// pp.
+    ///
+    /// Returns `None` if the pp can't be reached even with max combo.
+    pub fn combo_for_pp(self, pp: f64) -> Result<Option<u32>, ConvertError> {
+        let (perf, attrs) = self.with_attrs()?;
+
+        Ok(search::min_u32(0, attrs.max_combo, |combo| {
+            perf.clone().combo(combo).reaches_pp(pp)
+        }))
+    }
+
     /// Calculate all performance related values, including pp and stars.
     pub fn calculate(mut self) -> Result<OsuPerformanceAttributes, ConvertError> {
         let state = self.generate_state()?;
@@ -692,6 +812,23 @@ impl<'map> OsuPerformance<'map> {
         effective_miss_count = effective_miss_count.max(f64::from(state.misses));
         effective_miss_count = effective_miss_count.min(f64::from(state.total_hits()));
 
+        let misses = f64::from(state.misses);
+        let slider_breaks = effective_miss_count - misses;
+
+        let miss_breakdown = if using_classic_slider_acc {
+            OsuMissBreakdown {
+                misses,
+                combo_breaks: slider_breaks,
+                ..Default::default()
+            }
+        } else {
+            OsuMissBreakdown {
+                misses,
+                slider_breaks,
+                ..Default::default()
+            }
+        };
+
         let origin = match (lazer, using_classic_slider_acc) {
             (false, _) => OsuScoreOrigin::Stable,
             (true, false) => OsuScoreOrigin::WithSliderAcc {
@@ -709,15 +846,40 @@ impl<'map> OsuPerformance<'map> {
         let inner = OsuPerformanceInner {
             attrs,
             mods,
+            profile: self.difficulty.get_relax_profile(),
             acc,
             state,
             effective_miss_count,
+            miss_breakdown,
             using_classic_slider_acc,
+            measured_unstable_rate: self
+                .unstable_rate
+                .map(|unstable_rate| unstable_rate.get(self.difficulty.get_clock_rate())),
         };
 
         Ok(inner.calculate())
     }
 
+    /// Calculate the difficulty attributes unless they're already stored.
+    ///
+    /// Returns a copy of the attributes alongside the calculator.
+    fn with_attrs(mut self) -> Result<(Self, OsuDifficultyAttributes), ConvertError> {
+        let attrs = match self.map_or_attrs {
+            MapOrAttrs::Map(ref map) => {
+                let attrs = self.difficulty.calculate_for_mode::<Osu>(map)?;
+
+                self.map_or_attrs.insert_attrs(attrs).clone()
+            }
+            MapOrAttrs::Attrs(ref attrs) => attrs.clone(),
+        };
+
+        Ok((self, attrs))
+    }
+
+    fn reaches_pp(self, pp: f64) -> bool {
+        self.calculate().is_ok_and(|attrs| attrs.pp >= pp)
+    }
+
     pub(crate) const fn from_map_or_attrs(map_or_attrs: MapOrAttrs<'map, Osu>) -> Self {
         Self {
             map_or_attrs,
@@ -731,6 +893,7 @@ impl<'map> OsuPerformance<'map> {
             n50: None,
             misses: None,
             hitresult_priority: HitResultPriority::DEFAULT,
+            unstable_rate: None,
         }
     }
 
@@ -771,13 +934,16 @@ impl<'map, T: IntoModePerformance<'map, Osu>> From<T> for OsuPerformance<'map> {
 // * This is being adjusted to keep the final pp value scaled around what it used to be when changing things.
 pub const PERFORMANCE_BASE_MULTIPLIER: f64 = 1.15;
 
-struct OsuPerformanceInner<'mods> {
+struct OsuPerformanceInner<'a> {
     attrs: OsuDifficultyAttributes,
-    mods: &'mods GameMods,
+    mods: &'a GameMods,
+    profile: &'a RelaxProfile,
     acc: f64,
     state: OsuScoreState,
     effective_miss_count: f64,
+    miss_breakdown: OsuMissBreakdown,
     using_classic_slider_acc: bool,
+    measured_unstable_rate: Option<f64>,
 }
 
 impl OsuPerformanceInner<'_> {
@@ -808,9 +974,11 @@ impl OsuPerformanceInner<'_> {
             // * we use OD13.3 as maximum since it's the value at which great hitwidow becomes 0
             // * this is well beyond currently maximum achievable OD which is 12.17 (DTx2 + DA with OD11)
             let (n100_mult, n50_mult) = if self.attrs.od > 0.0 {
+                let od_ratio = self.attrs.od / self.profile.rx_miss_od;
+
                 (
-                    (1.0 - (self.attrs.od / 13.33).powf(1.8)).max(0.0),
-                    (1.0 - (self.attrs.od / 13.33).powf(5.0)).max(0.0),
+                    (1.0 - od_ratio.powf(self.profile.rx_n100_exponent)).max(0.0),
+                    (1.0 - od_ratio.powf(self.profile.rx_n50_exponent)).max(0.0),
                 )
             } else {
                 (1.0, 1.0)
@@ -818,37 +986,48 @@ impl OsuPerformanceInner<'_> {
 
             // * As we're adding Oks and Mehs to an approximated number of combo breaks the result can be
             // * higher than total hits in specific scenarios (which breaks some calculations) so we need to clamp it.
-            self.effective_miss_count = (self.effective_miss_count
-                + f64::from(self.state.n100) * n100_mult
-                + f64::from(self.state.n50) * n50_mult)
-                .min(total_hits);
+            let remaining = (total_hits - self.effective_miss_count).max(0.0);
+            self.miss_breakdown.n100 = (f64::from(self.state.n100) * n100_mult).min(remaining);
+            self.miss_breakdown.n50 =
+                (f64::from(self.state.n50) * n50_mult).min(remaining - self.miss_breakdown.n100);
+
+            self.effective_miss_count =
+                self.effective_miss_count + self.miss_breakdown.n100 + self.miss_breakdown.n50;
         }
 
         let aim_value = self.compute_aim_value();
         let speed_value = self.compute_speed_value();
+        let tapping_value = self.compute_tapping_value();
         let acc_value = self.compute_accuracy_value();
         let flashlight_value = self.compute_flashlight_value();
 
         let pp = (aim_value.powf(1.1)
             + speed_value.powf(1.1)
+            + tapping_value.powf(1.1)
             + acc_value.powf(1.1)
             + flashlight_value.powf(1.1))
         .powf(1.0 / 1.1)
             * multiplier;
 
+        let estimated_unstable_rate = self.estimated_unstable_rate();
+
         OsuPerformanceAttributes {
             difficulty: self.attrs,
             pp_acc: acc_value,
             pp_aim: aim_value,
             pp_flashlight: flashlight_value,
             pp_speed: speed_value,
+            pp_tapping: tapping_value,
             pp,
             effective_miss_count: self.effective_miss_count,
+            miss_breakdown: self.miss_breakdown,
+            estimated_unstable_rate,
+            measured_unstable_rate: self.measured_unstable_rate,
         }
     }
 
     fn compute_aim_value(&self) -> f64 {
-        if self.mods.ap() {
+        if self.mods.ap() && self.profile.ap_no_aim_value {
             return 0.0;
         }
 
@@ -869,7 +1048,7 @@ impl OsuPerformanceInner<'_> {
             );
         }
 
-        let ar_factor = if self.mods.rx() {
+        let ar_factor = if self.mods.rx() && self.profile.rx_no_aim_ar_bonus {
             0.0
         } else if self.attrs.ar > 10.33 {
             0.3 * (self.attrs.ar - 10.33)
@@ -929,7 +1108,8 @@ impl OsuPerformanceInner<'_> {
     }
 
     fn compute_speed_value(&self) -> f64 {
-        if self.mods.rx() {
+        // Autopilot uses the tapping value instead
+        if (self.mods.rx() && self.profile.rx_no_speed_value) || self.mods.ap() {
             return 0.0;
         }
 
@@ -950,9 +1130,7 @@ impl OsuPerformanceInner<'_> {
             );
         }
 
-        let ar_factor = if self.mods.ap() {
-            0.0
-        } else if self.attrs.ar > 10.33 {
+        let ar_factor = if self.attrs.ar > 10.33 {
             0.3 * (self.attrs.ar - 10.33)
         } else {
             0.0
@@ -981,7 +1159,9 @@ impl OsuPerformanceInner<'_> {
             - (relevant_total_diff - f64::from(self.state.n300 + self.state.n100)).max(0.0))
         .max(0.0);
 
-        let relevant_acc = if self.attrs.speed_note_count.eq(0.0) {
+        let relevant_acc = if let Some(measured_acc) = self.measured_acc_percentage() {
+            measured_acc
+        } else if self.attrs.speed_note_count.eq(0.0) {
             0.0
         } else {
             (relevant_n300 * 6.0 + relevant_n100 * 2.0 + relevant_n50)
@@ -1001,20 +1181,65 @@ impl OsuPerformanceInner<'_> {
         speed_value
     }
 
+    fn compute_tapping_value(&self) -> f64 {
+        if !self.mods.ap() {
+            return 0.0;
+        }
+
+        let mut tapping_value = OsuStrainSkill::difficulty_to_performance(self.attrs.autopilot);
+
+        let total_hits = self.total_hits();
+
+        let len_bonus = 0.95
+            + 0.4 * (total_hits / 2000.0).min(1.0)
+            + f64::from(u8::from(total_hits > 2000.0)) * (total_hits / 2000.0).log10() * 0.5;
+
+        tapping_value *= len_bonus;
+
+        if self.effective_miss_count > 0.0 {
+            tapping_value *= Self::calculate_miss_penalty(
+                self.effective_miss_count,
+                self.attrs.autopilot_difficult_strain_count,
+            );
+        }
+
+        let ar_factor = if self.profile.ap_no_tapping_ar_bonus {
+            0.0
+        } else if self.attrs.ar > 10.33 {
+            0.3 * (self.attrs.ar - 10.33)
+        } else {
+            0.0
+        };
+
+        // * Buff for longer maps with high AR.
+        tapping_value *= 1.0 + ar_factor * len_bonus;
+
+        // Without any movement, accuracy is the only indicator of how well
+        // the player could keep up with the tapping.
+        tapping_value *= (0.95 + self.attrs.od * self.attrs.od / 750.0)
+            * self.acc.powf((14.5 - self.attrs.od) / 2.0);
+
+        // * Scale the speed value with # of 50s to punish doubletapping.
+        tapping_value *= 0.99_f64.powf(
+            f64::from(u8::from(f64::from(self.state.n50) >= total_hits / 500.0))
+                * (f64::from(self.state.n50) - total_hits / 500.0),
+        );
+
+        tapping_value
+    }
+
     fn compute_accuracy_value(&self) -> f64 {
-        if self.mods.rx() {
+        if self.mods.rx() && self.profile.rx_no_acc_value {
             return 0.0;
         }
 
         // * This percentage only considers HitCircles of any value - in this part
         // * of the calculation we focus on hitting the timing hit window.
-        let mut amount_hit_objects_with_acc = self.attrs.n_circles;
-
-        if !self.using_classic_slider_acc {
-            amount_hit_objects_with_acc += self.attrs.n_sliders;
-        }
+        let amount_hit_objects_with_acc = self.amount_hit_objects_with_acc();
 
-        let mut better_acc_percentage = if amount_hit_objects_with_acc > 0 {
+        let mut better_acc_percentage = if let Some(measured_acc) = self.measured_acc_percentage() {
+            measured_acc
+        } else if amount_hit_objects_with_acc > 0 {
             f64::from(
                 (self.state.n300 as i32
                     - (self.state.total_hits() as i32 - amount_hit_objects_with_acc as i32))
@@ -1056,6 +1281,66 @@ impl OsuPerformanceInner<'_> {
         acc_value
     }
 
+    const fn amount_hit_objects_with_acc(&self) -> u32 {
+        if self.using_classic_slider_acc {
+            self.attrs.n_circles
+        } else {
+            self.attrs.n_circles + self.attrs.n_sliders
+        }
+    }
+
+    /// The great, ok, and meh hit windows in real time.
+    fn hit_windows(&self) -> (f64, f64, f64) {
+        let od = self.attrs.od;
+
+        (
+            (80.0 - 6.0 * od).max(0.0),
+            (140.0 - 8.0 * od).max(0.0),
+            (200.0 - 10.0 * od).max(0.0),
+        )
+    }
+
+    /// Upper bound on the unstable rate based on the hitresults of objects
+    /// that require accuracy, assuming all other objects are 300s.
+    fn estimated_unstable_rate(&self) -> Option<f64> {
+        let n = self.amount_hit_objects_with_acc();
+        let n_other = self.state.total_hits().saturating_sub(n);
+        let n_great = self.state.n300.saturating_sub(n_other);
+        let n_successful = cmp::min(n_great + self.state.n100, n);
+        let (great, ok, _) = self.hit_windows();
+
+        deviation::deviation_upper_bound(f64::from(n), n_great, n_successful, great, ok)
+            .map(|deviation| deviation * 10.0)
+    }
+
+    /// The accuracy of objects that require accuracy based on the measured
+    /// unstable rate, assuming normally distributed hit offsets.
+    ///
+    /// Misses are considered separately since they're not part of the
+    /// measured hit offsets.
+    fn measured_acc_percentage(&self) -> Option<f64> {
+        let deviation = self.measured_unstable_rate? / 10.0;
+        let n = self.amount_hit_objects_with_acc();
+
+        if n == 0 {
+            return Some(0.0);
+        }
+
+        let (great, ok, meh) = self.hit_windows();
+        let p_great = deviation::hit_probability(great, deviation);
+        let p_ok = deviation::hit_probability(ok, deviation);
+        let p_meh = deviation::hit_probability(meh, deviation);
+
+        if p_meh <= 0.0 {
+            return Some(0.0);
+        }
+
+        let timing_acc = (6.0 * p_great + 2.0 * (p_ok - p_great) + (p_meh - p_ok)) / (6.0 * p_meh);
+        let hit_ratio = 1.0 - f64::from(cmp::min(self.state.misses, n)) / f64::from(n);
+
+        Some(timing_acc * hit_ratio)
+    }
+
     fn compute_flashlight_value(&self) -> f64 {
         if !self.mods.fl() {
             return 0.0;
@@ -1579,4 +1864,156 @@ mod test {
         assert!(OsuPerformance::try_new(&map).is_none());
         assert!(OsuPerformance::try_new(map).is_none());
     }
+
+    #[test]
+    fn relax_profile() {
+        let map = beatmap();
+
+        let calculate = |profile: RelaxProfile| {
+            OsuPerformance::from(&map)
+                .mods(128) // RX
+                .relax_profile(profile)
+                .n100(20)
+                .n50(5)
+                .calculate()
+                .unwrap()
+        };
+
+        let default = calculate(RelaxProfile::DEFAULT);
+        let unchanged = OsuPerformance::from(&map)
+            .mods(128)
+            .n100(20)
+            .n50(5)
+            .calculate()
+            .unwrap();
+
+        assert_eq!(default, unchanged);
+        assert!(default.pp_speed.abs() < f64::EPSILON);
+        assert!(default.pp_acc.abs() < f64::EPSILON);
+
+        let custom = calculate(RelaxProfile {
+            rx_aim_multiplier: 1.0,
+            rx_no_speed_value: false,
+            rx_no_acc_value: false,
+            rx_n100_exponent: 0.0,
+            ..RelaxProfile::DEFAULT
+        });
+
+        assert!(custom.difficulty.aim > default.difficulty.aim);
+        assert!(custom.pp_acc > 0.0);
+        assert!(custom.effective_miss_count < default.effective_miss_count);
+    }
+
+    #[test]
+    fn miss_breakdown() {
+        let map = beatmap();
+
+        let calculate = |mods: u32, lazer: bool| {
+            OsuPerformance::from(&map)
+                .mods(mods)
+                .lazer(lazer)
+                .combo(200)
+                .n100(20)
+                .n50(5)
+                .misses(2)
+                .calculate()
+                .unwrap()
+        };
+
+        let stable = calculate(0, false);
+        let breakdown = stable.miss_breakdown;
+
+        assert!((breakdown.misses - 2.0).abs() < f64::EPSILON);
+        assert!(breakdown.combo_breaks > 0.0);
+        assert!(breakdown.slider_breaks.abs() < f64::EPSILON);
+        assert!(breakdown.n100.abs() < f64::EPSILON);
+        assert!(breakdown.n50.abs() < f64::EPSILON);
+        assert!((breakdown.total() - stable.effective_miss_count).abs() < 1e-9);
+
+        let relax = calculate(128, true);
+        let breakdown = relax.miss_breakdown;
+
+        assert!((breakdown.misses - 2.0).abs() < f64::EPSILON);
+        assert!(breakdown.combo_breaks.abs() < f64::EPSILON);
+        assert!(breakdown.n100 > 0.0);
+        assert!(breakdown.n50 > 0.0);
+        assert!((breakdown.total() - relax.effective_miss_count).abs() < 1e-9);
+    }
+
+    #[test]
+    fn autopilot_tapping() {
+        let map = beatmap();
+
+        let nomod = OsuPerformance::from(&map).calculate().unwrap();
+
+        assert!(nomod.difficulty.autopilot.abs() < f64::EPSILON);
+        assert!(nomod.pp_tapping.abs() < f64::EPSILON);
+
+        let autopilot = OsuPerformance::from(&map)
+            .mods(8192) // AP
+            .calculate()
+            .unwrap();
+
+        assert!(autopilot.difficulty.autopilot > 0.0);
+        assert!(autopilot.difficulty.autopilot_difficult_strain_count > 0.0);
+        assert!(autopilot.pp_aim.abs() < f64::EPSILON);
+        assert!(autopilot.pp_speed.abs() < f64::EPSILON);
+        assert!(autopilot.pp_tapping > 0.0);
+        assert!(autopilot.pp < nomod.pp);
+    }
+
+    #[test]
+    fn relax_stream_aim() {
+        let map = beatmap();
+
+        let nomod = OsuPerformance::from(&map).calculate().unwrap();
+
+        assert!(nomod.difficulty.relax_stream_aim.abs() < f64::EPSILON);
+
+        let relax = OsuPerformance::from(&map)
+            .mods(128) // RX
+            .calculate()
+            .unwrap();
+
+        let profile = RelaxProfile {
+            rx_stream_aim_multiplier: 0.0,
+            ..RelaxProfile::DEFAULT
+        };
+
+        let no_streams = OsuPerformance::from(&map)
+            .mods(128) // RX
+            .relax_profile(profile)
+            .calculate()
+            .unwrap();
+
+        assert!(relax.difficulty.relax_stream_aim > 0.0);
+        assert!(no_streams.difficulty.relax_stream_aim.abs() < f64::EPSILON);
+        assert!(relax.difficulty.aim > no_streams.difficulty.aim);
+        assert!(relax.pp_aim > no_streams.pp_aim);
+    }
+
+    #[test]
+    fn measured_unstable_rate() {
+        let map = beatmap();
+
+        let calculate = |n100: u32, unstable_rate: Option<f64>| {
+            let performance = OsuPerformance::from(&map).n100(n100);
+
+            match unstable_rate {
+                Some(unstable_rate) => performance.unstable_rate(unstable_rate),
+                None => performance,
+            }
+            .calculate()
+            .unwrap()
+        };
+
+        // Without a measured unstable rate the hitresults decide the accuracy
+        assert!(calculate(0, None).pp_acc > calculate(20, None).pp_acc);
+
+        // With one, only misses are considered from the hitresults
+        let precise = calculate(0, Some(80.0));
+        let imprecise = calculate(20, Some(80.0));
+
+        assert!((precise.pp_acc - imprecise.pp_acc).abs() < 1e-9);
+    }
 }
@@ -1,5 +1,7 @@
 /// Aggregation for a score's current state.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct OsuScoreState {
     /// Maximum combo that the score has had so far. **Not** the maximum
     /// possible combo of the map so far.
@@ -91,6 +93,7 @@ impl Default for OsuScoreState {
 
 /// Type to pass [`OsuScoreState::accuracy`] and specify the origin of a score.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum OsuScoreOrigin {
     /// For scores set on osu!stable
     Stable,
@@ -2,12 +2,16 @@ use rosu_map::section::general::GameMode;
 
 use crate::{model::mode::ConvertError, Beatmap, Difficulty};
 
-use super::difficulty::{skills::OsuSkills, DifficultyValues};
+use super::difficulty::{
+    skills::{strain::DecayingSkill, OsuSkills},
+    DifficultyValues,
+};
 
 /// The result of calculating the strains on a osu! map.
 ///
 /// Suitable to plot the difficulty of a map over time.
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OsuStrains {
     /// Strain peaks of the aim skill.
     pub aim: Vec<f64>,
@@ -17,6 +21,16 @@ pub struct OsuStrains {
     pub speed: Vec<f64>,
     /// Strain peaks of the flashlight skill.
     pub flashlight: Vec<f64>,
+    /// Strain peaks of the autopilot skill.
+    ///
+    /// Only calculated if the autopilot mod is enabled.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub autopilot: Vec<f64>,
+    /// Strain peaks of the stream aim skill.
+    ///
+    /// Only calculated if the relax mod is enabled.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub relax_stream_aim: Vec<f64>,
 }
 
 impl OsuStrains {
@@ -34,6 +48,8 @@ pub fn strains(difficulty: &Difficulty, map: &Beatmap) -> Result<OsuStrains, Con
                 aim_no_sliders,
                 speed,
                 flashlight,
+                autopilot,
+                relax_stream_aim,
             },
         attrs: _,
     } = DifficultyValues::calculate(difficulty, &map);
@@ -43,5 +59,7 @@ pub fn strains(difficulty: &Difficulty, map: &Beatmap) -> Result<OsuStrains, Con
         aim_no_sliders: aim_no_sliders.get_curr_strain_peaks().into_vec(),
         speed: speed.get_curr_strain_peaks().into_vec(),
         flashlight: flashlight.get_curr_strain_peaks().into_vec(),
+        autopilot: autopilot.get_curr_strain_peaks().into_vec(),
+        relax_stream_aim: relax_stream_aim.get_curr_strain_peaks().into_vec(),
     })
 }
@@ -10,6 +10,8 @@ pub(crate) struct DifficultyObject<'h> {
 
     pub(crate) delta: f32,
     pub(crate) strain_time: f32,
+
+    pub(crate) scaling_factor: f32,
 }
 
 impl<'h> DifficultyObject<'h> {
@@ -17,7 +19,7 @@ impl<'h> DifficultyObject<'h> {
         base: &'h OsuObject,
         prev: &OsuObject,
         prev_vals: Option<(f32, f32)>, // (jump_dist, strain_time)
-        prev_prev: Option<OsuObject>,
+        prev_prev: Option<&OsuObject>,
         clock_rate: f32,
         scaling_factor: f32,
     ) -> Self {
@@ -56,6 +58,8 @@ impl<'h> DifficultyObject<'h> {
 
             delta,
             strain_time,
+
+            scaling_factor,
         }
     }
 }
@@ -0,0 +1,461 @@
+use std::cmp;
+
+use rosu_map::section::general::GameMode;
+
+use crate::{
+    model::mode::ConvertError,
+    osu_2019::{
+        skill::SkillHistoryLen,
+        stars::{eval, OsuDifficultyAttributes, OsuDifficultySetup, DIFFICULTY_MULTIPLIER},
+        DifficultyObject, OsuObject, Skill, SkillKind,
+    },
+    util::checkpoints::{Checkpoints, Seek},
+    Beatmap, Difficulty,
+};
+
+/// Gradually calculate the difficulty attributes of an osu!standard map
+/// with the 2019 algorithm.
+///
+/// Note that this struct implements [`Iterator`].
+/// On every call of [`Iterator::next`], the map's next hit object will
+/// be processed and the [`OsuDifficultyAttributes`] will be updated and
+/// returned.
+///
+/// If you want to calculate performance attributes, use
+/// [`Osu2019GradualPerformance`] instead.
+///
+/// # Example
+///
+/// ```
+/// use akatsuki_pp::{Beatmap, Difficulty};
+/// use akatsuki_pp::osu_2019::Osu2019GradualDifficulty;
+///
+/// let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+///
+/// let difficulty = Difficulty::new().mods(128 + 64); // RXDT
+/// let mut iter = Osu2019GradualDifficulty::new(difficulty, &map).unwrap();
+///
+///  // the difficulty of the map after the first hit object
+/// let attrs1 = iter.next();
+/// // ... after the second hit object
+/// let attrs2 = iter.next();
+///
+/// // Remaining hit objects
+/// for difficulty in iter {
+///     // ...
+/// }
+/// ```
+///
+/// [`Osu2019GradualPerformance`]: crate::osu_2019::Osu2019GradualPerformance
+pub struct Osu2019GradualDifficulty {
+    pub(crate) idx: usize,
+    pub(crate) difficulty: Difficulty,
+    attrs: OsuDifficultyAttributes,
+    osu_objects: Box<[OsuObject]>,
+    /// Combo of each hit object i.e. `1` for circles and spinners.
+    object_combos: Box<[usize]>,
+    aim: Skill,
+    speed: Skill,
+    flashlight: Skill,
+    prev_vals: Option<(f32, f32)>,
+    current_section_end: f32,
+    clock_rate: f32,
+    scaling_factor: f32,
+    section_len: f32,
+    checkpoints: Checkpoints<Osu2019Checkpoint>,
+}
+
+/// The state after processing `idx` hit objects.
+///
+/// The skills are stored without their strain peaks and object strains.
+/// Instead, restoring truncates the current ones to the stored amounts.
+#[derive(Clone)]
+pub struct Osu2019Checkpoint {
+    idx: usize,
+    attrs: OsuDifficultyAttributes,
+    aim: (Skill, SkillHistoryLen),
+    speed: (Skill, SkillHistoryLen),
+    flashlight: (Skill, SkillHistoryLen),
+    prev_vals: Option<(f32, f32)>,
+    current_section_end: f32,
+}
+
+impl Osu2019GradualDifficulty {
+    /// Create a new difficulty attributes iterator for osu!standard maps.
+    pub fn new(difficulty: Difficulty, map: &Beatmap) -> Result<Self, ConvertError> {
+        let map = map.convert_ref(GameMode::Osu, difficulty.get_mods())?;
+
+        let mut object_combos = Vec::with_capacity(map.hit_objects.len());
+        let mut prev_combo = 0;
+
+        let OsuDifficultySetup {
+            mut attrs,
+            clock_rate,
+            scaling_factor,
+            section_len,
+            osu_objects,
+        } = OsuDifficultySetup::new(&map, &difficulty, map.hit_objects.len(), |max_combo| {
+            object_combos.push(max_combo - prev_combo);
+            prev_combo = max_combo;
+        });
+
+        attrs.n_circles = 0;
+        attrs.n_sliders = 0;
+        attrs.n_spinners = 0;
+        attrs.max_combo = 0;
+
+        let current_section_end = osu_objects
+            .first()
+            .map_or(0.0, |h| (h.time / section_len).ceil() * section_len);
+
+        let mut aim = Skill::new(SkillKind::Aim);
+        let mut speed = Skill::new(SkillKind::Speed);
+        let mut flashlight = Skill::new(SkillKind::Flashlight);
+
+        let checkpoints = Checkpoints::new(Osu2019Checkpoint {
+            idx: 0,
+            attrs: attrs.clone(),
+            aim: aim.clone_without_history(),
+            speed: speed.clone_without_history(),
+            flashlight: flashlight.clone_without_history(),
+            prev_vals: None,
+            current_section_end,
+        });
+
+        Ok(Self {
+            idx: 0,
+            difficulty,
+            attrs,
+            osu_objects: osu_objects.into_boxed_slice(),
+            object_combos: object_combos.into_boxed_slice(),
+            aim,
+            speed,
+            flashlight,
+            prev_vals: None,
+            current_section_end,
+            clock_rate,
+            scaling_factor,
+            section_len,
+            checkpoints,
+        })
+    }
+
+    /// Store the state after every `interval` hit objects so that seeking
+    /// backwards with [`seek`] has to process at most `interval` hit objects.
+    ///
+    /// Checkpoints are stored while hit objects are being processed. Each one
+    /// only contains the current state of the skills and the lengths of their
+    /// strain histories so its size does not grow with the amount of processed
+    /// hit objects.
+    ///
+    /// [`seek`]: Osu2019GradualDifficulty::seek
+    #[must_use]
+    pub fn checkpoints(mut self, interval: usize) -> Self {
+        self.checkpoints.set_interval(interval);
+
+        self
+    }
+
+    /// Move to the hit object at index `idx`, which may be before or after
+    /// the current one, and return the difficulty attributes after
+    /// processing it.
+    ///
+    /// The attributes are the same as for `nth(idx)` on a new iterator and
+    /// [`Iterator::next`] will continue with the hit object at `idx + 1`.
+    ///
+    /// Seeking backwards restores the latest checkpoint before `idx` or,
+    /// without [`checkpoints`], processes all hit objects from the start
+    /// again. Seeking forwards always processes all hit objects between the
+    /// current one and `idx` because restoring a checkpoint discards the
+    /// strain history after it.
+    ///
+    /// Returns `None` if `idx` is out of bounds.
+    ///
+    /// [`checkpoints`]: Osu2019GradualDifficulty::checkpoints
+    pub fn seek(&mut self, idx: usize) -> Option<OsuDifficultyAttributes> {
+        if idx >= self.osu_objects.len() {
+            return None;
+        }
+
+        self.seek_to(idx);
+
+        self.next()
+    }
+
+    fn increment_combo(h: &OsuObject, combo: usize, attrs: &mut OsuDifficultyAttributes) {
+        attrs.max_combo += combo;
+
+        // Sliders always provide combo for at least their head and tail
+        if h.is_spinner() {
+            attrs.n_spinners += 1;
+        } else if combo > 1 {
+            attrs.n_sliders += 1;
+        } else {
+            attrs.n_circles += 1;
+        }
+    }
+
+    /// Process the hit object at index `self.idx` without evaluating the
+    /// skills.
+    fn process_next(&mut self) {
+        let idx = self.idx;
+
+        if idx > 0 {
+            let h = DifficultyObject::new(
+                &self.osu_objects[idx],
+                &self.osu_objects[idx - 1],
+                self.prev_vals,
+                idx.checked_sub(2).map(|i| &self.osu_objects[i]),
+                self.clock_rate,
+                self.scaling_factor,
+            );
+
+            // The second object has no strain to save yet
+            if idx == 1 {
+                while h.base.time > self.current_section_end {
+                    self.current_section_end += self.section_len;
+                }
+            } else {
+                while h.base.time > self.current_section_end {
+                    self.aim.save_current_peak();
+                    self.aim.start_new_section_from(self.current_section_end);
+                    self.speed.save_current_peak();
+                    self.speed.start_new_section_from(self.current_section_end);
+                    self.flashlight.save_current_peak();
+                    self.flashlight
+                        .start_new_section_from(self.current_section_end);
+
+                    self.current_section_end += self.section_len;
+                }
+            }
+
+            self.aim.process(&h);
+            self.speed.process(&h);
+            self.flashlight.process(&h);
+
+            self.prev_vals = Some((h.jump_dist, h.strain_time));
+        }
+
+        Self::increment_combo(
+            &self.osu_objects[idx],
+            self.object_combos[idx],
+            &mut self.attrs,
+        );
+
+        self.idx += 1;
+    }
+}
+
+impl Iterator for Osu2019GradualDifficulty {
+    type Item = OsuDifficultyAttributes;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.record_checkpoint();
+
+        if self.idx >= self.osu_objects.len() {
+            return None;
+        }
+
+        self.process_next();
+
+        let mut attrs = self.attrs.clone();
+
+        // A single object has no strain
+        if self.idx < 2 {
+            return Some(attrs);
+        }
+
+        let aim_strain = self.aim.as_difficulty_value().sqrt() * DIFFICULTY_MULTIPLIER;
+        let speed_strain = self.speed.as_difficulty_value().sqrt() * DIFFICULTY_MULTIPLIER;
+        let flashlight_strain =
+            self.flashlight.as_difficulty_value().sqrt() * DIFFICULTY_MULTIPLIER;
+
+        eval(
+            &mut attrs,
+            aim_strain,
+            speed_strain,
+            flashlight_strain,
+            &self.aim,
+            &self.speed,
+        );
+
+        Some(attrs)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+
+        (len, Some(len))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.process(cmp::min(n, self.len().saturating_sub(1)));
+
+        self.next()
+    }
+}
+
+impl Seek for Osu2019GradualDifficulty {
+    type Snapshot = Osu2019Checkpoint;
+
+    const RESTORES_FORWARD: bool = false;
+
+    fn idx(&self) -> usize {
+        self.idx
+    }
+
+    fn stored_checkpoints(&self) -> &Checkpoints<Self::Snapshot> {
+        &self.checkpoints
+    }
+
+    fn stored_checkpoints_mut(&mut self) -> &mut Checkpoints<Self::Snapshot> {
+        &mut self.checkpoints
+    }
+
+    fn snapshot(&mut self) -> Self::Snapshot {
+        Osu2019Checkpoint {
+            idx: self.idx,
+            attrs: self.attrs.clone(),
+            aim: self.aim.clone_without_history(),
+            speed: self.speed.clone_without_history(),
+            flashlight: self.flashlight.clone_without_history(),
+            prev_vals: self.prev_vals,
+            current_section_end: self.current_section_end,
+        }
+    }
+
+    fn restore(&mut self, snapshot: Self::Snapshot) {
+        let Osu2019Checkpoint {
+            idx,
+            attrs,
+            aim,
+            speed,
+            flashlight,
+            prev_vals,
+            current_section_end,
+        } = snapshot;
+
+        self.idx = idx;
+        self.attrs = attrs;
+        self.aim.restore_with_history(aim.0, aim.1);
+        self.speed.restore_with_history(speed.0, speed.1);
+        self.flashlight
+            .restore_with_history(flashlight.0, flashlight.1);
+        self.prev_vals = prev_vals;
+        self.current_section_end = current_section_end;
+    }
+
+    fn process(&mut self, n: usize) {
+        for _ in 0..n {
+            self.process_next();
+        }
+    }
+}
+
+impl ExactSizeIterator for Osu2019GradualDifficulty {
+    fn len(&self) -> usize {
+        self.osu_objects.len() - self.idx
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        any::{DifficultyAttributes, Variant},
+        osu_2019::stars::{all_included, stars},
+    };
+
+    use super::*;
+
+    #[test]
+    fn empty() {
+        let map = Beatmap::from_bytes(&[]).unwrap();
+        let mut gradual = Osu2019GradualDifficulty::new(Difficulty::new(), &map).unwrap();
+        assert!(gradual.next().is_none());
+    }
+
+    #[test]
+    fn seek() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+
+        let difficulty = Difficulty::new().mods(128 + 64); // RXDT
+
+        let expected: Vec<_> = Osu2019GradualDifficulty::new(difficulty.clone(), &map)
+            .unwrap()
+            .collect();
+
+        let mut gradual = Osu2019GradualDifficulty::new(difficulty, &map)
+            .unwrap()
+            .checkpoints(50);
+
+        let last = expected.len() - 1;
+
+        for idx in [300, 10, 0, 1, 299, 301, last, 150, 51, 50, 49] {
+            assert_eq!(gradual.seek(idx).as_ref(), Some(&expected[idx]), "{idx}");
+        }
+
+        assert!(gradual.seek(last + 1).is_none());
+
+        gradual.seek(20);
+        assert_eq!(gradual.next().as_ref(), Some(&expected[21]));
+        assert_eq!(gradual.len(), last - 21);
+    }
+
+    #[test]
+    fn next_and_nth() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+
+        let difficulty = Difficulty::new().mods(128 + 64); // RXDT
+
+        let mut gradual = Osu2019GradualDifficulty::new(difficulty.clone(), &map).unwrap();
+        let mut gradual_2nd = Osu2019GradualDifficulty::new(difficulty.clone(), &map).unwrap();
+        let mut gradual_3rd = Osu2019GradualDifficulty::new(difficulty.clone(), &map).unwrap();
+
+        let hit_objects_len = map.hit_objects.len();
+
+        for i in 1.. {
+            let Some(next_gradual) = gradual.next() else {
+                assert_eq!(i, hit_objects_len + 1);
+                assert!(gradual_2nd.last().is_some() || hit_objects_len.is_multiple_of(2));
+                assert!(gradual_3rd.last().is_some() || hit_objects_len.is_multiple_of(3));
+                break;
+            };
+
+            if i % 2 == 0 {
+                let next_gradual_2nd = gradual_2nd.nth(1).unwrap();
+                assert_eq!(next_gradual, next_gradual_2nd);
+            }
+
+            if i % 3 == 0 {
+                let next_gradual_3rd = gradual_3rd.nth(2).unwrap();
+                assert_eq!(next_gradual, next_gradual_3rd);
+            }
+
+            let expected = difficulty
+                .clone()
+                .passed_objects(i as u32)
+                .variant(Variant::Relax2019)
+                .calculate(&map);
+
+            let DifficultyAttributes::Osu2019(expected) = expected else {
+                panic!("expected osu_2019 attributes");
+            };
+
+            assert_eq!(next_gradual, expected);
+        }
+    }
+
+    #[test]
+    fn stack_leniency() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+
+        let difficulty = Difficulty::new().mods(128).stack_leniency(true); // RX
+
+        let gradual = Osu2019GradualDifficulty::new(difficulty, &map).unwrap();
+        let stacked = gradual.last().unwrap();
+
+        assert_eq!(stacked, all_included(&map, 128.into(), None));
+        let unstacked = stars(&map, 128.into(), None);
+        assert!((stacked.aim_strain - unstacked.aim_strain).abs() > f64::EPSILON);
+    }
+}
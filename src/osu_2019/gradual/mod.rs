@@ -0,0 +1,4 @@
+pub use self::{difficulty::Osu2019GradualDifficulty, performance::Osu2019GradualPerformance};
+
+mod difficulty;
+mod performance;
@@ -0,0 +1,202 @@
+use crate::{
+    model::mode::ConvertError,
+    osu::OsuScoreState,
+    osu_2019::{stars::OsuPerformanceAttributes, OsuPP},
+    Beatmap, Difficulty,
+};
+
+use super::Osu2019GradualDifficulty;
+
+/// Gradually calculate the performance attributes of an osu!standard map
+/// with the 2019 algorithm.
+///
+/// After each hit object you can call [`next`]
+/// and it will return the resulting current [`OsuPerformanceAttributes`].
+/// To process multiple objects at once, use [`nth`] instead.
+///
+/// Both methods require an [`OsuScoreState`] that contains the current
+/// hitresults as well as the maximum combo so far.
+///
+/// If you only want to calculate difficulty attributes use
+/// [`Osu2019GradualDifficulty`] instead.
+///
+/// # Example
+///
+/// ```
+/// use akatsuki_pp::{Beatmap, Difficulty};
+/// use akatsuki_pp::osu::OsuScoreState;
+/// use akatsuki_pp::osu_2019::Osu2019GradualPerformance;
+///
+/// let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+///
+/// let difficulty = Difficulty::new().mods(128); // RX
+/// let mut gradual = Osu2019GradualPerformance::new(difficulty, &map).unwrap();
+/// let mut state = OsuScoreState::new(); // empty state, everything is on 0.
+///
+/// // The first 10 hits are 300s and there are no sliders for additional combo
+/// for _ in 0..10 {
+///     state.n300 += 1;
+///     state.max_combo += 1;
+///
+///     let attrs = gradual.next(state.clone()).unwrap();
+///     println!("PP: {}", attrs.pp);
+/// }
+///
+/// // Then comes a miss. Note that state's max combo won't be incremented for
+/// // the next few objects because the combo is reset.
+/// state.misses += 1;
+/// let attrs = gradual.next(state.clone()).unwrap();
+/// println!("PP: {}", attrs.pp);
+///
+/// // The next 10 objects will be a mixture of 300s, 100s, and 50s.
+/// // Notice how all 10 objects will be processed in one go.
+/// state.n300 += 2;
+/// state.n100 += 7;
+/// state.n50 += 1;
+/// // The `nth` method takes a zero-based value.
+/// let attrs = gradual.nth(state.clone(), 9).unwrap();
+/// println!("PP: {}", attrs.pp);
+///
+/// // Skip to the end
+/// # /*
+/// state.max_combo = ...
+/// state.n300 = ...
+/// state.n100 = ...
+/// state.n50 = ...
+/// state.misses = ...
+/// # */
+/// let attrs = gradual.last(state.clone()).unwrap();
+/// println!("PP: {}", attrs.pp);
+///
+/// // Once the final performance has been calculated, attempting to process
+/// // further objects will return `None`.
+/// assert!(gradual.next(state).is_none());
+/// ```
+///
+/// [`next`]: Osu2019GradualPerformance::next
+/// [`nth`]: Osu2019GradualPerformance::nth
+pub struct Osu2019GradualPerformance {
+    difficulty: Osu2019GradualDifficulty,
+}
+
+impl Osu2019GradualPerformance {
+    /// Create a new gradual performance calculator for osu!standard maps.
+    pub fn new(difficulty: Difficulty, map: &Beatmap) -> Result<Self, ConvertError> {
+        let difficulty = Osu2019GradualDifficulty::new(difficulty, map)?;
+
+        Ok(Self { difficulty })
+    }
+
+    /// Process the next hit object and calculate the performance attributes
+    /// for the resulting score state.
+    pub fn next(&mut self, state: OsuScoreState) -> Option<OsuPerformanceAttributes> {
+        self.nth(state, 0)
+    }
+
+    /// Process all remaining hit objects and calculate the final performance
+    /// attributes.
+    pub fn last(&mut self, state: OsuScoreState) -> Option<OsuPerformanceAttributes> {
+        self.nth(state, usize::MAX)
+    }
+
+    /// Process everything up to the next `n`th hitobject and calculate the
+    /// performance attributes for the resulting score state.
+    ///
+    /// Note that the count is zero-indexed, so `n=0` will process 1 object,
+    /// `n=1` will process 2, and so on.
+    pub fn nth(&mut self, state: OsuScoreState, n: usize) -> Option<OsuPerformanceAttributes> {
+        let performance = OsuPP::from_attributes(self.difficulty.nth(n)?)
+            .difficulty(self.difficulty.difficulty.clone())
+            .passed_objects(self.difficulty.idx as u32)
+            .state(state)
+            .calculate();
+
+        Some(performance)
+    }
+
+    /// Store the state after every `interval` hit objects so that seeking
+    /// backwards with [`seek`] has to process at most `interval` hit objects.
+    ///
+    /// See [`Osu2019GradualDifficulty::checkpoints`] for more info.
+    ///
+    /// [`seek`]: Osu2019GradualPerformance::seek
+    #[must_use]
+    pub fn checkpoints(mut self, interval: usize) -> Self {
+        self.difficulty = self.difficulty.checkpoints(interval);
+
+        self
+    }
+
+    /// Move to the hit object at index `idx`, which may be before or after the
+    /// current one, and calculate the performance attributes for the
+    /// resulting score state.
+    ///
+    /// See [`Osu2019GradualDifficulty::seek`] for how many hit objects have to
+    /// be processed.
+    ///
+    /// Returns `None` if `idx` is out of bounds.
+    pub fn seek(&mut self, state: OsuScoreState, idx: usize) -> Option<OsuPerformanceAttributes> {
+        let performance = OsuPP::from_attributes(self.difficulty.seek(idx)?)
+            .difficulty(self.difficulty.difficulty.clone())
+            .passed_objects(self.difficulty.idx as u32)
+            .state(state)
+            .calculate();
+
+        Some(performance)
+    }
+
+    /// Returns the amount of remaining objects.
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        self.difficulty.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next_and_nth() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+
+        let difficulty = Difficulty::new().mods(128 + 16 + 8); // RXHDHR
+
+        let mut gradual = Osu2019GradualPerformance::new(difficulty.clone(), &map).unwrap();
+        let mut gradual_2nd = Osu2019GradualPerformance::new(difficulty.clone(), &map).unwrap();
+        let mut gradual_3rd = Osu2019GradualPerformance::new(difficulty.clone(), &map).unwrap();
+
+        let mut state = OsuScoreState::default();
+
+        let hit_objects_len = map.hit_objects.len();
+
+        for i in 1.. {
+            state.misses += 1;
+
+            let Some(next_gradual) = gradual.next(state) else {
+                assert_eq!(i, hit_objects_len + 1);
+                assert!(gradual_2nd.last(state).is_some() || hit_objects_len.is_multiple_of(2));
+                assert!(gradual_3rd.last(state).is_some() || hit_objects_len.is_multiple_of(3));
+                break;
+            };
+
+            if i % 2 == 0 {
+                let next_gradual_2nd = gradual_2nd.nth(state, 1).unwrap();
+                assert_eq!(next_gradual, next_gradual_2nd);
+            }
+
+            if i % 3 == 0 {
+                let next_gradual_3rd = gradual_3rd.nth(state, 2).unwrap();
+                assert_eq!(next_gradual, next_gradual_3rd);
+            }
+
+            let expected = OsuPP::from_map(&map)
+                .difficulty(difficulty.clone())
+                .passed_objects(i as u32)
+                .state(state)
+                .calculate();
+
+            assert_eq!(next_gradual, expected);
+        }
+    }
+}
@@ -1,6 +1,9 @@
 mod difficulty_object;
 use difficulty_object::DifficultyObject;
 
+mod gradual;
+pub use gradual::{Osu2019GradualDifficulty, Osu2019GradualPerformance};
+
 mod osu_object;
 use osu_object::OsuObject;
 
@@ -14,3 +17,9 @@ mod skill_kind;
 use skill_kind::SkillKind;
 
 pub mod stars;
+pub(crate) use stars::difficulty;
+pub use stars::{OsuDifficultyAttributes, OsuPerformanceAttributes};
+
+mod strains;
+pub(crate) use strains::strains_with_difficulty;
+pub use strains::{strains, Osu2019Strains};
@@ -1,5 +1,15 @@
-use super::stars::{stars, OsuDifficultyAttributes, OsuPerformanceAttributes};
-use crate::{Beatmap, GameMods};
+use std::borrow::Cow;
+
+use super::stars::{difficulty, OsuDifficultyAttributes, OsuPerformanceAttributes};
+use crate::{
+    any::HitResultPriority,
+    osu::{
+        OsuDifficultyAttributes as ModernDifficultyAttributes, OsuPerformance, OsuScoreOrigin,
+        OsuScoreState,
+    },
+    util::{map_or_attrs::MapOrAttrs, search},
+    Beatmap, Difficulty, GameMods,
+};
 
 /// Calculator for pp on osu!standard maps.
 ///
@@ -16,7 +26,7 @@ use crate::{Beatmap, GameMods};
 ///     .mods(8 + 64) // HDDT
 ///     .combo(1234)
 ///     .misses(1)
-///     .accuracy(98.5) // should be set last
+///     .accuracy(98.5)
 ///     .calculate();
 ///
 /// println!("PP: {} | Stars: {}", attrs.pp, attrs.difficulty.stars);
@@ -28,63 +38,90 @@ use crate::{Beatmap, GameMods};
 ///
 /// println!("PP: {} | Stars: {}", next_result.pp, next_result.difficulty.stars);
 /// ```
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
+#[must_use]
 pub struct OsuPP<'m> {
-    map: Option<&'m Beatmap>,
+    map: Option<Cow<'m, Beatmap>>,
     attributes: Option<OsuDifficultyAttributes>,
-    mods: GameMods,
+    pub(crate) difficulty: Difficulty,
     combo: Option<u32>,
-    acc: Option<f32>,
+    acc: Option<f64>,
 
+    large_tick_hits: Option<u32>,
+    slider_end_hits: Option<u32>,
     n300: Option<u32>,
     n100: Option<u32>,
     n50: Option<u32>,
     n_misses: u32,
-
-    passed_objects: Option<u32>,
+    hitresult_priority: HitResultPriority,
 }
 
 impl<'m> OsuPP<'m> {
     /// Creates a new calculator for the given map.
     #[inline]
     pub fn from_map(map: &'m Beatmap) -> Self {
+        Self::from_cow(Cow::Borrowed(map))
+    }
+
+    pub(crate) const fn from_cow(map: Cow<'m, Beatmap>) -> Self {
         Self {
             map: Some(map),
             attributes: None,
-            mods: GameMods::default(),
+            difficulty: Difficulty::new(),
             combo: None,
             acc: None,
+            large_tick_hits: None,
+            slider_end_hits: None,
             n300: None,
             n100: None,
             n50: None,
             n_misses: 0,
-            passed_objects: None,
+            hitresult_priority: HitResultPriority::DEFAULT,
         }
     }
 
     /// Creates a new calculator for the given attributes.
     #[inline]
-    pub fn from_attributes(attributes: OsuDifficultyAttributes) -> Self {
+    pub const fn from_attributes(attributes: OsuDifficultyAttributes) -> Self {
         Self {
             map: None,
             attributes: Some(attributes),
-            mods: GameMods::default(),
+            difficulty: Difficulty::new(),
             combo: None,
             acc: None,
+            large_tick_hits: None,
+            slider_end_hits: None,
             n300: None,
             n100: None,
             n50: None,
             n_misses: 0,
-            passed_objects: None,
+            hitresult_priority: HitResultPriority::DEFAULT,
         }
     }
 
-    /// Specify mods through their bit values.
+    /// Specify mods.
+    ///
+    /// Accepted types are
+    /// - `u32`
+    /// - [`rosu_mods::GameModsLegacy`]
+    /// - [`rosu_mods::GameMods`]
+    /// - [`rosu_mods::GameModsIntermode`]
+    /// - [`&rosu_mods::GameModsIntermode`](rosu_mods::GameModsIntermode)
     ///
-    /// See [https://github.com/ppy/osu-api/wiki#mods](https://github.com/ppy/osu-api/wiki#mods)
+    /// See <https://github.com/ppy/osu-api/wiki#mods>
     #[inline]
     pub fn mods(mut self, mods: impl Into<GameMods>) -> Self {
-        self.mods = mods.into();
+        self.difficulty = self.difficulty.mods(mods);
+
+        self
+    }
+
+    /// Use the specified settings of the given [`Difficulty`].
+    ///
+    /// The [`Variant`](crate::any::Variant) of the [`Difficulty`] is ignored.
+    #[inline]
+    pub fn difficulty(mut self, difficulty: Difficulty) -> Self {
+        self.difficulty = difficulty;
 
         self
     }
@@ -97,6 +134,53 @@ impl<'m> OsuPP<'m> {
         self
     }
 
+    /// Whether the score was set on osu!lazer.
+    ///
+    /// Defaults to `false` since the 2019 algorithm predates osu!lazer.
+    ///
+    /// If enabled, the hit slider ticks and ends are used to determine slider
+    /// breaks instead of estimating them through the combo. Unless the
+    /// classic mod is enabled, slider heads and ends are also considered for
+    /// accuracy.
+    #[inline]
+    pub fn lazer(mut self, lazer: bool) -> Self {
+        self.difficulty = self.difficulty.lazer(lazer);
+
+        self
+    }
+
+    /// Specify the amount of "large tick" hits.
+    ///
+    /// Only relevant for osu!lazer scores, see [`OsuPP::lazer`].
+    ///
+    /// - *without* `CL`, this value is the amount of hit slider ticks and
+    ///   repeats
+    /// - *with* `CL`, this value is the amount of hit slider heads, ticks,
+    ///   and repeats
+    ///
+    /// Defaults to all large ticks being hit.
+    #[inline]
+    pub const fn n_large_ticks(mut self, n_large_ticks: u32) -> Self {
+        self.large_tick_hits = Some(n_large_ticks);
+
+        self
+    }
+
+    /// Specify the amount of hit slider ends.
+    ///
+    /// Only relevant for osu!lazer scores, see [`OsuPP::lazer`].
+    ///
+    /// osu! calls this value "slider tail hits" without the classic
+    /// mod and "small tick hits" with the classic mod.
+    ///
+    /// Defaults to all slider ends being hit.
+    #[inline]
+    pub const fn n_slider_ends(mut self, n_slider_ends: u32) -> Self {
+        self.slider_end_hits = Some(n_slider_ends);
+
+        self
+    }
+
     /// Specify the amount of 300s of a play.
     #[inline]
     pub fn n300(mut self, n300: u32) -> Self {
@@ -129,133 +213,328 @@ impl<'m> OsuPP<'m> {
         self
     }
 
+    /// Amount of passed objects for partial plays, e.g. a fail.
+    ///
+    /// If you want to calculate the performance after every few objects,
+    /// instead of using [`OsuPP`] multiple times with different
+    /// `passed_objects`, you should use [`Osu2019GradualPerformance`].
+    ///
+    /// [`Osu2019GradualPerformance`]: crate::osu_2019::Osu2019GradualPerformance
     #[inline]
     pub fn passed_objects(mut self, passed_objects: u32) -> Self {
-        self.passed_objects = self.passed_objects.replace(passed_objects);
+        self.difficulty = self.difficulty.passed_objects(passed_objects);
+
+        self
+    }
+
+    /// Adjust the clock rate used in the calculation.
+    ///
+    /// If none is specified, it will take the clock rate based on the mods
+    /// i.e. 1.5 for DT, 0.75 for HT and 1.0 otherwise.
+    ///
+    /// | Minimum | Maximum |
+    /// | :-----: | :-----: |
+    /// | 0.01    | 100     |
+    #[inline]
+    pub fn clock_rate(mut self, clock_rate: f64) -> Self {
+        self.difficulty = self.difficulty.clock_rate(clock_rate);
+
+        self
+    }
+
+    /// Override a beatmap's set AR.
+    ///
+    /// `with_mods` determines if the given value should be used before
+    /// or after accounting for mods, e.g. on `true` the value will be
+    /// used as is and on `false` it will be modified based on the mods.
+    ///
+    /// | Minimum | Maximum |
+    /// | :-----: | :-----: |
+    /// | -20     | 20      |
+    #[inline]
+    pub fn ar(mut self, ar: f32, with_mods: bool) -> Self {
+        self.difficulty = self.difficulty.ar(ar, with_mods);
+
+        self
+    }
+
+    /// Override a beatmap's set CS.
+    ///
+    /// `with_mods` determines if the given value should be used before
+    /// or after accounting for mods, e.g. on `true` the value will be
+    /// used as is and on `false` it will be modified based on the mods.
+    ///
+    /// | Minimum | Maximum |
+    /// | :-----: | :-----: |
+    /// | -20     | 20      |
+    #[inline]
+    pub fn cs(mut self, cs: f32, with_mods: bool) -> Self {
+        self.difficulty = self.difficulty.cs(cs, with_mods);
+
+        self
+    }
+
+    /// Override a beatmap's set HP.
+    ///
+    /// `with_mods` determines if the given value should be used before
+    /// or after accounting for mods, e.g. on `true` the value will be
+    /// used as is and on `false` it will be modified based on the mods.
+    ///
+    /// | Minimum | Maximum |
+    /// | :-----: | :-----: |
+    /// | -20     | 20      |
+    #[inline]
+    pub fn hp(mut self, hp: f32, with_mods: bool) -> Self {
+        self.difficulty = self.difficulty.hp(hp, with_mods);
 
         self
     }
 
-    /// Generate the hit results with respect to the given accuracy between `0` and `100`.
+    /// Override a beatmap's set OD.
     ///
-    /// Be sure to set `misses` beforehand!
+    /// `with_mods` determines if the given value should be used before
+    /// or after accounting for mods, e.g. on `true` the value will be
+    /// used as is and on `false` it will be modified based on the mods.
+    ///
+    /// | Minimum | Maximum |
+    /// | :-----: | :-----: |
+    /// | -20     | 20      |
+    #[inline]
+    pub fn od(mut self, od: f32, with_mods: bool) -> Self {
+        self.difficulty = self.difficulty.od(od, with_mods);
+
+        self
+    }
+
+    /// Provide parameters through an [`OsuScoreState`].
+    #[inline]
+    pub const fn state(mut self, state: OsuScoreState) -> Self {
+        let OsuScoreState {
+            max_combo,
+            large_tick_hits,
+            slider_end_hits,
+            n300,
+            n100,
+            n50,
+            misses,
+        } = state;
+
+        self.combo = Some(max_combo);
+        self.large_tick_hits = Some(large_tick_hits);
+        self.slider_end_hits = Some(slider_end_hits);
+        self.n300 = Some(n300);
+        self.n100 = Some(n100);
+        self.n50 = Some(n50);
+        self.n_misses = misses;
+
+        self
+    }
+
+    /// Specify the accuracy of a play between `0` and `100`.
+    /// This will be used to generate matching hitresults.
+    #[inline]
     pub fn accuracy(mut self, acc: f32) -> Self {
-        let n_objects = self.n_objects();
+        self.acc = Some(f64::from(acc.clamp(0.0, 100.0)) / 100.0);
 
-        let acc = acc / 100.0;
+        self
+    }
 
-        if self.n100.or(self.n50).is_some() {
-            let mut n100 = self.n100.unwrap_or(0);
-            let mut n50 = self.n50.unwrap_or(0);
+    /// Specify how hitresults should be generated.
+    ///
+    /// Defauls to [`HitResultPriority::BestCase`].
+    #[inline]
+    pub const fn hitresult_priority(mut self, priority: HitResultPriority) -> Self {
+        self.hitresult_priority = priority;
 
-            let placed_points = 2 * n100 + n50 + self.n_misses;
-            let missing_objects = n_objects - n100 - n50 - self.n_misses;
-            let missing_points =
-                ((6.0 * acc * n_objects as f32).round() as u32).saturating_sub(placed_points);
+        self
+    }
 
-            let mut n300 = missing_objects.min(missing_points / 6);
-            n50 += missing_objects - n300;
+    /// Create the [`OsuScoreState`] that will be used for performance calculation.
+    ///
+    /// Hitresults are generated the same way as for [`OsuPerformance`] and
+    /// are clamped to the amount of objects.
+    pub fn generate_state(&mut self) -> OsuScoreState {
+        let attrs = self.assert_attributes();
+
+        let modern_attrs = ModernDifficultyAttributes {
+            n_circles: attrs.n_circles as u32,
+            n_sliders: attrs.n_sliders as u32,
+            n_large_ticks: n_large_ticks(attrs),
+            n_spinners: attrs.n_spinners as u32,
+            max_combo: attrs.max_combo as u32,
+            ..Default::default()
+        };
 
-            if let Some(orig_n50) = self.n50.filter(|_| self.n100.is_none()) {
-                // Only n50s were changed, try to load some off again onto n100s
-                let difference = n50 - orig_n50;
-                let n = n300.min(difference / 4);
+        // The 2019 algorithm treats scores as osu!stable unless specified otherwise
+        let lazer = self.difficulty.get_lazer_opt().unwrap_or(false);
+
+        let mut performance = OsuPerformance::from(modern_attrs)
+            .difficulty(self.difficulty.clone().lazer(lazer))
+            .hitresult_priority(self.hitresult_priority)
+            .misses(self.n_misses);
+
+        performance.acc = self.acc;
+        performance.combo = self.combo;
+        performance.large_tick_hits = self.large_tick_hits;
+        performance.slider_end_hits = self.slider_end_hits;
+        performance.n300 = self.n300;
+        performance.n100 = self.n100;
+        performance.n50 = self.n50;
+
+        // Attributes are already available so no conversion can fail
+        let state = performance.generate_state().unwrap_or_default();
+
+        self.combo = Some(state.max_combo);
+        self.large_tick_hits = Some(state.large_tick_hits);
+        self.slider_end_hits = Some(state.slider_end_hits);
+        self.n300 = Some(state.n300);
+        self.n100 = Some(state.n100);
+        self.n50 = Some(state.n50);
+        self.n_misses = state.misses;
+
+        state
+    }
 
-                n300 -= n;
-                n100 += 5 * n;
-                n50 -= 4 * n;
-            }
+    /// The origin of the score w.r.t. the given attributes and whether
+    /// slider heads are ignored for accuracy.
+    fn score_origin(&self, attrs: &OsuDifficultyAttributes) -> (OsuScoreOrigin, bool) {
+        let lazer = self.difficulty.get_lazer_opt().unwrap_or(false);
+        let using_classic_slider_acc = self.difficulty.get_mods().no_slider_head_acc(lazer);
+        let n_sliders = attrs.n_sliders as u32;
+
+        let origin = match (lazer, using_classic_slider_acc) {
+            (false, _) => OsuScoreOrigin::Stable,
+            (true, false) => OsuScoreOrigin::WithSliderAcc {
+                max_large_ticks: n_large_ticks(attrs),
+                max_slider_ends: n_sliders,
+            },
+            (true, true) => OsuScoreOrigin::WithoutSliderAcc {
+                max_large_ticks: n_sliders + n_large_ticks(attrs),
+                max_slider_ends: n_sliders,
+            },
+        };
 
-            self.n300.replace(n300);
-            self.n100.replace(n100);
-            self.n50.replace(n50);
-        } else {
-            let misses = self.n_misses.min(n_objects);
-            let target_total = (acc * n_objects as f32 * 6.0).round() as u32;
-            let delta = target_total - (n_objects - misses);
-
-            let mut n300 = delta / 5;
-            let mut n100 = delta % 5;
-            let mut n50 = n_objects - n300 - n100 - misses;
-
-            // Sacrifice n300s to transform n50s into n100s
-            let n = n300.min(n50 / 4);
-            n300 -= n;
-            n100 += 5 * n;
-            n50 -= 4 * n;
-
-            self.n300.replace(n300);
-            self.n100.replace(n100);
-            self.n50.replace(n50);
-        }
+        (origin, using_classic_slider_acc)
+    }
 
-        let acc = (6 * self.n300.unwrap() + 2 * self.n100.unwrap() + self.n50.unwrap()) as f32
-            / (6 * n_objects) as f32;
+    fn assert_attributes(&mut self) -> &OsuDifficultyAttributes {
+        self.attributes.get_or_insert_with(|| {
+            let map = self.map.as_ref().expect("either map or attributes");
 
-        self.acc.replace(acc);
+            difficulty(&self.difficulty, map)
+        })
+    }
+
+    /// Adjust the current score so that it becomes a full combo.
+    ///
+    /// Misses are turned into n300s while n100s and n50s are kept as they
+    /// are. The combo is set to the map's max combo and all slider end and
+    /// large tick hits are considered hit.
+    pub fn if_fc(mut self) -> Self {
+        let state = self.generate_state();
+
+        self.n300 = Some(state.n300 + state.misses);
+        self.n100 = Some(state.n100);
+        self.n50 = Some(state.n50);
+        self.n_misses = 0;
+        self.combo = None;
+        self.large_tick_hits = None;
+        self.slider_end_hits = None;
+        self.acc = None;
 
         self
     }
 
-    fn assert_hitresults(&mut self) {
-        if self.acc.is_none() {
-            let n_objects = self.n_objects();
-
-            let remaining = n_objects
-                .saturating_sub(self.n300.unwrap_or(0))
-                .saturating_sub(self.n100.unwrap_or(0))
-                .saturating_sub(self.n50.unwrap_or(0))
-                .saturating_sub(self.n_misses);
-
-            if remaining > 0 {
-                if self.n300.is_none() {
-                    self.n300.replace(remaining);
-                    self.n100.get_or_insert(0);
-                    self.n50.get_or_insert(0);
-                } else if self.n100.is_none() {
-                    self.n100.replace(remaining);
-                    self.n50.get_or_insert(0);
-                } else if self.n50.is_none() {
-                    self.n50.replace(remaining);
-                } else {
-                    *self.n300.as_mut().unwrap() += remaining;
-                }
-            } else {
-                self.n300.get_or_insert(0);
-                self.n100.get_or_insert(0);
-                self.n50.get_or_insert(0);
-            }
+    /// Modify the current [`OsuScoreState`] through the given function.
+    ///
+    /// Unspecified hitresults are generated before the function is called.
+    /// All adjusted values are used as is and a previously specified
+    /// accuracy is discarded. Slider end and large tick hits only have an
+    /// effect on osu!lazer scores.
+    pub fn what_if<F>(mut self, f: F) -> Self
+    where
+        F: FnOnce(&mut OsuScoreState),
+    {
+        let mut state = self.generate_state();
+        f(&mut state);
+        self.acc = None;
+
+        self.state(state)
+    }
 
-            let numerator = self.n50.unwrap() + self.n100.unwrap() * 2 + self.n300.unwrap() * 6;
-            self.acc.replace(numerator as f32 / n_objects as f32 / 6.0);
-        }
+    /// Find the minimal accuracy between `0.0` and `100.0` that is required
+    /// to reach the given amount of pp.
+    ///
+    /// All hitresults except for misses are generated from the accuracy while
+    /// all other parameters are kept as they are.
+    ///
+    /// Returns `None` if the pp can't be reached even with 100% accuracy.
+    pub fn accuracy_for_pp(mut self, pp: f64) -> Option<f64> {
+        self.assert_attributes();
+        self.n300 = None;
+        self.n100 = None;
+        self.n50 = None;
+
+        search::min_f64(0.0, 100.0, |acc| {
+            self.clone().accuracy(acc as f32).reaches_pp(pp)
+        })
+    }
+
+    /// Find the maximal amount of misses for which the given amount of pp
+    /// can still be reached.
+    ///
+    /// Returns `None` if the pp can't be reached even without misses.
+    pub fn misses_for_pp(mut self, pp: f64) -> Option<u32> {
+        let attrs = self.assert_attributes();
+        let n_objects = (attrs.n_circles + attrs.n_sliders + attrs.n_spinners) as u32;
+
+        search::max_u32(0, n_objects, |misses| {
+            self.clone().misses(misses).reaches_pp(pp)
+        })
+    }
+
+    /// Find the minimal combo that is required to reach the given amount of
+    /// pp.
+    ///
+    /// Returns `None` if the pp can't be reached even with max combo.
+    pub fn combo_for_pp(mut self, pp: f64) -> Option<u32> {
+        let max_combo = self.assert_attributes().max_combo as u32;
+
+        search::min_u32(0, max_combo, |combo| {
+            self.clone().combo(combo).reaches_pp(pp)
+        })
     }
 
     /// Returns an object which contains the pp and [`DifficultyAttributes`](crate::osu::DifficultyAttributes)
     /// containing stars and other attributes.
     pub fn calculate(mut self) -> OsuPerformanceAttributes {
-        if self.attributes.is_none() {
-            let attributes = stars(self.map.unwrap(), self.mods.clone(), self.passed_objects);
-            self.attributes.replace(attributes);
-        }
-
-        // Make sure the hitresults and accuracy are set
-        self.assert_hitresults();
+        // Make sure the attributes and hitresults are set
+        let state = self.generate_state();
 
         let total_hits = self.total_hits() as f32;
         let mut multiplier = 1.09;
 
-        let effective_miss_count = self.calculate_effective_miss_count();
+        let (origin, using_classic_slider_acc) =
+            self.score_origin(self.attributes.as_ref().unwrap());
+
+        let acc = state.accuracy(origin) as f32;
+
+        let effective_miss_count = self.calculate_effective_miss_count(
+            origin,
+            state.large_tick_hits,
+            state.slider_end_hits,
+        );
 
         // SO penalty
-        if self.mods.so() {
+        if self.difficulty.get_mods().so() {
             multiplier *=
                 1.0 - (self.attributes.as_ref().unwrap().n_spinners as f32 / total_hits).powf(0.85);
         }
 
-        let mut aim_value = self.compute_aim_value(total_hits, effective_miss_count);
-        let speed_value = self.compute_speed_value(total_hits, effective_miss_count);
-        let acc_value = self.compute_accuracy_value(total_hits);
+        let mut aim_value = self.compute_aim_value(total_hits, effective_miss_count, acc);
+        let speed_value = self.compute_speed_value(total_hits, effective_miss_count, acc);
+        let acc_value = self.compute_accuracy_value(total_hits, using_classic_slider_acc);
+        let flashlight_value = self.compute_flashlight_value(total_hits, effective_miss_count, acc);
 
         let mut acc_depression = 1.0;
 
@@ -264,7 +543,7 @@ impl<'m> OsuPP<'m> {
             ((difficulty.aim_strain / difficulty.speed_strain) * 100.0).round() / 100.0;
 
         if streams_nerf < 1.09 {
-            let acc_factor = (1.0 - self.acc.unwrap()).abs();
+            let acc_factor = (1.0 - acc).abs();
             acc_depression = (0.86 - acc_factor).max(0.5);
 
             if acc_depression > 0.0 {
@@ -272,6 +551,8 @@ impl<'m> OsuPP<'m> {
             }
         }
 
+        // The flashlight value is not calibrated yet so FL is still only
+        // rewarded through the aim bonus
         let pp = (aim_value.powf(1.185)
             + speed_value.powf(0.83 * acc_depression)
             + acc_value.powf(1.14))
@@ -283,16 +564,21 @@ impl<'m> OsuPP<'m> {
             pp_aim: aim_value as f64,
             pp_speed: speed_value as f64,
             pp_acc: acc_value as f64,
+            pp_flashlight: f64::from(flashlight_value),
             pp: pp as f64,
             effective_miss_count: effective_miss_count as f64,
         }
     }
 
-    fn compute_aim_value(&self, total_hits: f32, effective_miss_count: f32) -> f32 {
+    fn reaches_pp(self, pp: f64) -> bool {
+        self.calculate().pp >= pp
+    }
+
+    fn compute_aim_value(&self, total_hits: f32, effective_miss_count: f32, acc: f32) -> f32 {
         let attributes = self.attributes.as_ref().unwrap();
 
         // TD penalty
-        let raw_aim = if self.mods.td() {
+        let raw_aim = if self.difficulty.get_mods().td() {
             attributes.aim_strain.powf(0.8) as f32
         } else {
             attributes.aim_strain as f32
@@ -326,36 +612,33 @@ impl<'m> OsuPP<'m> {
         aim_value *= 1.0 + ar_factor as f32 * len_bonus;
 
         // HD bonus
-        if self.mods.hd() {
+        if self.difficulty.get_mods().hd() {
             aim_value *= 1.0 + 0.05 * (11.0 - attributes.ar) as f32;
         }
 
         // FL bonus
-        if self.mods.fl() {
+        if self.difficulty.get_mods().fl() {
             aim_value *= 1.0
                 + 0.3 * (total_hits / 200.0).min(1.0)
-                + (total_hits > 200.0) as u8 as f32
+                + f32::from(u8::from(total_hits > 200.0))
                     * 0.25
                     * ((total_hits - 200.0) / 300.0).min(1.0)
-                + (total_hits > 500.0) as u8 as f32 * (total_hits - 500.0) / 1600.0;
+                + f32::from(u8::from(total_hits > 500.0)) * (total_hits - 500.0) / 1600.0;
         }
 
         // Scaling the aim value with accuracy and OD
         aim_value *= (0.93 + attributes.od as f32 * attributes.od as f32 / 750.0)
-            * self
-                .acc
-                .unwrap()
-                .powf((14.5 - attributes.od.max(8.0) as f32) / 2.0);
+            * acc.powf((14.5 - attributes.od.max(8.0) as f32) / 2.0);
 
         aim_value *= 0.98_f32.powf(match (self.n50.unwrap() as f32) < total_hits / 500.0 {
             true => 0.0,
             false => self.n50.unwrap() as f32 - total_hits / 500.0,
         });
-        
+
         aim_value
     }
 
-    fn compute_speed_value(&self, total_hits: f32, effective_miss_count: f32) -> f32 {
+    fn compute_speed_value(&self, total_hits: f32, effective_miss_count: f32, acc: f32) -> f32 {
         let attributes = self.attributes.as_ref().unwrap();
 
         let mut speed_value =
@@ -389,16 +672,13 @@ impl<'m> OsuPP<'m> {
         }
 
         // HD bonus
-        if self.mods.hd() {
+        if self.difficulty.get_mods().hd() {
             speed_value *= 1.0 + 0.05 * (11.0 - attributes.ar) as f32;
         }
 
         // Scaling the speed value with accuracy and OD
         speed_value *= (0.93 + attributes.od as f32 * attributes.od as f32 / 750.0)
-            * self
-                .acc
-                .unwrap()
-                .powf((14.5 - attributes.od.max(8.0) as f32) / 2.0);
+            * acc.powf((14.5 - attributes.od.max(8.0) as f32) / 2.0);
 
         speed_value *= 0.98_f32.powf(match (self.n50.unwrap() as f32) < total_hits / 500.0 {
             true => 0.0,
@@ -408,9 +688,63 @@ impl<'m> OsuPP<'m> {
         speed_value
     }
 
-    fn compute_accuracy_value(&self, total_hits: f32) -> f32 {
+    /// Uses the formula of the 2021 osu!lazer flashlight value.
+    fn compute_flashlight_value(
+        &self,
+        total_hits: f32,
+        effective_miss_count: f32,
+        acc: f32,
+    ) -> f32 {
+        if !self.difficulty.get_mods().fl() {
+            return 0.0;
+        }
+
+        let attributes = self.attributes.as_ref().unwrap();
+
+        // TD penalty
+        let raw_flashlight = if self.difficulty.get_mods().td() {
+            attributes.flashlight_strain.powf(0.8) as f32
+        } else {
+            attributes.flashlight_strain as f32
+        };
+
+        let mut flashlight_value = raw_flashlight.powi(2) * 25.0;
+
+        // HD bonus
+        if self.difficulty.get_mods().hd() {
+            flashlight_value *= 1.3;
+        }
+
+        // Penalize misses
+        if effective_miss_count > 0.0 {
+            flashlight_value *= 0.97
+                * (1.0 - (effective_miss_count / total_hits).powf(0.775))
+                    .powf(effective_miss_count.powf(0.875));
+        }
+
+        // Account for shorter maps having a higher ratio of 0 combo/100 combo flashlight radius
+        flashlight_value *= 0.7
+            + 0.1 * (total_hits / 200.0).min(1.0)
+            + (total_hits > 200.0) as u8 as f32 * 0.2 * ((total_hits - 200.0) / 200.0).min(1.0);
+
+        // Scale the flashlight value with accuracy slightly
+        flashlight_value *= 0.5 + acc / 2.0;
+
+        // It is important to also consider accuracy difficulty when doing that
+        flashlight_value *= 0.98 + (attributes.od * attributes.od) as f32 / 2500.0;
+
+        flashlight_value
+    }
+
+    fn compute_accuracy_value(&self, total_hits: f32, using_classic_slider_acc: bool) -> f32 {
         let attributes = self.attributes.as_ref().unwrap();
-        let n_circles = attributes.n_circles as f32;
+
+        // Slider heads are judged like circles on osu!lazer
+        let n_circles = if using_classic_slider_acc {
+            attributes.n_circles as f32
+        } else {
+            (attributes.n_circles + attributes.n_sliders) as f32
+        };
         let n300 = self.n300.unwrap_or(0) as f32;
         let n100 = self.n100.unwrap_or(0) as f32;
         let n50 = self.n50.unwrap_or(0) as f32;
@@ -426,12 +760,12 @@ impl<'m> OsuPP<'m> {
         acc_value *= ((n_circles as f32 / 1000.0).powf(0.3)).min(1.15);
 
         // HD bonus
-        if self.mods.hd() {
+        if self.difficulty.get_mods().hd() {
             acc_value *= 1.08;
         }
 
         // FL bonus
-        if self.mods.fl() {
+        if self.difficulty.get_mods().fl() {
             acc_value *= 1.02;
         }
 
@@ -455,40 +789,171 @@ impl<'m> OsuPP<'m> {
     }
 
     #[inline]
-    fn calculate_effective_miss_count(&self) -> f32 {
+    fn calculate_effective_miss_count(
+        &self,
+        origin: OsuScoreOrigin,
+        large_tick_hits: u32,
+        slider_end_hits: u32,
+    ) -> f32 {
         let mut combo_based_miss_count = 0.0;
 
         let attributes = self.attributes.as_ref().unwrap();
         let combo = self.combo.unwrap_or(attributes.max_combo as u32) as f32;
         let n100 = self.n100.unwrap_or(0) as f32;
         let n50 = self.n50.unwrap_or(0) as f32;
+        let n_misses = self.n_misses as f32;
 
         if attributes.n_sliders > 0 {
+            if let OsuScoreOrigin::WithSliderAcc {
+                max_large_ticks,
+                max_slider_ends,
+            } = origin
+            {
+                // Dropped slider ends don't break combo but lower the max combo
+                let fc_threshold =
+                    attributes.max_combo as f32 - (max_slider_ends - slider_end_hits) as f32;
+
+                if combo < fc_threshold {
+                    combo_based_miss_count = fc_threshold / combo.max(1.0);
+                }
+
+                // Missed ticks break combo just like regular misses
+                let large_tick_misses = (max_large_ticks - large_tick_hits) as f32;
+
+                return combo_based_miss_count
+                    .min(large_tick_misses + n_misses)
+                    .max(n_misses);
+            }
+
+            // The amount of dropped slider ends is unknown so we estimate it
+            // to be 10% of all sliders
             let fc_threshold = attributes.max_combo as f32 - (0.1 * attributes.n_sliders as f32);
+
             if combo < fc_threshold {
                 combo_based_miss_count = fc_threshold / combo.max(1.0);
             }
         }
 
-        combo_based_miss_count = combo_based_miss_count.min(n100 + n50 + self.n_misses as f32);
-        combo_based_miss_count.max(self.n_misses as f32)
+        combo_based_miss_count = combo_based_miss_count.min(n100 + n50 + n_misses);
+        combo_based_miss_count.max(n_misses)
     }
 
     #[inline]
     fn n_objects(&self) -> u32 {
-        if let Some(passed_objects) = self.passed_objects {
-            return passed_objects;
-        }
+        let n_objects = match (self.attributes.as_ref(), self.map.as_ref()) {
+            (Some(attributes), _) => {
+                attributes.n_circles + attributes.n_sliders + attributes.n_spinners
+            }
+            (None, Some(map)) => map.hit_objects.len(),
+            (None, None) => 0,
+        };
+
+        n_objects.min(self.difficulty.get_passed_objects()) as u32
+    }
+}
 
-        match self.attributes.as_ref() {
-            Some(attributes) => {
-                (attributes.n_circles + attributes.n_sliders + attributes.n_spinners) as u32
+impl<'map> TryFrom<OsuPerformance<'map>> for OsuPP<'map> {
+    type Error = OsuPerformance<'map>;
+
+    /// Try to create [`OsuPP`] through [`OsuPerformance`].
+    ///
+    /// Returns `None` if [`OsuPerformance`] does not contain a beatmap, i.e.
+    /// if it was constructed through attributes or
+    /// [`OsuPerformance::generate_state`] was called.
+    fn try_from(mut osu: OsuPerformance<'map>) -> Result<Self, Self::Error> {
+        let map = match osu.map_or_attrs {
+            MapOrAttrs::Map(map) => map,
+            map_or_attrs @ MapOrAttrs::Attrs(_) => {
+                osu.map_or_attrs = map_or_attrs;
+
+                return Err(osu);
             }
-            None => self.map.unwrap().hit_objects.len() as u32,
-        }
+        };
+
+        let OsuPerformance {
+            map_or_attrs: _,
+            difficulty,
+            acc,
+            combo,
+            large_tick_hits,
+            slider_end_hits,
+            n300,
+            n100,
+            n50,
+            misses,
+            hitresult_priority,
+            unstable_rate: _,
+        } = osu;
+
+        let mut pp = Self::from_cow(map)
+            .difficulty(difficulty)
+            .hitresult_priority(hitresult_priority);
+
+        pp.acc = acc;
+        pp.combo = combo;
+        pp.large_tick_hits = large_tick_hits;
+        pp.slider_end_hits = slider_end_hits;
+        pp.n300 = n300;
+        pp.n100 = n100;
+        pp.n50 = n50;
+        pp.n_misses = misses.unwrap_or(0);
+
+        Ok(pp)
     }
 }
 
+impl<'map> TryFrom<OsuPP<'map>> for OsuPerformance<'map> {
+    type Error = OsuPP<'map>;
+
+    /// Try to create [`OsuPerformance`] through [`OsuPP`].
+    ///
+    /// Returns `None` if [`OsuPP`] does not contain a beatmap, i.e.
+    /// if it was constructed through attributes.
+    fn try_from(mut osu: OsuPP<'map>) -> Result<Self, Self::Error> {
+        let Some(map) = osu.map.take() else {
+            return Err(osu);
+        };
+
+        let OsuPP {
+            map: _,
+            attributes: _,
+            difficulty,
+            combo,
+            acc,
+            large_tick_hits,
+            slider_end_hits,
+            n300,
+            n100,
+            n50,
+            n_misses,
+            hitresult_priority,
+        } = osu;
+
+        let mut performance = OsuPerformance::from_map_or_attrs(MapOrAttrs::Map(map))
+            .difficulty(difficulty)
+            .hitresult_priority(hitresult_priority)
+            .misses(n_misses);
+
+        performance.acc = acc;
+        performance.combo = combo;
+        performance.large_tick_hits = large_tick_hits;
+        performance.slider_end_hits = slider_end_hits;
+        performance.n300 = n300;
+        performance.n100 = n100;
+        performance.n50 = n50;
+
+        Ok(performance)
+    }
+}
+
+/// The amount of slider ticks and repeats.
+const fn n_large_ticks(attrs: &OsuDifficultyAttributes) -> u32 {
+    // Each slider contributes combo for its head, ticks, repeats, and tail
+    attrs
+        .max_combo
+        .saturating_sub(attrs.n_circles + attrs.n_spinners + 2 * attrs.n_sliders) as u32
+}
+
 /// Provides attributes for an osu! beatmap.
 pub trait OsuAttributeProvider {
     /// Returns the attributes of the map.
@@ -508,3 +973,289 @@ impl OsuAttributeProvider for OsuPerformanceAttributes {
         Some(self.difficulty)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::float_cmp)]
+
+    use std::sync::OnceLock;
+
+    use proptest::prelude::*;
+    use rosu_mods::{
+        generated_mods::{ClassicOsu, DifficultyAdjustOsu, DoubleTimeOsu, RelaxOsu},
+        GameMod, GameMods as GameModsLazer,
+    };
+
+    use super::*;
+
+    static ATTRS: OnceLock<OsuDifficultyAttributes> = OnceLock::new();
+
+    const N_OBJECTS: u32 = 601;
+
+    fn test_map() -> Beatmap {
+        Beatmap::from_path("./resources/2785319.osu").unwrap()
+    }
+
+    fn attrs() -> OsuDifficultyAttributes {
+        ATTRS
+            .get_or_init(|| OsuPP::from_map(&test_map()).calculate().difficulty)
+            .clone()
+    }
+
+    #[test]
+    fn custom_clock_rate() {
+        let map = test_map();
+
+        let mut mods = GameModsLazer::new();
+        mods.insert(GameMod::RelaxOsu(RelaxOsu::default()));
+        mods.insert(GameMod::DoubleTimeOsu(DoubleTimeOsu {
+            speed_change: Some(1.3),
+            adjust_pitch: None,
+        }));
+
+        let lazer = OsuPP::from_map(&map).mods(mods).calculate();
+        let custom = OsuPP::from_map(&map).mods(128).clock_rate(1.3).calculate();
+
+        assert_eq!(lazer, custom);
+
+        let dt = OsuPP::from_map(&map).mods(128 + 64).calculate();
+
+        assert!(custom.pp < dt.pp);
+    }
+
+    #[test]
+    fn attribute_overrides() {
+        let map = test_map();
+
+        let attrs = OsuPP::from_map(&map)
+            .mods(128 + 16) // RXHR
+            .ar(9.5, true)
+            .od(8.0, false)
+            .cs(4.0, true)
+            .hp(5.0, true)
+            .calculate()
+            .difficulty;
+
+        assert_eq!(attrs.ar, 9.5);
+        assert_eq!(attrs.od, 10.0);
+        assert_eq!(attrs.cs, 4.0);
+        assert_eq!(attrs.hp, 5.0);
+    }
+
+    #[test]
+    fn difficulty_adjust_settings() {
+        let map = test_map();
+
+        let mut mods = GameModsLazer::new();
+        mods.insert(GameMod::DifficultyAdjustOsu(DifficultyAdjustOsu {
+            approach_rate: Some(7.0),
+            ..DifficultyAdjustOsu::default()
+        }));
+
+        let attrs = OsuPP::from_map(&map).mods(mods).calculate().difficulty;
+
+        assert_eq!(attrs.ar, 7.0);
+    }
+
+    #[test]
+    fn flashlight() {
+        let map = test_map();
+
+        let relax = OsuPP::from_map(&map).mods(128).calculate();
+
+        assert!(relax.difficulty.flashlight_strain > 0.0);
+        assert_eq!(relax.pp_flashlight, 0.0);
+
+        let flashlight = OsuPP::from_map(&map).mods(128 + 1024).calculate(); // RXFL
+
+        assert_eq!(flashlight.difficulty, relax.difficulty);
+        assert!(flashlight.pp_flashlight > 0.0);
+        assert!(flashlight.pp > relax.pp);
+
+        // FL is rewarded through the legacy length bonus on aim
+        let attrs = &flashlight.difficulty;
+        let total_hits = (attrs.n_circles + attrs.n_sliders + attrs.n_spinners) as f64;
+        let len_bonus = 1.0
+            + 0.3 * (total_hits / 200.0).min(1.0)
+            + 0.25 * ((total_hits - 200.0) / 300.0).clamp(0.0, 1.0)
+            + ((total_hits - 500.0) / 1600.0).max(0.0);
+
+        assert!((flashlight.pp_aim / relax.pp_aim - len_bonus).abs() < 1e-4);
+
+        let hidden = OsuPP::from_map(&map).mods(128 + 1024 + 8).calculate(); // RXHDFL
+
+        assert!(hidden.pp_flashlight > flashlight.pp_flashlight);
+    }
+
+    #[test]
+    fn lazer_slider_breaks() {
+        let map = test_map();
+
+        let stable = OsuPP::from_map(&map).mods(128).combo(400).calculate();
+        let explicit_stable = OsuPP::from_map(&map)
+            .mods(128)
+            .lazer(false)
+            .combo(400)
+            .calculate();
+
+        assert_eq!(stable, explicit_stable);
+
+        let attrs = stable.difficulty.clone();
+        let max_large_ticks = n_large_ticks(&attrs);
+
+        // No dropped slider ends or ticks so the combo was broken by a
+        // slider end which doesn't count as miss
+        let full_sliders = OsuPP::from_attributes(attrs.clone())
+            .mods(128)
+            .lazer(true)
+            .combo(attrs.max_combo as u32 - 1)
+            .n_slider_ends(attrs.n_sliders as u32 - 1)
+            .calculate();
+
+        assert_eq!(full_sliders.effective_miss_count, 0.0);
+
+        let tick_misses = OsuPP::from_attributes(attrs.clone())
+            .mods(128)
+            .lazer(true)
+            .combo(400)
+            .n_large_ticks(max_large_ticks - 2)
+            .calculate();
+
+        assert!(tick_misses.effective_miss_count > 0.0);
+        assert!(tick_misses.effective_miss_count <= 2.0);
+
+        // Without imperfect hits, stable can't attribute the combo break
+        assert_eq!(stable.effective_miss_count, 0.0);
+
+        // Dropped ticks are not kept for a full combo
+        let mut if_fc = OsuPP::from_attributes(attrs.clone())
+            .mods(128)
+            .lazer(true)
+            .combo(400)
+            .n_large_ticks(max_large_ticks - 2)
+            .n_slider_ends(attrs.n_sliders as u32 - 1)
+            .if_fc();
+
+        let state = if_fc.generate_state();
+
+        assert_eq!(state.large_tick_hits, max_large_ticks);
+        assert_eq!(state.slider_end_hits, attrs.n_sliders as u32);
+        assert_eq!(if_fc.calculate().effective_miss_count, 0.0);
+    }
+
+    #[test]
+    fn lazer_slider_acc() {
+        let map = test_map();
+
+        let stable = OsuPP::from_map(&map).mods(128).n100(20).calculate();
+        let lazer = OsuPP::from_map(&map)
+            .mods(128)
+            .lazer(true)
+            .n100(20)
+            .calculate();
+
+        // Slider ticks and ends increase the accuracy
+        assert!(lazer.pp_aim > stable.pp_aim);
+
+        let mut classic = GameModsLazer::new();
+        classic.insert(GameMod::RelaxOsu(RelaxOsu::default()));
+        classic.insert(GameMod::ClassicOsu(ClassicOsu::default()));
+
+        let lazer_classic = OsuPP::from_map(&map)
+            .mods(classic)
+            .lazer(true)
+            .n100(20)
+            .calculate();
+
+        assert!(lazer_classic.pp_aim > stable.pp_aim);
+        assert_ne!(lazer_classic.pp_aim, lazer.pp_aim);
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(500))]
+
+        #[test]
+        fn hitresults_match_modern(
+            acc in prop::option::weighted(0.8, 0.0_f32..=100.0),
+            n300 in prop::option::weighted(0.1, 0_u32..=N_OBJECTS + 10),
+            n100 in prop::option::weighted(0.1, 0_u32..=N_OBJECTS + 10),
+            n50 in prop::option::weighted(0.1, 0_u32..=N_OBJECTS + 10),
+            n_misses in 0_u32..=N_OBJECTS + 10,
+            best_case in prop::bool::ANY,
+        ) {
+            let attrs = attrs();
+
+            let priority = if best_case {
+                HitResultPriority::BestCase
+            } else {
+                HitResultPriority::WorstCase
+            };
+
+            let mut pp = OsuPP::from_attributes(attrs.clone())
+                .misses(n_misses)
+                .hitresult_priority(priority);
+
+            let mut modern = OsuPerformance::from(ModernDifficultyAttributes {
+                n_circles: attrs.n_circles as u32,
+                n_sliders: attrs.n_sliders as u32,
+                n_large_ticks: n_large_ticks(&attrs),
+                n_spinners: attrs.n_spinners as u32,
+                max_combo: attrs.max_combo as u32,
+                ..Default::default()
+            })
+            .lazer(false)
+            .misses(n_misses)
+            .hitresult_priority(priority);
+
+            if let Some(acc) = acc {
+                pp = pp.accuracy(acc);
+                modern = modern.accuracy(f64::from(acc));
+            }
+
+            if let Some(n300) = n300 {
+                pp = pp.n300(n300);
+                modern = modern.n300(n300);
+            }
+
+            if let Some(n100) = n100 {
+                pp = pp.n100(n100);
+                modern = modern.n100(n100);
+            }
+
+            if let Some(n50) = n50 {
+                pp = pp.n50(n50);
+                modern = modern.n50(n50);
+            }
+
+            let state = pp.clone().generate_state();
+
+            assert_eq!(state, modern.generate_state().unwrap());
+            assert!(state.misses <= N_OBJECTS);
+
+            let attrs = pp.calculate();
+
+            assert!(attrs.pp.is_finite());
+        }
+    }
+
+    #[test]
+    fn impossible_inputs() {
+        let map = test_map();
+
+        let attrs = OsuPP::from_map(&map)
+            .mods(128)
+            .misses(N_OBJECTS + 100)
+            .n300(N_OBJECTS * 2)
+            .accuracy(100.0)
+            .calculate();
+
+        assert!(attrs.pp.is_finite());
+
+        let mut pp = OsuPP::from_map(&map).misses(10).accuracy(0.0);
+        let state = pp.generate_state();
+
+        assert_eq!(state.misses, 10);
+        assert_eq!(state.n300, 0);
+        assert_eq!(state.total_hits(), N_OBJECTS);
+    }
+}
@@ -1,6 +1,6 @@
-use super::{DifficultyObject, SkillKind};
+use super::{skill_kind::HistoryObject, DifficultyObject, SkillKind};
 
-use std::cmp::Ordering;
+use std::{cmp::Ordering, collections::VecDeque, mem};
 
 const SPEED_SKILL_MULTIPLIER: f32 = 1400.0;
 const SPEED_STRAIN_DECAY_BASE: f32 = 0.3;
@@ -8,8 +8,17 @@ const SPEED_STRAIN_DECAY_BASE: f32 = 0.3;
 const AIM_SKILL_MULTIPLIER: f32 = 26.25;
 const AIM_STRAIN_DECAY_BASE: f32 = 0.15;
 
+// Adopted from the 2021 osu!lazer flashlight skill and scaled down to match
+// the values of the 2019 aim and speed skills. The values are not calibrated
+// against RX+FL scores yet which is why the skill is not part of the pp.
+const FLASHLIGHT_SKILL_MULTIPLIER: f32 = 0.05;
+const FLASHLIGHT_STRAIN_DECAY_BASE: f32 = 0.15;
+const FLASHLIGHT_HISTORY_LEN: usize = 10;
+
 const DECAY_WEIGHT: f32 = 0.9;
+const FLASHLIGHT_DECAY_WEIGHT: f32 = 1.0;
 
+#[derive(Clone)]
 pub(crate) struct Skill {
     current_strain: f32,
     current_section_peak: f32,
@@ -19,6 +28,16 @@ pub(crate) struct Skill {
 
     prev_time: Option<f32>,
     pub(crate) object_strains: Vec<f32>,
+
+    /// Most recent objects first; only tracked for [`SkillKind::Flashlight`].
+    history: VecDeque<HistoryObject>,
+}
+
+/// The amounts of strain peaks and object strains of a [`Skill`].
+#[derive(Copy, Clone)]
+pub(crate) struct SkillHistoryLen {
+    strain_peaks: usize,
+    object_strains: usize,
 }
 
 impl Skill {
@@ -33,9 +52,42 @@ impl Skill {
 
             prev_time: None,
             object_strains: Vec::new(),
+
+            history: VecDeque::new(),
         }
     }
 
+    /// Clone the skill without its strain peaks and object strains and
+    /// return their amounts alongside.
+    pub(crate) fn clone_without_history(&mut self) -> (Self, SkillHistoryLen) {
+        let strain_peaks = mem::take(&mut self.strain_peaks);
+        let object_strains = mem::take(&mut self.object_strains);
+
+        let len = SkillHistoryLen {
+            strain_peaks: strain_peaks.len(),
+            object_strains: object_strains.len(),
+        };
+
+        let clone = self.clone();
+        self.strain_peaks = strain_peaks;
+        self.object_strains = object_strains;
+
+        (clone, len)
+    }
+
+    /// Replace the skill with a clone of [`clone_without_history`] whose
+    /// strain peaks and object strains are the current ones truncated to
+    /// `len`.
+    ///
+    /// [`clone_without_history`]: Skill::clone_without_history
+    pub(crate) fn restore_with_history(&mut self, mut clone: Self, len: SkillHistoryLen) {
+        clone.strain_peaks = mem::take(&mut self.strain_peaks);
+        clone.strain_peaks.truncate(len.strain_peaks);
+        clone.object_strains = mem::take(&mut self.object_strains);
+        clone.object_strains.truncate(len.object_strains);
+        *self = clone;
+    }
+
     #[inline]
     pub(crate) fn save_current_peak(&mut self) {
         self.strain_peaks.push(self.current_section_peak);
@@ -49,30 +101,61 @@ impl Skill {
     #[inline]
     pub(crate) fn process(&mut self, current: &DifficultyObject<'_>) {
         self.current_strain *= self.strain_decay(current.delta);
-        self.current_strain += self.kind.strain_value_of(current) * self.skill_multiplier();
+        self.current_strain +=
+            self.kind.strain_value_of(current, &self.history) * self.skill_multiplier();
 
         self.object_strains.push(self.current_strain);
 
         self.current_section_peak = self.current_section_peak.max(self.current_strain);
         self.prev_time.replace(current.base.time);
+
+        if let SkillKind::Flashlight = self.kind {
+            if self.history.len() == FLASHLIGHT_HISTORY_LEN {
+                self.history.pop_back();
+            }
+
+            self.history.push_front(HistoryObject::new(current));
+        }
     }
 
     pub(crate) fn difficulty_value(&mut self) -> f32 {
         let mut difficulty = 0.0;
         let mut weight = 1.0;
+        let decay_weight = self.decay_weight();
 
         self.strain_peaks
             .sort_unstable_by(|a, b| b.partial_cmp(a).unwrap_or(Ordering::Equal));
 
         for &strain in self.strain_peaks.iter() {
             difficulty += strain * weight;
-            weight *= DECAY_WEIGHT;
+            weight *= decay_weight;
         }
 
         difficulty
     }
 
-    pub(crate) fn count_difficult_strains(&mut self) -> f64 {
+    /// Same as [`Skill::difficulty_value`] but the current section peak is
+    /// considered without saving it and the strain peaks are not modified.
+    pub(crate) fn as_difficulty_value(&self) -> f32 {
+        let mut strain_peaks = Vec::with_capacity(self.strain_peaks.len() + 1);
+        strain_peaks.extend_from_slice(&self.strain_peaks);
+        strain_peaks.push(self.current_section_peak);
+
+        let mut difficulty = 0.0;
+        let mut weight = 1.0;
+        let decay_weight = self.decay_weight();
+
+        strain_peaks.sort_unstable_by(|a, b| b.partial_cmp(a).unwrap_or(Ordering::Equal));
+
+        for strain in strain_peaks {
+            difficulty += strain * weight;
+            weight *= decay_weight;
+        }
+
+        difficulty
+    }
+
+    pub(crate) fn count_difficult_strains(&self) -> f64 {
         let top_strain = self
             .object_strains
             .iter()
@@ -89,6 +172,7 @@ impl Skill {
         match self.kind {
             SkillKind::Aim => AIM_SKILL_MULTIPLIER,
             SkillKind::Speed => SPEED_SKILL_MULTIPLIER,
+            SkillKind::Flashlight => FLASHLIGHT_SKILL_MULTIPLIER,
         }
     }
 
@@ -97,6 +181,15 @@ impl Skill {
         match self.kind {
             SkillKind::Aim => AIM_STRAIN_DECAY_BASE,
             SkillKind::Speed => SPEED_STRAIN_DECAY_BASE,
+            SkillKind::Flashlight => FLASHLIGHT_STRAIN_DECAY_BASE,
+        }
+    }
+
+    #[inline]
+    const fn decay_weight(&self) -> f32 {
+        match self.kind {
+            SkillKind::Aim | SkillKind::Speed => DECAY_WEIGHT,
+            SkillKind::Flashlight => FLASHLIGHT_DECAY_WEIGHT,
         }
     }
 
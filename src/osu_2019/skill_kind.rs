@@ -1,3 +1,7 @@
+use std::collections::VecDeque;
+
+use rosu_map::util::Pos;
+
 use super::DifficultyObject;
 
 const SINGLE_SPACING_TRESHOLD: f32 = 125.0;
@@ -12,14 +16,44 @@ const SPEED_BALANCING_FACTOR: f32 = 40.0;
 const AIM_ANGLE_BONUS_BEGIN: f32 = std::f32::consts::FRAC_PI_3;
 const TIMING_THRESHOLD: f32 = 107.0;
 
+const FLASHLIGHT_SMALL_DIST_NERF: f32 = 75.0;
+const FLASHLIGHT_STACK_NERF: f32 = 25.0;
+
 #[derive(Copy, Clone)]
 pub(crate) enum SkillKind {
     Aim,
     Speed,
+    Flashlight,
+}
+
+/// The values of a previous [`DifficultyObject`] that are required by the
+/// flashlight skill.
+#[derive(Clone)]
+pub(crate) struct HistoryObject {
+    end_pos: Pos,
+    jump_dist: f32,
+    strain_time: f32,
+    is_spinner: bool,
+}
+
+impl HistoryObject {
+    pub(crate) fn new(h: &DifficultyObject<'_>) -> Self {
+        Self {
+            end_pos: h.base.end_pos,
+            jump_dist: h.jump_dist,
+            strain_time: h.strain_time,
+            is_spinner: h.base.is_spinner(),
+        }
+    }
 }
 
 impl SkillKind {
-    pub(crate) fn strain_value_of(self, current: &DifficultyObject<'_>) -> f32 {
+    /// `history` contains the previous objects, most recent first.
+    pub(crate) fn strain_value_of(
+        self,
+        current: &DifficultyObject<'_>,
+        history: &VecDeque<HistoryObject>,
+    ) -> f32 {
         match self {
             Self::Aim => {
                 if current.base.is_spinner() {
@@ -90,6 +124,40 @@ impl SkillKind {
                     * (0.95 + speed_bonus * (dist / SINGLE_SPACING_TRESHOLD).powf(3.5))
                     / current.strain_time
             }
+            Self::Flashlight => {
+                if current.base.is_spinner() {
+                    return 0.0;
+                }
+
+                let scaling_factor = current.scaling_factor;
+
+                let mut small_dist_nerf = 1.0;
+                let mut cumulative_strain_time = 0.0;
+                let mut last_strain_time = current.strain_time;
+                let mut result = 0.0;
+
+                for (i, prev) in history.iter().enumerate() {
+                    if !prev.is_spinner {
+                        let jump_dist = (current.base.pos - prev.end_pos).length();
+                        cumulative_strain_time += last_strain_time;
+
+                        // Nerf the value if the object is right next to the previous one
+                        if i == 0 {
+                            small_dist_nerf = (jump_dist / FLASHLIGHT_SMALL_DIST_NERF).min(1.0);
+                        }
+
+                        // Stacked objects are easy to read even when hidden
+                        let stack_nerf =
+                            (prev.jump_dist / scaling_factor / FLASHLIGHT_STACK_NERF).min(1.0);
+
+                        result += stack_nerf * scaling_factor * jump_dist / cumulative_strain_time;
+                    }
+
+                    last_strain_time = prev.strain_time;
+                }
+
+                (small_dist_nerf * result).powi(2)
+            }
         }
     }
 }
@@ -1,16 +1,21 @@
-//! The positional offset of notes created by stack leniency is not considered.
+//! By default, the positional offset of notes created by stack leniency is not considered.
 //! This means the jump distance inbetween notes might be slightly off, resulting in small inaccuracies.
-//! Since calculating these offsets is relatively expensive though, this version is faster than `all_included`.
+//! Since calculating these offsets is relatively expensive though, this is faster than [`all_included`]
+//! which applies stacking.
 
 use super::{DifficultyObject, OsuObject, Skill, SkillKind};
 
-use crate::{Beatmap, GameMods};
+use crate::{
+    model::{beatmap::BeatmapAttributes, mods::Reflection},
+    osu::{convert::convert_objects, difficulty::scaling_factor::ScalingFactor},
+    Beatmap, Difficulty, GameMods,
+};
 
 use rosu_map::section::hit_objects::CurveBuffers;
 
 const OBJECT_RADIUS: f32 = 64.0;
 const SECTION_LEN: f32 = 400.0;
-const DIFFICULTY_MULTIPLIER: f32 = 0.0675;
+pub(crate) const DIFFICULTY_MULTIPLIER: f32 = 0.0675;
 const NORMALIZED_RADIUS: f32 = 52.0;
 
 /// Star calculation for osu!standard maps.
@@ -22,137 +27,297 @@ const NORMALIZED_RADIUS: f32 = 52.0;
 /// processing stack leniency is relatively expensive.
 ///
 /// In case of a partial play, e.g. a fail, one can specify the amount of passed objects.
+///
+/// To consider stack leniency, use [`all_included`] instead.
 pub fn stars(
     map: &Beatmap,
     mods: GameMods,
     passed_objects: Option<u32>,
 ) -> OsuDifficultyAttributes {
-    let map_attributes = map.attributes().mods(mods).build();
+    let mut diff = Difficulty::new().mods(mods);
+
+    if let Some(passed_objects) = passed_objects {
+        diff = diff.passed_objects(passed_objects);
+    }
 
-    let mut diff_attributes = OsuDifficultyAttributes {
-        ar: map_attributes.ar,
-        od: map_attributes.od,
-        cs: map_attributes.cs,
-        beatmap_id: map.beatmap_id,
-        beatmap_creator: map.creator.clone(),
-        ..Default::default()
-    };
+    difficulty(&diff, map)
+}
 
-    let take = passed_objects.unwrap_or(map.hit_objects.len() as u32) as usize;
+/// Star calculation for osu!standard maps.
+///
+/// Both slider paths and stack leniency are considered.
+/// Since processing stack leniency is relatively expensive, this version is
+/// slower than [`stars`] but jump distances on stacked notes are correct.
+///
+/// In case of a partial play, e.g. a fail, one can specify the amount of passed objects.
+pub fn all_included(
+    map: &Beatmap,
+    mods: GameMods,
+    passed_objects: Option<u32>,
+) -> OsuDifficultyAttributes {
+    let mut diff = Difficulty::new().mods(mods).stack_leniency(true);
 
-    if take < 2 {
-        return diff_attributes;
+    if let Some(passed_objects) = passed_objects {
+        diff = diff.passed_objects(passed_objects);
     }
 
-    let section_len = SECTION_LEN * map_attributes.clock_rate as f32;
-    let radius = OBJECT_RADIUS * (1.0 - 0.7 * (map_attributes.cs as f32 - 5.0) / 5.0) / 2.0;
-    let mut scaling_factor = NORMALIZED_RADIUS / radius;
+    difficulty(&diff, map)
+}
 
-    if radius < 30.0 {
-        let small_circle_bonus = (30.0 - radius).min(5.0) / 50.0;
-        scaling_factor *= 1.0 + small_circle_bonus;
+/// Star calculation for osu!standard maps w.r.t. all settings of the given
+/// [`Difficulty`].
+pub(crate) fn difficulty(difficulty: &Difficulty, map: &Beatmap) -> OsuDifficultyAttributes {
+    let DifficultyValues {
+        mut attrs,
+        mut aim,
+        mut speed,
+        mut flashlight,
+        section_len: _,
+    } = DifficultyValues::calculate(difficulty, map);
+
+    if attrs.n_circles + attrs.n_sliders + attrs.n_spinners < 2 {
+        return attrs;
     }
 
-    let mut ticks_buf = Vec::new();
-    let mut curve_bufs = CurveBuffers::default();
-
-    let mut hit_objects = map.hit_objects.iter().take(take).filter_map(|h| {
-        Some(OsuObject::new(
-            h,
-            map,
-            radius,
-            scaling_factor,
-            &mut ticks_buf,
-            &mut diff_attributes,
-            &mut curve_bufs,
-        ))
-    });
-
-    let mut aim = Skill::new(SkillKind::Aim);
-    let mut speed = Skill::new(SkillKind::Speed);
-
-    // First object has no predecessor and thus no strain, handle distinctly
-    let mut current_section_end =
-        (map.hit_objects[0].start_time as f32 / section_len).ceil() * section_len;
-
-    let mut prev_prev = None;
-    let mut prev = hit_objects.next().unwrap();
-    let mut prev_vals = None;
-
-    // Handle second object separately to remove later if-branching
-    let curr = hit_objects.next().unwrap();
-    let h = DifficultyObject::new(
-        &curr,
-        &prev,
-        prev_vals,
-        prev_prev,
-        map_attributes.clock_rate as f32,
-        scaling_factor,
+    let aim_strain = aim.difficulty_value().sqrt() * DIFFICULTY_MULTIPLIER;
+    let speed_strain = speed.difficulty_value().sqrt() * DIFFICULTY_MULTIPLIER;
+    let flashlight_strain = flashlight.difficulty_value().sqrt() * DIFFICULTY_MULTIPLIER;
+
+    eval(
+        &mut attrs,
+        aim_strain,
+        speed_strain,
+        flashlight_strain,
+        &aim,
+        &speed,
     );
 
-    while h.base.time as f32 > current_section_end {
-        current_section_end += section_len;
-    }
+    attrs
+}
 
-    aim.process(&h);
-    speed.process(&h);
-
-    prev_prev = Some(prev);
-    prev_vals = Some((h.jump_dist, h.strain_time));
-    prev = curr;
-
-    // Handle all other objects
-    for curr in hit_objects {
-        let h = DifficultyObject::new(
-            &curr,
-            &prev,
-            prev_vals,
-            prev_prev,
-            map_attributes.clock_rate as f32,
-            scaling_factor,
-        );
+/// The skills after processing all hit objects, before evaluating them.
+pub(crate) struct DifficultyValues {
+    pub(crate) attrs: OsuDifficultyAttributes,
+    pub(crate) aim: Skill,
+    pub(crate) speed: Skill,
+    pub(crate) flashlight: Skill,
+    pub(crate) section_len: f32,
+}
+
+impl DifficultyValues {
+    pub(crate) fn calculate(difficulty: &Difficulty, map: &Beatmap) -> Self {
+        let take = difficulty.get_passed_objects();
 
-        while h.base.time as f32 > current_section_end {
-            aim.save_current_peak();
-            aim.start_new_section_from(current_section_end);
-            speed.save_current_peak();
-            speed.start_new_section_from(current_section_end);
+        let OsuDifficultySetup {
+            attrs,
+            clock_rate,
+            scaling_factor,
+            section_len,
+            osu_objects,
+        } = OsuDifficultySetup::new(map, difficulty, take, |_| {});
+
+        let mut aim = Skill::new(SkillKind::Aim);
+        let mut speed = Skill::new(SkillKind::Speed);
+        let mut flashlight = Skill::new(SkillKind::Flashlight);
+
+        if osu_objects.len() < 2 {
+            return Self {
+                attrs,
+                aim,
+                speed,
+                flashlight,
+                section_len,
+            };
+        }
 
-            current_section_end += section_len;
+        // First object has no predecessor and thus no strain, handle distinctly
+        let mut current_section_end = (osu_objects[0].time / section_len).ceil() * section_len;
+
+        let mut prev_vals = None;
+
+        for (i, curr) in osu_objects.iter().enumerate().skip(1) {
+            let h = DifficultyObject::new(
+                curr,
+                &osu_objects[i - 1],
+                prev_vals,
+                i.checked_sub(2).map(|j| &osu_objects[j]),
+                clock_rate,
+                scaling_factor,
+            );
+
+            // The second object has no strain to save yet
+            if i == 1 {
+                while h.base.time > current_section_end {
+                    current_section_end += section_len;
+                }
+            } else {
+                while h.base.time > current_section_end {
+                    aim.save_current_peak();
+                    aim.start_new_section_from(current_section_end);
+                    speed.save_current_peak();
+                    speed.start_new_section_from(current_section_end);
+                    flashlight.save_current_peak();
+                    flashlight.start_new_section_from(current_section_end);
+
+                    current_section_end += section_len;
+                }
+            }
+
+            aim.process(&h);
+            speed.process(&h);
+            flashlight.process(&h);
+
+            prev_vals = Some((h.jump_dist, h.strain_time));
         }
 
-        aim.process(&h);
-        speed.process(&h);
+        aim.save_current_peak();
+        speed.save_current_peak();
+        flashlight.save_current_peak();
 
-        prev_prev = Some(prev);
-        prev_vals = Some((h.jump_dist, h.strain_time));
-        prev = curr;
+        Self {
+            attrs,
+            aim,
+            speed,
+            flashlight,
+            section_len,
+        }
     }
+}
 
-    aim.save_current_peak();
-    speed.save_current_peak();
+/// Values that are required before processing any [`DifficultyObject`].
+pub(crate) struct OsuDifficultySetup {
+    pub(crate) attrs: OsuDifficultyAttributes,
+    pub(crate) clock_rate: f32,
+    pub(crate) scaling_factor: f32,
+    pub(crate) section_len: f32,
+    pub(crate) osu_objects: Vec<OsuObject>,
+}
 
-    let aim_strain = aim.difficulty_value().sqrt() * DIFFICULTY_MULTIPLIER;
-    let speed_strain = speed.difficulty_value().sqrt() * DIFFICULTY_MULTIPLIER;
+impl OsuDifficultySetup {
+    /// Convert the first `take` hit objects of the map.
+    ///
+    /// `on_object` is called with the total max combo after each converted object.
+    pub(crate) fn new(
+        map: &Beatmap,
+        difficulty: &Difficulty,
+        take: usize,
+        mut on_object: impl FnMut(usize),
+    ) -> Self {
+        let map_attributes = map.attributes().difficulty(difficulty).build();
+
+        let mut attrs = OsuDifficultyAttributes {
+            ar: map_attributes.ar,
+            od: map_attributes.od,
+            hp: map_attributes.hp,
+            cs: map_attributes.cs,
+            beatmap_id: map.beatmap_id,
+            beatmap_creator: map.creator.clone(),
+            ..Default::default()
+        };
+
+        let clock_rate = map_attributes.clock_rate as f32;
+        let section_len = SECTION_LEN * clock_rate;
+        let radius = OBJECT_RADIUS * (1.0 - 0.7 * (map_attributes.cs as f32 - 5.0) / 5.0) / 2.0;
+        let mut scaling_factor = NORMALIZED_RADIUS / radius;
+
+        if radius < 30.0 {
+            let small_circle_bonus = (30.0 - radius).min(5.0) / 50.0;
+            scaling_factor *= 1.0 + small_circle_bonus;
+        }
 
-    let aim_difficult_strain_count = aim.count_difficult_strains();
-    let speed_difficult_strain_count = speed.count_difficult_strains();
+        let mut ticks_buf = Vec::new();
+        let mut curve_bufs = CurveBuffers::default();
+
+        let mut osu_objects: Vec<_> = map
+            .hit_objects
+            .iter()
+            .take(take)
+            .map(|h| {
+                let h = OsuObject::new(
+                    h,
+                    map,
+                    radius,
+                    scaling_factor,
+                    &mut ticks_buf,
+                    &mut attrs,
+                    &mut curve_bufs,
+                );
+
+                on_object(attrs.max_combo);
+
+                h
+            })
+            .collect();
+
+        if difficulty.get_stack_leniency() {
+            apply_stacking(map, &map_attributes, &mut osu_objects);
+        }
 
-    let stars = aim_strain + speed_strain + (aim_strain - speed_strain).abs() / 2.0;
+        Self {
+            attrs,
+            clock_rate,
+            scaling_factor,
+            section_len,
+            osu_objects,
+        }
+    }
+}
+
+/// Offset the positions of stacked notes by using the stacking of the
+/// current algorithm.
+fn apply_stacking(
+    map: &Beatmap,
+    map_attributes: &BeatmapAttributes,
+    osu_objects: &mut [OsuObject],
+) {
+    let scaling_factor = ScalingFactor::new(map_attributes.cs);
+    let time_preempt =
+        f64::from((map_attributes.hit_windows.ar * map_attributes.clock_rate) as f32);
+
+    // Only the stack offsets are of interest so mods and attributes are irrelevant
+    let stacked = convert_objects(
+        map,
+        &scaling_factor,
+        Reflection::None,
+        time_preempt,
+        0,
+        &mut crate::osu::OsuDifficultyAttributes::default(),
+    );
 
-    diff_attributes.stars = stars as f64;
-    diff_attributes.speed_strain = speed_strain as f64;
-    diff_attributes.aim_strain = aim_strain as f64;
-    diff_attributes.aim_difficult_strain_count = aim_difficult_strain_count;
-    diff_attributes.speed_difficult_strain_count = speed_difficult_strain_count;
+    for (h, stacked) in osu_objects.iter_mut().zip(stacked.iter()) {
+        h.pos += stacked.stack_offset;
+        h.end_pos += stacked.stack_offset;
+    }
+}
+
+/// Process the skill ratings and store the results in `attrs`.
+///
+/// The flashlight rating does not contribute to the star rating.
+pub(crate) fn eval(
+    attrs: &mut OsuDifficultyAttributes,
+    aim_strain: f32,
+    speed_strain: f32,
+    flashlight_strain: f32,
+    aim: &Skill,
+    speed: &Skill,
+) {
+    let stars = aim_strain + speed_strain + (aim_strain - speed_strain).abs() / 2.0;
 
-    diff_attributes
+    attrs.stars = stars as f64;
+    attrs.speed_strain = speed_strain as f64;
+    attrs.aim_strain = aim_strain as f64;
+    attrs.flashlight_strain = f64::from(flashlight_strain);
+    attrs.aim_difficult_strain_count = aim.count_difficult_strains();
+    attrs.speed_difficult_strain_count = speed.count_difficult_strains();
 }
 
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct OsuDifficultyAttributes {
     pub aim_strain: f64,
     pub speed_strain: f64,
+    pub flashlight_strain: f64,
     pub ar: f64,
     pub od: f64,
     pub hp: f64,
@@ -168,12 +333,19 @@ pub struct OsuDifficultyAttributes {
     pub beatmap_creator: String,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct OsuPerformanceAttributes {
     pub difficulty: OsuDifficultyAttributes,
     pub pp: f64,
     pub pp_acc: f64,
     pub pp_aim: f64,
     pub pp_speed: f64,
+    /// The value of the flashlight skill.
+    ///
+    /// Not part of [`pp`](Self::pp) until it is calibrated. FL is instead
+    /// rewarded through a length bonus on [`pp_aim`](Self::pp_aim).
+    pub pp_flashlight: f64,
     pub effective_miss_count: f64,
 }
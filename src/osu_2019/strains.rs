@@ -0,0 +1,84 @@
+use crate::{Beatmap, Difficulty, GameMods};
+
+use super::stars::DifficultyValues;
+
+/// The result of calculating the strains of the 2019 algorithm on a osu! map.
+///
+/// Suitable to plot the difficulty of a map over time.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Osu2019Strains {
+    /// Strain peaks of the aim skill.
+    pub aim: Vec<f64>,
+    /// Strain peaks of the speed skill.
+    pub speed: Vec<f64>,
+    /// Strain peaks of the flashlight skill.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub flashlight: Vec<f64>,
+    /// Time between two strains in ms.
+    ///
+    /// Unlike [`OsuStrains::SECTION_LEN`], this value is not adjusted by the
+    /// clock rate, i.e. it refers to the map's original timeline.
+    ///
+    /// [`OsuStrains::SECTION_LEN`]: crate::osu::OsuStrains::SECTION_LEN
+    pub section_len: f64,
+}
+
+/// Perform the star calculation of the 2019 algorithm but instead of
+/// evaluating the skill strains, return them as is.
+///
+/// Suitable to plot the difficulty of a map over time.
+pub fn strains(map: &Beatmap, mods: impl Into<GameMods>) -> Osu2019Strains {
+    strains_with_difficulty(&Difficulty::new().mods(mods), map)
+}
+
+pub(crate) fn strains_with_difficulty(difficulty: &Difficulty, map: &Beatmap) -> Osu2019Strains {
+    let DifficultyValues {
+        attrs: _,
+        aim,
+        speed,
+        flashlight,
+        section_len,
+    } = DifficultyValues::calculate(difficulty, map);
+
+    let peaks = |strain_peaks: Vec<f32>| strain_peaks.into_iter().map(f64::from).collect();
+
+    Osu2019Strains {
+        aim: peaks(aim.strain_peaks),
+        speed: peaks(speed.strain_peaks),
+        flashlight: peaks(flashlight.strain_peaks),
+        section_len: f64::from(section_len),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::osu_2019::stars::{stars, DIFFICULTY_MULTIPLIER};
+
+    use super::*;
+
+    #[test]
+    fn peaks_match_stars() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+        let mods = 128 + 64; // RXDT
+
+        let strains = strains(&map, mods);
+        let attrs = stars(&map, mods.into(), None);
+
+        assert!((strains.section_len - 600.0).abs() < f64::EPSILON);
+
+        let evaluate = |peaks: &[f64]| {
+            let mut peaks: Vec<_> = peaks.iter().map(|&peak| peak as f32).collect();
+            peaks.sort_unstable_by(|a, b| b.total_cmp(a));
+
+            let (difficulty, _) = peaks.into_iter().fold((0.0, 1.0), |(sum, weight), peak| {
+                (sum + peak * weight, weight * 0.9)
+            });
+
+            f64::from(f32::sqrt(difficulty) * DIFFICULTY_MULTIPLIER)
+        };
+
+        assert!((evaluate(&strains.aim) - attrs.aim_strain).abs() < 1e-6);
+        assert!((evaluate(&strains.speed) - attrs.speed_strain).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,264 @@
+use std::cmp;
+
+use rosu_map::section::general::GameMode;
+
+use crate::any::PerformanceAttributes;
+
+/// Weight factor of each score w.r.t. its position in the top plays.
+const WEIGHT_FACTOR: f64 = 0.95;
+
+/// The amount of ranked scores after which the bonus pp no longer increase.
+const MAX_BONUS_SCORES: usize = 1000;
+
+/// Aggregation of scores into the total pp of a profile.
+///
+/// Scores are weighted by `0.95^i` where `i` is the index of the score in the
+/// top plays, starting at `0`. On top of that, bonus pp are awarded based on
+/// the amount of ranked scores.
+///
+/// Only scores of the specified [`GameMode`] are considered so that a
+/// profile can be created for each leaderboard separately.
+///
+/// # Example
+///
+/// ```
+/// use akatsuki_pp::{model::mode::GameMode, profile::ProfilePerformance};
+///
+/// let profile = ProfilePerformance::new(GameMode::Osu)
+///     .scores([(GameMode::Osu, 300.0), (GameMode::Osu, 250.0)])
+///     .scores([(GameMode::Taiko, 400.0)]); // ignored
+///
+/// let attrs = profile.calculate();
+/// assert!((attrs.weighted_pp - (300.0 + 250.0 * 0.95)).abs() < f64::EPSILON);
+///
+/// let gain = profile.pp_gain(280.0);
+/// println!("A 280pp score would add {gain:.2}pp");
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[must_use]
+pub struct ProfilePerformance {
+    mode: GameMode,
+    /// Sorted in descending order.
+    pps: Vec<f64>,
+    max_scores: usize,
+    ranked_scores: Option<usize>,
+}
+
+impl ProfilePerformance {
+    /// The default amount of top scores that are weighted.
+    pub const DEFAULT_MAX_SCORES: usize = 100;
+
+    /// Create a new profile for the given mode without any scores.
+    pub const fn new(mode: GameMode) -> Self {
+        Self {
+            mode,
+            pps: Vec::new(),
+            max_scores: Self::DEFAULT_MAX_SCORES,
+            ranked_scores: None,
+        }
+    }
+
+    /// Add scores to the profile.
+    ///
+    /// Scores of a different mode than the profile's mode are ignored.
+    pub fn scores<I>(mut self, scores: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<ProfileScore>,
+    {
+        let mode = self.mode;
+
+        let pps = scores
+            .into_iter()
+            .map(Into::into)
+            .filter(|score| score.mode == mode)
+            .map(|score| score.pp);
+
+        self.pps.extend(pps);
+        self.pps.sort_unstable_by(|a, b| b.total_cmp(a));
+
+        self
+    }
+
+    /// Specify the amount of top scores that are weighted.
+    ///
+    /// Defaults to [`ProfilePerformance::DEFAULT_MAX_SCORES`].
+    pub const fn max_scores(mut self, max_scores: usize) -> Self {
+        self.max_scores = max_scores;
+
+        self
+    }
+
+    /// Specify the amount of ranked scores which is used for the bonus pp.
+    ///
+    /// Defaults to the amount of added scores.
+    pub const fn ranked_scores(mut self, ranked_scores: usize) -> Self {
+        self.ranked_scores = Some(ranked_scores);
+
+        self
+    }
+
+    /// Calculate the weighted, bonus, and total pp of the profile.
+    pub fn calculate(&self) -> ProfileAttributes {
+        let weighted_pp = weighted_pp(self.pps.iter().copied().take(self.max_scores));
+        let bonus_pp = bonus_pp(self.get_ranked_scores());
+
+        ProfileAttributes {
+            pp: weighted_pp + bonus_pp,
+            weighted_pp,
+            bonus_pp,
+        }
+    }
+
+    /// The amount of total pp that the profile would gain through a new score
+    /// with the given pp value.
+    ///
+    /// The score is assumed to be set on a map that has no previous score so
+    /// the amount of ranked scores increases by one.
+    pub fn pp_gain(&self, pp: f64) -> f64 {
+        let idx = self.pps.partition_point(|&curr| curr >= pp);
+
+        let before = &self.pps[..idx];
+        let after = &self.pps[idx..];
+
+        let weighted_pp = weighted_pp(
+            before
+                .iter()
+                .copied()
+                .chain([pp])
+                .chain(after.iter().copied())
+                .take(self.max_scores),
+        );
+
+        let bonus_pp = bonus_pp(self.get_ranked_scores() + 1);
+
+        weighted_pp + bonus_pp - self.calculate().pp
+    }
+
+    fn get_ranked_scores(&self) -> usize {
+        self.ranked_scores.unwrap_or(self.pps.len())
+    }
+}
+
+/// A score's pp value alongside its mode.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ProfileScore {
+    /// The mode of the score.
+    pub mode: GameMode,
+    /// The pp value of the score.
+    pub pp: f64,
+}
+
+impl From<(GameMode, f64)> for ProfileScore {
+    fn from((mode, pp): (GameMode, f64)) -> Self {
+        Self { mode, pp }
+    }
+}
+
+impl From<&PerformanceAttributes> for ProfileScore {
+    fn from(attrs: &PerformanceAttributes) -> Self {
+        Self {
+            mode: attrs.mode(),
+            pp: attrs.pp(),
+        }
+    }
+}
+
+impl From<PerformanceAttributes> for ProfileScore {
+    fn from(attrs: PerformanceAttributes) -> Self {
+        Self::from(&attrs)
+    }
+}
+
+/// The result of aggregating the scores of a profile.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProfileAttributes {
+    /// The total pp i.e. the sum of weighted pp and bonus pp.
+    pub pp: f64,
+    /// The sum of all weighted top scores.
+    pub weighted_pp: f64,
+    /// The bonus pp based on the amount of ranked scores.
+    pub bonus_pp: f64,
+}
+
+/// Sum of the given pp values weighted by their position.
+///
+/// The values must be sorted in descending order.
+fn weighted_pp(pps: impl Iterator<Item = f64>) -> f64 {
+    pps.zip(0..).map(|(pp, i)| pp * WEIGHT_FACTOR.powi(i)).sum()
+}
+
+/// Bonus pp for the given amount of ranked scores.
+fn bonus_pp(ranked_scores: usize) -> f64 {
+    let n = cmp::min(ranked_scores, MAX_BONUS_SCORES) as i32;
+
+    (417.0 - 1.0 / 3.0) * (1.0 - 0.995_f64.powi(n))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_eq_float(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "{a} != {b}");
+    }
+
+    #[test]
+    fn empty() {
+        let profile = ProfilePerformance::new(GameMode::Osu);
+
+        assert_eq!(profile.calculate(), ProfileAttributes::default());
+        assert_eq_float(profile.pp_gain(100.0), 100.0 + bonus_pp(1));
+    }
+
+    #[test]
+    fn weighting() {
+        let profile = ProfilePerformance::new(GameMode::Mania)
+            .scores([(GameMode::Mania, 100.0), (GameMode::Mania, 300.0)])
+            .scores([(GameMode::Osu, 1000.0)])
+            .scores([(GameMode::Mania, 200.0)]);
+
+        let attrs = profile.calculate();
+
+        assert_eq_float(
+            attrs.weighted_pp,
+            300.0 + 200.0 * 0.95 + 100.0 * 0.95 * 0.95,
+        );
+        assert_eq_float(attrs.bonus_pp, bonus_pp(3));
+        assert_eq_float(attrs.pp, attrs.weighted_pp + attrs.bonus_pp);
+    }
+
+    #[test]
+    fn max_scores() {
+        let pps = (1..=200).map(|pp| (GameMode::Taiko, f64::from(pp)));
+
+        let profile = ProfilePerformance::new(GameMode::Taiko)
+            .scores(pps)
+            .max_scores(2)
+            .ranked_scores(5000);
+
+        let attrs = profile.calculate();
+
+        assert_eq_float(attrs.weighted_pp, 200.0 + 199.0 * 0.95);
+        assert_eq_float(attrs.bonus_pp, bonus_pp(MAX_BONUS_SCORES));
+
+        // Not good enough to be weighted; bonus pp are already maxed
+        assert_eq_float(profile.pp_gain(150.0), 0.0);
+    }
+
+    #[test]
+    fn pp_gain() {
+        let profile = ProfilePerformance::new(GameMode::Catch)
+            .scores([(GameMode::Catch, 300.0), (GameMode::Catch, 100.0)]);
+
+        let expected = ProfilePerformance::new(GameMode::Catch)
+            .scores([(GameMode::Catch, 300.0), (GameMode::Catch, 100.0)])
+            .scores([(GameMode::Catch, 200.0)])
+            .calculate()
+            .pp
+            - profile.calculate().pp;
+
+        assert_eq_float(profile.pp_gain(200.0), expected);
+    }
+}
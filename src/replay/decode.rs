@@ -0,0 +1,295 @@
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+    io,
+};
+
+use rosu_map::section::general::GameMode;
+
+use super::{LifeBarFrame, Replay, ReplayFrame};
+
+/// The first version that stores the online score id as 64-bit integer.
+const LONG_SCORE_ID_VERSION: u32 = 20_140_721;
+
+/// Bitflag of the target practice mod.
+const TARGET_PRACTICE: u32 = 1 << 23;
+
+/// Frame delta that indicates the frame contains the RNG seed.
+const SEED_FRAME_DELTA: i32 = -12345;
+
+impl Replay {
+    pub(super) fn decode(bytes: &[u8]) -> Result<Self, ReplayError> {
+        let mut reader = Reader::new(bytes);
+
+        let mode = match reader.u8()? {
+            0 => GameMode::Osu,
+            1 => GameMode::Taiko,
+            2 => GameMode::Catch,
+            3 => GameMode::Mania,
+            mode => return Err(ReplayError::Mode { mode }),
+        };
+
+        let game_version = reader.u32()?;
+        let beatmap_md5 = reader.string()?;
+        let player_name = reader.string()?;
+        let replay_md5 = reader.string()?;
+        let n300 = reader.u16()?;
+        let n100 = reader.u16()?;
+        let n50 = reader.u16()?;
+        let n_geki = reader.u16()?;
+        let n_katu = reader.u16()?;
+        let misses = reader.u16()?;
+        let score = reader.u32()?;
+        let max_combo = reader.u16()?;
+        let perfect = reader.bool()?;
+        let mods = reader.u32()?;
+        let life_bar = parse_life_bar(&reader.string()?)?;
+        let timestamp = reader.i64()?;
+
+        let compressed_len = reader.u32()? as usize;
+        let compressed = reader.bytes(compressed_len)?;
+        let (frames, rng_seed) = decompress_frames(compressed)?;
+
+        let online_id = if game_version >= LONG_SCORE_ID_VERSION {
+            reader.i64()?
+        } else {
+            i64::from(reader.i32()?)
+        };
+
+        let target_practice_accuracy = if mods & TARGET_PRACTICE > 0 {
+            Some(reader.f64()?)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            mode,
+            game_version,
+            beatmap_md5,
+            player_name,
+            replay_md5,
+            n300,
+            n100,
+            n50,
+            n_geki,
+            n_katu,
+            misses,
+            score,
+            max_combo,
+            perfect,
+            mods,
+            life_bar,
+            timestamp,
+            frames,
+            rng_seed,
+            online_id,
+            target_practice_accuracy,
+        })
+    }
+}
+
+/// Parse comma-separated `time|life` pairs.
+fn parse_life_bar(s: &str) -> Result<Vec<LifeBarFrame>, ReplayError> {
+    s.split(',')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (time, life) = pair.split_once('|').ok_or(ReplayError::LifeBar)?;
+
+            Ok(LifeBarFrame {
+                time: time.parse().map_err(|_| ReplayError::LifeBar)?,
+                life: life.parse().map_err(|_| ReplayError::LifeBar)?,
+            })
+        })
+        .collect()
+}
+
+/// Decompress the LZMA stream and parse its comma-separated
+/// `delta|x|y|keys` frames.
+fn decompress_frames(compressed: &[u8]) -> Result<(Vec<ReplayFrame>, Option<i32>), ReplayError> {
+    if compressed.is_empty() {
+        return Ok((Vec::new(), None));
+    }
+
+    let mut decompressed = Vec::new();
+
+    lzma_rs::lzma_decompress(&mut io::BufReader::new(compressed), &mut decompressed)
+        .map_err(|_| ReplayError::Lzma)?;
+
+    let text = std::str::from_utf8(&decompressed).map_err(|_| ReplayError::Utf8)?;
+
+    let mut frames = Vec::new();
+    let mut rng_seed = None;
+    let mut time = 0_i32;
+
+    for frame in text.split(',').filter(|frame| !frame.is_empty()) {
+        let mut split = frame.split('|');
+
+        let mut next = || split.next().ok_or(ReplayError::Frame);
+
+        let delta: i32 = next()?.parse().map_err(|_| ReplayError::Frame)?;
+        let x: f32 = next()?.parse().map_err(|_| ReplayError::Frame)?;
+        let y: f32 = next()?.parse().map_err(|_| ReplayError::Frame)?;
+        let keys: i32 = next()?.parse().map_err(|_| ReplayError::Frame)?;
+
+        if delta == SEED_FRAME_DELTA {
+            rng_seed = Some(keys);
+
+            continue;
+        }
+
+        time = time.checked_add(delta).ok_or(ReplayError::Frame)?;
+
+        frames.push(ReplayFrame {
+            time,
+            delta,
+            x,
+            y,
+            keys: keys as u32,
+        });
+    }
+
+    Ok((frames, rng_seed))
+}
+
+/// Error when decoding a replay.
+#[derive(Debug)]
+pub enum ReplayError {
+    /// Failed to read the replay file.
+    Io(io::Error),
+    /// Invalid mode
+    Mode { mode: u8 },
+    /// A string did not start with the expected prefix byte.
+    StringPrefix { byte: u8 },
+    /// The length of a string is too large.
+    StringLength,
+    /// The bytes ended unexpectedly.
+    UnexpectedEof,
+    /// Invalid UTF-8 in a string or the frame data.
+    Utf8,
+    /// Failed to decompress the frame data.
+    Lzma,
+    /// Invalid frame in the frame data.
+    Frame,
+    /// Invalid life bar frame.
+    LifeBar,
+}
+
+impl Error for ReplayError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ReplayError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl Display for ReplayError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            ReplayError::Io(_) => f.write_str("Failed to read replay"),
+            ReplayError::Mode { mode } => write!(f, "Invalid mode {mode}"),
+            ReplayError::StringPrefix { byte } => write!(f, "Invalid string prefix {byte}"),
+            ReplayError::StringLength => f.write_str("Invalid string length"),
+            ReplayError::UnexpectedEof => f.write_str("Unexpected end of bytes"),
+            ReplayError::Utf8 => f.write_str("Invalid UTF-8"),
+            ReplayError::Lzma => f.write_str("Failed to decompress frame data"),
+            ReplayError::Frame => f.write_str("Invalid frame data"),
+            ReplayError::LifeBar => f.write_str("Invalid life bar data"),
+        }
+    }
+}
+
+impl From<io::Error> for ReplayError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    const fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    const fn bytes(&mut self, len: usize) -> Result<&'a [u8], ReplayError> {
+        if self.bytes.len() < len {
+            return Err(ReplayError::UnexpectedEof);
+        }
+
+        let (bytes, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+
+        Ok(bytes)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], ReplayError> {
+        self.bytes(N)
+            .map(|bytes| bytes.try_into().expect("length was checked"))
+    }
+
+    fn u8(&mut self) -> Result<u8, ReplayError> {
+        self.array().map(|[n]| n)
+    }
+
+    fn bool(&mut self) -> Result<bool, ReplayError> {
+        self.u8().map(|n| n != 0)
+    }
+
+    fn u16(&mut self) -> Result<u16, ReplayError> {
+        self.array().map(u16::from_le_bytes)
+    }
+
+    fn u32(&mut self) -> Result<u32, ReplayError> {
+        self.array().map(u32::from_le_bytes)
+    }
+
+    fn i32(&mut self) -> Result<i32, ReplayError> {
+        self.array().map(i32::from_le_bytes)
+    }
+
+    fn i64(&mut self) -> Result<i64, ReplayError> {
+        self.array().map(i64::from_le_bytes)
+    }
+
+    fn f64(&mut self) -> Result<f64, ReplayError> {
+        self.array().map(f64::from_le_bytes)
+    }
+
+    /// Either a single `0x00` byte for an empty string or `0x0b` followed by
+    /// the ULEB128 encoded length and the UTF-8 bytes.
+    fn string(&mut self) -> Result<String, ReplayError> {
+        match self.u8()? {
+            0x00 => Ok(String::new()),
+            0x0b => {
+                let len = self.uleb128()?;
+                let bytes = self.bytes(len)?;
+
+                String::from_utf8(bytes.to_owned()).map_err(|_| ReplayError::Utf8)
+            }
+            byte => Err(ReplayError::StringPrefix { byte }),
+        }
+    }
+
+    fn uleb128(&mut self) -> Result<usize, ReplayError> {
+        let mut value = 0;
+        let mut shift = 0;
+
+        loop {
+            let byte = self.u8()?;
+            value |= usize::from(byte & 0x7f) << shift;
+
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+
+            shift += 7;
+
+            if shift >= usize::BITS {
+                return Err(ReplayError::StringLength);
+            }
+        }
+    }
+}
@@ -0,0 +1,374 @@
+use std::{fs, path::Path};
+
+use rosu_map::section::general::GameMode;
+
+use crate::{
+    any::{IntoPerformance, ScoreState},
+    model::mode::ConvertError,
+    osu::OsuScoreState,
+    Beatmap, GameMods, Performance,
+};
+
+pub use self::{
+    decode::ReplayError,
+    osu::{OsuHitResult, OsuObjectJudgement, OsuReplayJudgements},
+};
+
+mod decode;
+mod osu;
+
+/// A decoded osu!stable replay i.e. the content of an `.osr` file.
+///
+/// # Example
+///
+/// ```no_run
+/// use akatsuki_pp::{osu_2019::OsuPP, replay::Replay, Beatmap};
+///
+/// let replay = Replay::from_path("./replay.osr").unwrap();
+/// let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+///
+/// let attrs = replay.performance(&map).calculate();
+/// println!("PP: {}", attrs.pp());
+///
+/// let attrs_2019 = OsuPP::from_map(&map)
+///     .mods(replay.game_mods())
+///     .state(replay.osu_score_state())
+///     .calculate();
+/// println!("PP (2019): {}", attrs_2019.pp);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Replay {
+    /// The mode of the replay.
+    pub mode: GameMode,
+    /// The version of the game when the replay was created e.g. `20240101`.
+    pub game_version: u32,
+    /// MD5 hash of the beatmap.
+    pub beatmap_md5: String,
+    /// The name of the player.
+    pub player_name: String,
+    /// MD5 hash of the replay.
+    pub replay_md5: String,
+    /// Amount of 300s.
+    pub n300: u16,
+    /// Amount of 100s (150s in osu!taiko, droplets in osu!catch).
+    pub n100: u16,
+    /// Amount of 50s (tiny droplets in osu!catch).
+    pub n50: u16,
+    /// Amount of gekis (n320 in osu!mania).
+    pub n_geki: u16,
+    /// Amount of katus (n200 in osu!mania, tiny droplet misses in osu!catch).
+    pub n_katu: u16,
+    /// Amount of misses.
+    pub misses: u16,
+    /// The total score.
+    pub score: u32,
+    /// The max combo of the score.
+    pub max_combo: u16,
+    /// Whether the score is a perfect combo.
+    pub perfect: bool,
+    /// The legacy bitflags of the mods.
+    pub mods: u32,
+    /// The life bar over the course of the replay.
+    pub life_bar: Vec<LifeBarFrame>,
+    /// The time at which the replay was created in Windows ticks.
+    pub timestamp: i64,
+    /// The input frames of the replay.
+    pub frames: Vec<ReplayFrame>,
+    /// The seed of the random number generator, if any.
+    pub rng_seed: Option<i32>,
+    /// The online id of the score, `0` if not submitted.
+    pub online_id: i64,
+    /// The total accuracy of all hits if the target practice mod is enabled.
+    pub target_practice_accuracy: Option<f64>,
+}
+
+impl Replay {
+    /// Decode a replay from a path to an `.osr` file.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, ReplayError> {
+        Self::from_bytes(&fs::read(path)?)
+    }
+
+    /// Decode a replay from the content of an `.osr` file.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ReplayError> {
+        Self::decode(bytes)
+    }
+
+    /// The replay's mods.
+    pub fn game_mods(&self) -> GameMods {
+        self.mods.into()
+    }
+
+    /// The replay's judgements and combo as [`ScoreState`].
+    pub fn score_state(&self) -> ScoreState {
+        ScoreState {
+            max_combo: u32::from(self.max_combo),
+            osu_large_tick_hits: 0,
+            slider_end_hits: 0,
+            n_geki: u32::from(self.n_geki),
+            n_katu: u32::from(self.n_katu),
+            n300: u32::from(self.n300),
+            n100: u32::from(self.n100),
+            n50: u32::from(self.n50),
+            misses: u32::from(self.misses),
+        }
+    }
+
+    /// The replay's judgements and combo as [`OsuScoreState`].
+    ///
+    /// Since osu!stable does not track slider ticks and ends separately,
+    /// `large_tick_hits` and `slider_end_hits` are `0`.
+    pub fn osu_score_state(&self) -> OsuScoreState {
+        self.score_state().into()
+    }
+
+    /// Create a [`Performance`] calculator for the replay's mode, mods, and
+    /// score state.
+    ///
+    /// Since replays originate from osu!stable, the calculation won't
+    /// consider osu!lazer specifics.
+    pub fn performance<'a>(&self, map_or_attrs: impl IntoPerformance<'a>) -> Performance<'a> {
+        map_or_attrs
+            .into_performance()
+            .mode_or_ignore(self.mode)
+            .lazer(false)
+            .mods(self.game_mods())
+            .state(self.score_state())
+    }
+
+    /// Re-simulate the replay's frames on the given osu!standard map to
+    /// judge each hit object independently of the replay's header.
+    ///
+    /// Hit objects are judged like on osu!stable, considering stacking,
+    /// slider paths, the hit windows, notelock, and the `Relax`, `Autopilot`,
+    /// and `SpunOut` mods.
+    ///
+    /// Returns an error if the replay is not of mode osu!standard or the map
+    /// cannot be converted to osu!standard.
+    pub fn osu_judgements(&self, map: &Beatmap) -> Result<OsuReplayJudgements, ConvertError> {
+        osu::simulate(self, map)
+    }
+}
+
+/// A single input frame of a [`Replay`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ReplayFrame {
+    /// The time of the frame in ms since the start of the replay.
+    pub time: i32,
+    /// The time in ms since the previous frame.
+    pub delta: i32,
+    /// The x-coordinate of the cursor.
+    ///
+    /// For osu!mania, this contains the bitflags of the pressed keys instead.
+    pub x: f32,
+    /// The y-coordinate of the cursor.
+    pub y: f32,
+    /// Bitflags of the pressed keys.
+    ///
+    /// For osu!standard, see [`ReplayFrame::M1`] and co.
+    pub keys: u32,
+}
+
+impl ReplayFrame {
+    /// Bitflag of the left mouse button.
+    pub const M1: u32 = 1 << 0;
+    /// Bitflag of the right mouse button.
+    pub const M2: u32 = 1 << 1;
+    /// Bitflag of the first key; always combined with [`ReplayFrame::M1`].
+    pub const K1: u32 = 1 << 2;
+    /// Bitflag of the second key; always combined with [`ReplayFrame::M2`].
+    pub const K2: u32 = 1 << 3;
+    /// Bitflag of smoke.
+    pub const SMOKE: u32 = 1 << 4;
+
+    /// Whether any of the osu!standard hit keys is pressed.
+    pub const fn is_pressing(&self) -> bool {
+        self.keys & (Self::M1 | Self::M2) > 0
+    }
+}
+
+/// The amount of life at some point of a [`Replay`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct LifeBarFrame {
+    /// The time in ms.
+    pub time: i32,
+    /// The amount of life between `0.0` and `1.0`.
+    pub life: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::BufReader;
+
+    use crate::{osu_2019::OsuPP, Beatmap};
+
+    use super::*;
+
+    fn write_string(bytes: &mut Vec<u8>, s: &str) {
+        bytes.push(0x0b);
+
+        let mut len = s.len();
+
+        loop {
+            let mut byte = (len & 0x7f) as u8;
+            len >>= 7;
+
+            if len > 0 {
+                byte |= 0x80;
+            }
+
+            bytes.push(byte);
+
+            if len == 0 {
+                break;
+            }
+        }
+
+        bytes.extend_from_slice(s.as_bytes());
+    }
+
+    fn encode(mods: u32, frames: &str) -> Vec<u8> {
+        let mut bytes = vec![0]; // osu!standard
+        bytes.extend_from_slice(&20_240_101_u32.to_le_bytes());
+        write_string(&mut bytes, "f1b0a2bd8d4f3fb1b7a2fd7ae3e2bc6a");
+        write_string(&mut bytes, &"player".repeat(30));
+        bytes.push(0x00);
+
+        for n in [580_u16, 15, 2, 100, 10, 4] {
+            bytes.extend_from_slice(&n.to_le_bytes());
+        }
+
+        bytes.extend_from_slice(&12_345_678_u32.to_le_bytes());
+        bytes.extend_from_slice(&800_u16.to_le_bytes());
+        bytes.push(0);
+        bytes.extend_from_slice(&mods.to_le_bytes());
+        write_string(&mut bytes, "0|1,5000|0.75,");
+        bytes.extend_from_slice(&638_000_000_000_000_000_i64.to_le_bytes());
+
+        let mut compressed = Vec::new();
+        lzma_rs::lzma_compress(&mut BufReader::new(frames.as_bytes()), &mut compressed).unwrap();
+        bytes.extend_from_slice(&(compressed.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&compressed);
+
+        bytes.extend_from_slice(&4_000_000_000_i64.to_le_bytes());
+
+        bytes
+    }
+
+    #[test]
+    fn decode() {
+        let frames = "0|256|-500|0,-1|256|-500|0,16|100.5|200|5,17|110|210|0,-12345|0|0|42,";
+        let bytes = encode(8 + 64, frames);
+        let replay = Replay::from_bytes(&bytes).unwrap();
+
+        assert_eq!(replay.mode, GameMode::Osu);
+        assert_eq!(replay.game_version, 20_240_101);
+        assert_eq!(replay.player_name, "player".repeat(30));
+        assert_eq!(replay.replay_md5, "");
+        assert_eq!((replay.n300, replay.n100, replay.n50), (580, 15, 2));
+        assert_eq!((replay.n_geki, replay.n_katu, replay.misses), (100, 10, 4));
+        assert_eq!(replay.max_combo, 800);
+        assert_eq!(replay.game_mods(), GameMods::from(8 + 64));
+        assert_eq!(replay.online_id, 4_000_000_000);
+        assert_eq!(replay.rng_seed, Some(42));
+        assert_eq!(replay.target_practice_accuracy, None);
+
+        assert_eq!(
+            replay.life_bar,
+            [
+                LifeBarFrame { time: 0, life: 1.0 },
+                LifeBarFrame {
+                    time: 5000,
+                    life: 0.75
+                }
+            ]
+        );
+
+        assert_eq!(replay.frames.len(), 4);
+        assert_eq!(
+            replay.frames[2],
+            ReplayFrame {
+                time: 15,
+                delta: 16,
+                x: 100.5,
+                y: 200.0,
+                keys: ReplayFrame::M1 | ReplayFrame::K1,
+            }
+        );
+        assert!(replay.frames[2].is_pressing());
+        assert!(!replay.frames[3].is_pressing());
+    }
+
+    #[test]
+    fn decode_errors() {
+        let bytes = encode(0, "0|0|0|0,");
+
+        assert!(matches!(
+            Replay::from_bytes(&bytes[..bytes.len() - 1]),
+            Err(ReplayError::UnexpectedEof)
+        ));
+
+        let mut invalid_mode = bytes.clone();
+        invalid_mode[0] = 4;
+
+        assert!(matches!(
+            Replay::from_bytes(&invalid_mode),
+            Err(ReplayError::Mode { mode: 4 })
+        ));
+
+        assert!(matches!(
+            Replay::from_bytes(&encode(0, "0|0|0,")),
+            Err(ReplayError::Frame)
+        ));
+
+        let overflow = format!("{max}|0|0|0,{max}|0|0|0,", max = i32::MAX);
+
+        assert!(matches!(
+            Replay::from_bytes(&encode(0, &overflow)),
+            Err(ReplayError::Frame)
+        ));
+    }
+
+    #[test]
+    fn performance() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+        let replay = Replay::from_bytes(&encode(8, "")).unwrap();
+
+        let expected_state = OsuScoreState {
+            max_combo: 800,
+            large_tick_hits: 0,
+            slider_end_hits: 0,
+            n300: 580,
+            n100: 15,
+            n50: 2,
+            misses: 4,
+        };
+
+        assert_eq!(replay.game_mods(), GameMods::from(8));
+        assert_eq!(replay.osu_score_state(), expected_state);
+
+        let expected = Performance::new(&map)
+            .lazer(false)
+            .mods(8)
+            .combo(800)
+            .n_geki(100)
+            .n_katu(10)
+            .n300(580)
+            .n100(15)
+            .n50(2)
+            .misses(4)
+            .calculate();
+
+        assert_eq!(replay.performance(&map).calculate(), expected);
+
+        let expected = OsuPP::from_map(&map)
+            .mods(8)
+            .state(expected_state)
+            .calculate();
+        let actual = OsuPP::from_map(&map)
+            .mods(replay.game_mods())
+            .state(replay.osu_score_state())
+            .calculate();
+
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,930 @@
+use std::f64::consts::{PI, TAU};
+
+use rosu_map::{
+    section::{
+        general::GameMode,
+        hit_objects::{BorrowedCurve, CurveBuffers},
+    },
+    util::Pos,
+};
+
+use crate::{
+    model::{
+        hit_object::HitObjectKind,
+        mode::ConvertError,
+        mods::{GameMods, Reflection},
+    },
+    osu::{
+        convert::convert_objects,
+        difficulty::scaling_factor::ScalingFactor,
+        object::{NestedSliderObjectKind, OsuObject, OsuObjectKind, OsuSlider},
+        OsuDifficultyAttributes, OsuScoreState,
+    },
+    Beatmap,
+};
+
+use super::{Replay, ReplayFrame};
+
+/// Clicking a circle earlier than this many ms does not judge it.
+const MISS_WINDOW: f64 = 400.0;
+
+/// Relax only clicks objects that start within this many ms.
+const RELAX_LENIENCY: f64 = 3.0;
+
+/// Radius of the follow circle while tracking, relative to the object radius.
+const FOLLOW_AREA: f64 = 2.4;
+
+/// Slider tails are checked this many ms before the slider's end.
+const TAIL_LENIENCY: f64 = -36.0;
+
+/// Maximum spinner rotations per ms of real time i.e. 477 rpm.
+const MAX_SPINS_PER_MS: f64 = 477.0 / 60_000.0;
+
+const SPINNER_CENTER: Pos = Pos::new(256.0, 192.0);
+
+const KEYS: u32 = ReplayFrame::M1 | ReplayFrame::M2;
+
+/// The judgement of an osu!standard hit object.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum OsuHitResult {
+    /// A 300.
+    Great,
+    /// A 100.
+    Ok,
+    /// A 50.
+    Meh,
+    /// A miss.
+    Miss,
+}
+
+/// The re-simulated judgement of a single hit object.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OsuObjectJudgement {
+    /// The start time of the hit object in ms.
+    pub start_time: f64,
+    /// The judgement of the hit object.
+    pub result: OsuHitResult,
+    /// The time difference between the click and the start time in ms.
+    ///
+    /// `None` for spinners and if the circle or slider head was not hit.
+    pub hit_offset: Option<f64>,
+}
+
+/// The outcome of re-simulating a [`Replay`] on an osu!standard map.
+///
+/// Created through [`Replay::osu_judgements`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct OsuReplayJudgements {
+    /// The score state that results from the judgements.
+    ///
+    /// Besides the hitresults and combo, it also includes the hit slider
+    /// ticks and repeats as `large_tick_hits`, and the hit slider tails as
+    /// `slider_end_hits`.
+    pub state: OsuScoreState,
+    /// The judgements of all hit objects in order.
+    pub objects: Vec<OsuObjectJudgement>,
+}
+
+impl OsuReplayJudgements {
+    /// The hit offsets of all hit circles and slider heads in ms.
+    pub fn hit_offsets(&self) -> impl Iterator<Item = f64> + '_ {
+        self.objects
+            .iter()
+            .filter_map(|judgement| judgement.hit_offset)
+    }
+}
+
+pub(super) fn simulate(
+    replay: &Replay,
+    map: &Beatmap,
+) -> Result<OsuReplayJudgements, ConvertError> {
+    if replay.mode != GameMode::Osu {
+        return Err(ConvertError::Convert {
+            from: replay.mode,
+            to: GameMode::Osu,
+        });
+    }
+
+    let mods = replay.game_mods();
+    let map = map.convert_ref(GameMode::Osu, &mods)?;
+    let setup = Setup::new(&map, &mods);
+
+    let objects = convert_objects(
+        &map,
+        &setup.scaling_factor,
+        setup.reflection,
+        setup.time_preempt,
+        0,
+        &mut OsuDifficultyAttributes::default(),
+    );
+
+    let simulation = Simulation {
+        frames: &replay.frames,
+        objects: &objects,
+        setup: &setup,
+        relax: mods.rx(),
+        autopilot: mods.ap(),
+        auto_spin: mods.so() || mods.ap(),
+    };
+
+    Ok(simulation.run(&map))
+}
+
+/// Mod-dependent values that are shared across all hit objects.
+struct Setup {
+    scaling_factor: ScalingFactor,
+    reflection: Reflection,
+    time_preempt: f64,
+    clock_rate: f64,
+    od: f64,
+    great: f64,
+    ok: f64,
+    meh: f64,
+}
+
+impl Setup {
+    fn new(map: &Beatmap, mods: &GameMods) -> Self {
+        let builder = map.attributes().mods(mods.clone());
+        let map_attrs = builder.build();
+        let hit_windows = builder.hit_windows();
+        let clock_rate = map_attrs.clock_rate;
+
+        // Replay times are in map time so the hit windows must not be
+        // adjusted by the clock rate.
+        let great = hit_windows.od_great * clock_rate;
+        let ok = hit_windows.od_ok.unwrap_or(0.0) * clock_rate;
+
+        // All osu!standard hit windows are linear in OD
+        let od = (80.0 - great) / 6.0;
+        let meh = 200.0 - 10.0 * od;
+
+        Self {
+            scaling_factor: ScalingFactor::new(map_attrs.cs),
+            reflection: mods.reflection(),
+            time_preempt: f64::from((hit_windows.ar * clock_rate) as f32),
+            clock_rate,
+            od,
+            great,
+            ok,
+            meh,
+        }
+    }
+
+    fn result(&self, offset: f64) -> OsuHitResult {
+        let offset = offset.abs();
+
+        if offset <= self.great {
+            OsuHitResult::Great
+        } else if offset <= self.ok {
+            OsuHitResult::Ok
+        } else if offset <= self.meh {
+            OsuHitResult::Meh
+        } else {
+            OsuHitResult::Miss
+        }
+    }
+
+    fn spins_required(&self, duration: f64) -> f64 {
+        let spins_per_second = if self.od > 5.0 {
+            2.5 + 1.25 * (self.od - 5.0) / 5.0
+        } else {
+            2.5 - (5.0 - self.od) / 5.0
+        };
+
+        (duration / 1000.0 * spins_per_second).trunc()
+    }
+}
+
+/// The judgement of a hit circle or slider head.
+struct Head {
+    /// The time at which the judgement happened.
+    time: f64,
+    result: OsuHitResult,
+    offset: Option<f64>,
+}
+
+struct Simulation<'a> {
+    frames: &'a [ReplayFrame],
+    objects: &'a [OsuObject],
+    setup: &'a Setup,
+    relax: bool,
+    autopilot: bool,
+    auto_spin: bool,
+}
+
+impl Simulation<'_> {
+    fn run(&self, map: &Beatmap) -> OsuReplayJudgements {
+        let mut heads = self.judge_heads().into_iter();
+        let mut curve_bufs = CurveBuffers::default();
+
+        let mut state = OsuScoreState::new();
+        let mut objects = Vec::with_capacity(self.objects.len());
+
+        // Time of each combo change and whether the combo increased or broke
+        let mut combo_events = Vec::new();
+
+        for (h, hit_object) in self.objects.iter().zip(map.hit_objects.iter()) {
+            let (result, hit_offset) = match (&h.kind, &hit_object.kind) {
+                (OsuObjectKind::Circle, _) => {
+                    let head = heads.next().expect("missing head");
+                    combo_events.push((head.time, head.result != OsuHitResult::Miss));
+
+                    (head.result, head.offset)
+                }
+                (OsuObjectKind::Slider(slider), HitObjectKind::Slider(map_slider)) => {
+                    let head = heads.next().expect("missing head");
+                    let head_hit = head.result != OsuHitResult::Miss;
+                    combo_events.push((head.time, head_hit));
+
+                    let path = SliderPath {
+                        origin: h.stacked_pos(),
+                        start_time: h.start_time,
+                        span_duration: (slider.end_time - h.start_time)
+                            / map_slider.span_count() as f64,
+                        span_count: map_slider.span_count(),
+                        reflection: self.setup.reflection,
+                        curve: map_slider.curve(GameMode::Osu, &mut curve_bufs),
+                    };
+
+                    let mut hits = usize::from(head_hit);
+
+                    for (time, kind, hit) in self.track_slider(h, slider, &path) {
+                        hits += usize::from(hit);
+
+                        if let NestedSliderObjectKind::Tail = kind {
+                            if hit {
+                                state.slider_end_hits += 1;
+                                combo_events.push((time, true));
+                            }
+                        } else {
+                            state.large_tick_hits += u32::from(hit);
+                            combo_events.push((time, hit));
+                        }
+                    }
+
+                    let parts = slider.nested_objects.len() + 1;
+
+                    let result = if hits == parts {
+                        OsuHitResult::Great
+                    } else if 2 * hits >= parts {
+                        OsuHitResult::Ok
+                    } else if hits > 0 {
+                        OsuHitResult::Meh
+                    } else {
+                        OsuHitResult::Miss
+                    };
+
+                    (result, head.offset)
+                }
+                (OsuObjectKind::Spinner(spinner), _) => {
+                    let result = self.spin(h.start_time, spinner.duration);
+                    let end_time = h.start_time + spinner.duration;
+                    combo_events.push((end_time, result != OsuHitResult::Miss));
+
+                    (result, None)
+                }
+                (OsuObjectKind::Slider(_), _) => unreachable!("slider must originate from slider"),
+            };
+
+            match result {
+                OsuHitResult::Great => state.n300 += 1,
+                OsuHitResult::Ok => state.n100 += 1,
+                OsuHitResult::Meh => state.n50 += 1,
+                OsuHitResult::Miss => state.misses += 1,
+            }
+
+            objects.push(OsuObjectJudgement {
+                start_time: h.start_time,
+                result,
+                hit_offset,
+            });
+        }
+
+        combo_events.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+
+        let mut combo = 0;
+
+        for (_, hit) in combo_events {
+            if hit {
+                combo += 1;
+                state.max_combo = state.max_combo.max(combo);
+            } else {
+                combo = 0;
+            }
+        }
+
+        OsuReplayJudgements { state, objects }
+    }
+
+    /// Judge all hit circles and slider heads in order.
+    ///
+    /// Objects can only be hit once all previous objects are judged or their
+    /// start time has passed. Hitting an object misses all previous objects
+    /// that have not been judged yet.
+    fn judge_heads(&self) -> Vec<Head> {
+        let heads: Vec<_> = self.objects.iter().filter(|h| !h.is_spinner()).collect();
+
+        let mut judged = Vec::with_capacity(heads.len());
+        let mut prev_keys = 0;
+
+        for frame in self.frames {
+            let keys = frame.keys & KEYS;
+
+            let presses = if self.relax {
+                1
+            } else {
+                (keys & !prev_keys).count_ones()
+            };
+
+            prev_keys = keys;
+
+            let time = f64::from(frame.time);
+            let pos = Pos::new(frame.x, frame.y);
+
+            for _ in 0..presses {
+                self.miss_passed(&heads, &mut judged, time);
+                self.press(&heads, &mut judged, time, pos);
+            }
+        }
+
+        self.miss_passed(&heads, &mut judged, f64::INFINITY);
+
+        judged
+    }
+
+    /// Miss all objects whose hit window ended before `time`.
+    fn miss_passed(&self, heads: &[&OsuObject], judged: &mut Vec<Head>, time: f64) {
+        while let Some(h) = heads.get(judged.len()) {
+            let end_time = h.start_time + self.setup.meh;
+
+            if end_time >= time {
+                break;
+            }
+
+            judged.push(Head {
+                time: end_time,
+                result: OsuHitResult::Miss,
+                offset: None,
+            });
+        }
+    }
+
+    fn press(&self, heads: &[&OsuObject], judged: &mut Vec<Head>, time: f64, pos: Pos) {
+        let next = judged.len();
+
+        for (idx, h) in heads.iter().enumerate().skip(next) {
+            let offset = time - h.start_time;
+
+            if offset < -MISS_WINDOW || (self.relax && offset < -RELAX_LENIENCY) {
+                return;
+            }
+
+            if !self.hovers(pos, h.stacked_pos(), self.radius()) {
+                continue;
+            }
+
+            // Notelock; the press is consumed without judging anything
+            if idx > next && time < heads[idx - 1].start_time {
+                return;
+            }
+
+            for _ in next..idx {
+                judged.push(Head {
+                    time,
+                    result: OsuHitResult::Miss,
+                    offset: None,
+                });
+            }
+
+            let result = self.setup.result(offset);
+
+            judged.push(Head {
+                time,
+                result,
+                offset: (result != OsuHitResult::Miss).then_some(offset),
+            });
+
+            return;
+        }
+    }
+
+    /// Check whether the nested objects of a slider were tracked.
+    ///
+    /// Returns the time of the check, the kind of nested object, and
+    /// whether it was hit, ordered by time.
+    fn track_slider(
+        &self,
+        h: &OsuObject,
+        slider: &OsuSlider,
+        path: &SliderPath<'_>,
+    ) -> Vec<(f64, NestedSliderObjectKind, bool)> {
+        let duration = slider.end_time - h.start_time;
+        let tail_time = (slider.end_time + TAIL_LENIENCY).max(h.start_time + duration / 2.0);
+
+        let mut checks: Vec<_> = slider
+            .nested_objects
+            .iter()
+            .map(|nested| {
+                let time = match nested.kind {
+                    NestedSliderObjectKind::Tail => tail_time,
+                    NestedSliderObjectKind::Repeat | NestedSliderObjectKind::Tick => {
+                        nested.start_time
+                    }
+                };
+
+                (time, nested.kind, false)
+            })
+            .collect();
+
+        checks.sort_by(|(a, ..), (b, ..)| a.total_cmp(b));
+
+        let mut frame_idx = self
+            .frames
+            .partition_point(|frame| f64::from(frame.time) < h.start_time);
+
+        let mut tracking = false;
+
+        for (time, _, hit) in checks.iter_mut() {
+            while let Some(frame) = self
+                .frames
+                .get(frame_idx)
+                .filter(|frame| f64::from(frame.time) <= *time)
+            {
+                let ball = path.ball_pos(f64::from(frame.time));
+                tracking = self.tracks(frame, ball, tracking);
+                frame_idx += 1;
+            }
+
+            let Some(frame) = frame_idx.checked_sub(1).map(|idx| &self.frames[idx]) else {
+                continue;
+            };
+
+            tracking = self.tracks(frame, path.ball_pos(*time), tracking);
+            *hit = tracking;
+        }
+
+        checks
+    }
+
+    fn tracks(&self, frame: &ReplayFrame, ball: Pos, was_tracking: bool) -> bool {
+        let held = self.relax || frame.keys & KEYS > 0;
+
+        let radius = if was_tracking {
+            FOLLOW_AREA * self.radius()
+        } else {
+            self.radius()
+        };
+
+        held && self.hovers(Pos::new(frame.x, frame.y), ball, radius)
+    }
+
+    fn spin(&self, start_time: f64, duration: f64) -> OsuHitResult {
+        if self.auto_spin {
+            return OsuHitResult::Great;
+        }
+
+        let spins_required = self.setup.spins_required(duration);
+
+        if spins_required <= 0.0 {
+            return OsuHitResult::Great;
+        }
+
+        let end_time = start_time + duration;
+        let mut rotation = 0.0;
+        let mut prev = None;
+
+        let frames = self.frames.iter().filter(|frame| {
+            let time = f64::from(frame.time);
+
+            (start_time..=end_time).contains(&time)
+        });
+
+        for frame in frames {
+            if !self.relax && frame.keys & KEYS == 0 {
+                prev = None;
+
+                continue;
+            }
+
+            let time = f64::from(frame.time);
+            let angle = f64::from((frame.y - SPINNER_CENTER.y).atan2(frame.x - SPINNER_CENTER.x));
+
+            if let Some((prev_time, prev_angle)) = prev {
+                let mut delta: f64 = angle - prev_angle;
+
+                if delta > PI {
+                    delta -= TAU;
+                } else if delta < -PI {
+                    delta += TAU;
+                }
+
+                let max_delta = (time - prev_time) / self.setup.clock_rate * MAX_SPINS_PER_MS * TAU;
+                rotation += delta.abs().min(max_delta);
+            }
+
+            prev = Some((time, angle));
+        }
+
+        let progress = rotation / TAU / spins_required;
+
+        if progress >= 1.0 {
+            OsuHitResult::Great
+        } else if progress > 0.9 {
+            OsuHitResult::Ok
+        } else if progress > 0.75 {
+            OsuHitResult::Meh
+        } else {
+            OsuHitResult::Miss
+        }
+    }
+
+    fn hovers(&self, cursor: Pos, pos: Pos, radius: f64) -> bool {
+        self.autopilot || f64::from(cursor.distance(pos)) <= radius
+    }
+
+    const fn radius(&self) -> f64 {
+        self.setup.scaling_factor.radius
+    }
+}
+
+/// The path of a slider's ball.
+struct SliderPath<'a> {
+    /// The stacked position of the slider head.
+    origin: Pos,
+    start_time: f64,
+    span_duration: f64,
+    span_count: usize,
+    reflection: Reflection,
+    curve: BorrowedCurve<'a>,
+}
+
+impl SliderPath<'_> {
+    fn ball_pos(&self, time: f64) -> Pos {
+        let progress = if self.span_duration > 0.0 {
+            ((time - self.start_time) / self.span_duration).clamp(0.0, self.span_count as f64)
+        } else {
+            0.0
+        };
+
+        let span = (progress as usize).min(self.span_count - 1);
+        let mut span_progress = progress - span as f64;
+
+        if span % 2 == 1 {
+            span_progress = 1.0 - span_progress;
+        }
+
+        let mut pos = self.curve.position_at(span_progress);
+
+        match self.reflection {
+            Reflection::None => {}
+            Reflection::Vertical => pos.y = -pos.y,
+            Reflection::Horizontal => pos.x = -pos.x,
+            Reflection::Both => pos = Pos::new(-pos.x, -pos.y),
+        }
+
+        self.origin + pos
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{any::DifficultyAttributes, Difficulty};
+
+    use super::*;
+
+    fn replay(frames: Vec<ReplayFrame>, mods: u32) -> Replay {
+        Replay {
+            mode: GameMode::Osu,
+            game_version: 20_240_101,
+            beatmap_md5: String::new(),
+            player_name: String::new(),
+            replay_md5: String::new(),
+            n300: 0,
+            n100: 0,
+            n50: 0,
+            n_geki: 0,
+            n_katu: 0,
+            misses: 0,
+            score: 0,
+            max_combo: 0,
+            perfect: false,
+            mods,
+            life_bar: Vec::new(),
+            timestamp: 0,
+            frames,
+            rng_seed: None,
+            online_id: 0,
+            target_practice_accuracy: None,
+        }
+    }
+
+    /// Frames that perfectly hit every object, alternating between keys.
+    fn autoplay(map: &Beatmap, mods: u32) -> Vec<ReplayFrame> {
+        let setup = Setup::new(map, &GameMods::from(mods));
+
+        let objects = convert_objects(
+            map,
+            &setup.scaling_factor,
+            setup.reflection,
+            setup.time_preempt,
+            0,
+            &mut OsuDifficultyAttributes::default(),
+        );
+
+        let mut curve_bufs = CurveBuffers::default();
+        let mut frames = Vec::new();
+
+        let mut push = |time: f64, pos: Pos, keys: u32| {
+            let time = time as i32;
+            let delta = frames
+                .last()
+                .map_or(time, |prev: &ReplayFrame| time - prev.time);
+
+            frames.push(ReplayFrame {
+                time,
+                delta,
+                x: pos.x,
+                y: pos.y,
+                keys,
+            });
+        };
+
+        for (i, (h, hit_object)) in objects.iter().zip(map.hit_objects.iter()).enumerate() {
+            let keys = if i % 2 == 0 {
+                ReplayFrame::M1 | ReplayFrame::K1
+            } else {
+                ReplayFrame::M2 | ReplayFrame::K2
+            };
+
+            match (&h.kind, &hit_object.kind) {
+                (OsuObjectKind::Circle, _) => push(h.start_time, h.stacked_pos(), keys),
+                (OsuObjectKind::Slider(slider), HitObjectKind::Slider(map_slider)) => {
+                    let path = SliderPath {
+                        origin: h.stacked_pos(),
+                        start_time: h.start_time,
+                        span_duration: (slider.end_time - h.start_time)
+                            / map_slider.span_count() as f64,
+                        span_count: map_slider.span_count(),
+                        reflection: setup.reflection,
+                        curve: map_slider.curve(GameMode::Osu, &mut curve_bufs),
+                    };
+
+                    let mut time = h.start_time;
+
+                    while time < slider.end_time {
+                        push(time, path.ball_pos(time), keys);
+                        time += 10.0;
+                    }
+                }
+                (OsuObjectKind::Spinner(spinner), _) => {
+                    let mut time = h.start_time;
+                    let mut angle: f32 = 0.0;
+
+                    while time <= h.start_time + spinner.duration {
+                        let offset = Pos::new(angle.cos(), angle.sin()) * 50.0;
+                        push(time, SPINNER_CENTER + offset, keys);
+                        time += 10.0;
+                        angle += 0.06 * std::f32::consts::TAU;
+                    }
+                }
+                (OsuObjectKind::Slider(_), _) => unreachable!(),
+            }
+
+            let next_start = objects
+                .get(i + 1)
+                .map_or(f64::INFINITY, |next| next.start_time);
+            let release_time = (h.end_time() + 20.0).min(f64::midpoint(h.end_time(), next_start));
+
+            if release_time > h.end_time() {
+                push(release_time, h.stacked_end_pos(), 0);
+            }
+        }
+
+        frames
+    }
+
+    fn difficulty_attrs(map: &Beatmap, mods: u32) -> OsuDifficultyAttributes {
+        let DifficultyAttributes::Osu(attrs) = Difficulty::new().mods(mods).calculate(map) else {
+            unreachable!()
+        };
+
+        attrs
+    }
+
+    /// A circle at 1000ms and a straight slider from 2000ms to 3000ms with a
+    /// single tick at 2500ms.
+    const BOUNDARY_MAP: &str = "osu file format v14
+
+[General]
+Mode: 0
+
+[Difficulty]
+HPDrainRate:5
+CircleSize:4
+OverallDifficulty:5
+ApproachRate:9
+SliderMultiplier:1
+SliderTickRate:1
+
+[TimingPoints]
+0,500,4,2,0,100,1,0
+
+[HitObjects]
+256,192,1000,1,0,0:0:0:0:
+100,192,2000,2,0,L|300:192,1,200
+";
+
+    fn frame(time: i32, x: f32, y: f32, keys: u32) -> ReplayFrame {
+        ReplayFrame {
+            time,
+            delta: 0,
+            x,
+            y,
+            keys,
+        }
+    }
+
+    /// Click the circle, then hit the slider head and hold until `release`
+    /// while staying `distance` below the slider ball.
+    fn slider_frames(distance: f32, release: i32) -> Vec<ReplayFrame> {
+        let keys = ReplayFrame::M1 | ReplayFrame::K1;
+        let mut frames = vec![
+            frame(1000, 256.0, 192.0, keys),
+            frame(1010, 256.0, 192.0, 0),
+        ];
+        frames.push(frame(2000, 100.0, 192.0, keys));
+
+        for time in 2001..release {
+            let x = 100.0 + 200.0 * (time - 2000) as f32 / 1000.0;
+            frames.push(frame(time, x, 192.0 + distance, keys));
+        }
+
+        frames.push(frame(release, 300.0, 192.0 + distance, 0));
+
+        frames
+    }
+
+    #[test]
+    fn full_combo() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+
+        // NM, HR, HDDT, RX
+        for mods in [0, 16, 8 + 64, 128] {
+            let mut frames = autoplay(&map, mods);
+
+            if mods == 128 {
+                for frame in &mut frames {
+                    frame.keys = 0;
+                }
+            }
+
+            let judgements = replay(frames, mods).osu_judgements(&map).unwrap();
+            let attrs = difficulty_attrs(&map, mods);
+
+            let expected = OsuScoreState {
+                max_combo: attrs.max_combo,
+                large_tick_hits: attrs.n_large_ticks,
+                slider_end_hits: attrs.n_sliders,
+                n300: attrs.n_objects(),
+                n100: 0,
+                n50: 0,
+                misses: 0,
+            };
+
+            assert_eq!(judgements.state, expected, "mods={mods}");
+            assert_eq!(judgements.objects.len(), attrs.n_objects() as usize);
+
+            let n_offsets = judgements.hit_offsets().count();
+            assert_eq!(n_offsets, (attrs.n_circles + attrs.n_sliders) as usize);
+            assert!(judgements.hit_offsets().all(|offset| offset.abs() < 1.0));
+        }
+    }
+
+    #[test]
+    fn missed_circle() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+        let mut frames = autoplay(&map, 0);
+
+        let circle = map
+            .hit_objects
+            .iter()
+            .skip(10)
+            .find(|h| h.is_circle())
+            .unwrap();
+
+        let frame = frames
+            .iter_mut()
+            .find(|frame| f64::from(frame.time) >= circle.start_time.trunc())
+            .unwrap();
+
+        frame.keys = 0;
+
+        let judgements = replay(frames, 0).osu_judgements(&map).unwrap();
+        let attrs = difficulty_attrs(&map, 0);
+
+        assert_eq!(judgements.state.misses, 1);
+        assert_eq!(judgements.state.n300, attrs.n_objects() - 1);
+        assert!(judgements.state.max_combo < attrs.max_combo);
+
+        let missed = judgements
+            .objects
+            .iter()
+            .find(|judgement| judgement.result == OsuHitResult::Miss)
+            .unwrap();
+
+        assert!((missed.start_time - circle.start_time).abs() < f64::EPSILON);
+        assert_eq!(missed.hit_offset, None);
+    }
+
+    #[test]
+    fn no_input() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+        let judgements = replay(Vec::new(), 0).osu_judgements(&map).unwrap();
+        let attrs = difficulty_attrs(&map, 0);
+
+        assert_eq!(judgements.state.misses, attrs.n_objects());
+        assert_eq!(judgements.state.max_combo, 0);
+    }
+
+    #[test]
+    fn relax_leniency() {
+        let map: Beatmap = BOUNDARY_MAP.parse().unwrap();
+
+        let early = replay(vec![frame(996, 256.0, 192.0, 0)], 128);
+        let judgements = early.osu_judgements(&map).unwrap();
+        assert_eq!(judgements.objects[0].result, OsuHitResult::Miss);
+
+        let lenient = replay(vec![frame(997, 256.0, 192.0, 0)], 128);
+        let judgements = lenient.osu_judgements(&map).unwrap();
+        assert_eq!(judgements.objects[0].result, OsuHitResult::Great);
+        assert_eq!(judgements.objects[0].hit_offset, Some(-3.0));
+    }
+
+    #[test]
+    fn relax_one_press_per_frame() {
+        // Both heads are hovered and within their hit window at 2000ms
+        let map: Beatmap = BOUNDARY_MAP
+            .replace("256,192,1000", "100,192,1990")
+            .parse()
+            .unwrap();
+
+        let both_keys = ReplayFrame::M1 | ReplayFrame::M2;
+        let frames = vec![frame(2000, 100.0, 192.0, both_keys)];
+
+        let judgements = replay(frames.clone(), 0).osu_judgements(&map).unwrap();
+        assert!(judgements.objects[0].hit_offset.is_some());
+        assert!(judgements.objects[1].hit_offset.is_some());
+
+        let judgements = replay(frames, 128).osu_judgements(&map).unwrap();
+        assert!(judgements.objects[0].hit_offset.is_some());
+        assert_eq!(judgements.objects[1].hit_offset, None);
+    }
+
+    #[test]
+    fn follow_area() {
+        let map: Beatmap = BOUNDARY_MAP.parse().unwrap();
+        let radius = Setup::new(&map, &GameMods::from(0)).scaling_factor.radius as f32;
+
+        let inside = replay(slider_frames(2.3 * radius, 3100), 0);
+        let state = inside.osu_judgements(&map).unwrap().state;
+        assert_eq!((state.large_tick_hits, state.slider_end_hits), (1, 1));
+        assert_eq!(state.n300, 2);
+
+        let outside = replay(slider_frames(2.5 * radius, 3100), 0);
+        let state = outside.osu_judgements(&map).unwrap().state;
+        assert_eq!((state.large_tick_hits, state.slider_end_hits), (0, 0));
+        assert_eq!((state.n300, state.n50), (1, 1));
+    }
+
+    #[test]
+    fn tail_leniency() {
+        let map: Beatmap = BOUNDARY_MAP.parse().unwrap();
+
+        // The tail is checked 36ms before the slider's end at 3000ms
+        let late = replay(slider_frames(0.0, 2965), 0);
+        let state = late.osu_judgements(&map).unwrap().state;
+        assert_eq!((state.large_tick_hits, state.slider_end_hits), (1, 1));
+
+        let early = replay(slider_frames(0.0, 2964), 0);
+        let state = early.osu_judgements(&map).unwrap().state;
+        assert_eq!((state.large_tick_hits, state.slider_end_hits), (1, 0));
+        assert_eq!(state.n100, 1);
+    }
+
+    #[test]
+    fn wrong_mode() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+        let mut replay = replay(Vec::new(), 0);
+        replay.mode = GameMode::Taiko;
+
+        assert!(matches!(
+            replay.osu_judgements(&map),
+            Err(ConvertError::Convert { .. })
+        ));
+
+        let taiko = Beatmap::from_path("./resources/1028484.osu").unwrap();
+        replay.mode = GameMode::Osu;
+
+        assert!(replay.osu_judgements(&taiko).is_err());
+    }
+}
@@ -1,13 +1,17 @@
-use crate::taiko::performance::TaikoPerformance;
+use crate::{any::ZeroedComponents, taiko::performance::TaikoPerformance};
 
 /// The result of a difficulty calculation on an osu!taiko map.
 #[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct TaikoDifficultyAttributes {
     /// The difficulty of the stamina skill.
     pub stamina: f64,
     /// The difficulty of the rhythm skill.
     pub rhythm: f64,
     /// The difficulty of the color skill.
+    ///
+    /// Always `0.0` if [`is_relax`](Self::is_relax) is `true`.
     pub color: f64,
     /// The difficulty of the hardest parts of the map.
     pub peak: f64,
@@ -26,6 +30,11 @@ pub struct TaikoDifficultyAttributes {
     ///
     /// [`Beatmap`]: crate::model::beatmap::Beatmap
     pub is_convert: bool,
+    /// Whether the relax mod was enabled.
+    ///
+    /// On relax, hitting the right color is not required so the color skill
+    /// is not considered and the accuracy portion of the pp is zeroed.
+    pub is_relax: bool,
 }
 
 impl TaikoDifficultyAttributes {
@@ -41,6 +50,11 @@ impl TaikoDifficultyAttributes {
         self.is_convert
     }
 
+    /// Whether the relax mod was enabled.
+    pub const fn is_relax(&self) -> bool {
+        self.is_relax
+    }
+
     /// Returns a builder for performance calculation.
     pub fn performance<'a>(self) -> TaikoPerformance<'a> {
         self.into()
@@ -49,19 +63,34 @@ impl TaikoDifficultyAttributes {
 
 /// The result of a performance calculation on an osu!taiko map.
 #[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct TaikoPerformanceAttributes {
     /// The difficulty attributes that were used for the performance calculation
     pub difficulty: TaikoDifficultyAttributes,
     /// The final performance points.
     pub pp: f64,
     /// The accuracy portion of the final pp.
+    ///
+    /// Always `0.0` for relax and autopilot scores.
     pub pp_acc: f64,
     /// The strain portion of the final pp.
+    ///
+    /// Always `0.0` for autopilot scores.
     pub pp_difficulty: f64,
     /// Scaled miss count based on total hits.
     pub effective_miss_count: f64,
     /// Upper bound on the player's tap deviation.
     pub estimated_unstable_rate: Option<f64>,
+    /// The unstable rate that was measured through hit offsets, if any.
+    ///
+    /// If available, it is used instead of the estimated unstable rate.
+    pub measured_unstable_rate: Option<f64>,
+    /// The components that were zeroed due to the relax or autopilot mod.
+    ///
+    /// On relax, the color skill and the accuracy are zeroed. Autopilot is
+    /// not available in osu!taiko so all components are zeroed.
+    pub zeroed: ZeroedComponents,
 }
 
 impl TaikoPerformanceAttributes {
@@ -87,6 +116,11 @@ impl TaikoPerformanceAttributes {
         self.difficulty.is_convert
     }
 
+    /// Whether the relax mod was enabled.
+    pub const fn is_relax(&self) -> bool {
+        self.difficulty.is_relax
+    }
+
     /// Returns a builder for performance calculation.
     pub fn performance<'a>(self) -> TaikoPerformance<'a> {
         self.difficulty.into()
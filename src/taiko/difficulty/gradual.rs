@@ -5,13 +5,16 @@ use rosu_map::section::general::GameMode;
 use crate::{
     any::difficulty::skills::Skill,
     model::{beatmap::HitWindows, hit_object::HitObject, mode::ConvertError},
-    util::sync::RefCount,
+    util::{
+        checkpoints::{Checkpoints, Seek},
+        sync::RefCount,
+    },
     Beatmap, Difficulty,
 };
 
 use super::{
     object::{TaikoDifficultyObject, TaikoDifficultyObjects},
-    skills::TaikoSkills,
+    skills::{TaikoSkills, TaikoSkillsHistoryLen},
     DifficultyValues, TaikoDifficultyAttributes,
 };
 
@@ -56,6 +59,20 @@ pub struct TaikoGradualDifficulty {
     skills: TaikoSkills,
     total_hits: usize,
     first_combos: FirstTwoCombos,
+    checkpoints: Checkpoints<TaikoCheckpoint>,
+}
+
+/// The state after processing `idx` hits.
+///
+/// The skills are stored without their strain peaks. Instead, restoring
+/// truncates the current strain peaks to the stored amounts.
+#[derive(Clone)]
+pub struct TaikoCheckpoint {
+    idx: usize,
+    attrs: TaikoDifficultyAttributes,
+    diff_objects_iter: Iter<'static, RefCount<TaikoDifficultyObject>>,
+    skills: TaikoSkills,
+    history_len: TaikoSkillsHistoryLen,
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -101,12 +118,13 @@ impl TaikoGradualDifficulty {
             &mut n_diff_objects,
         );
 
-        let skills = TaikoSkills::new();
+        let mut skills = TaikoSkills::new();
 
         let attrs = TaikoDifficultyAttributes {
             great_hit_window: od_great,
             ok_hit_window: od_ok.unwrap_or(0.0),
             is_convert: map.is_convert,
+            is_relax: difficulty.get_mods().rx(),
             ..Default::default()
         };
 
@@ -114,6 +132,16 @@ impl TaikoGradualDifficulty {
 
         let diff_objects_iter = extend_lifetime(diff_objects.iter());
 
+        let (initial_skills, history_len) = skills.clone_without_history();
+
+        let checkpoints = Checkpoints::new(TaikoCheckpoint {
+            idx: 0,
+            attrs: attrs.clone(),
+            diff_objects_iter: diff_objects_iter.clone(),
+            skills: initial_skills,
+            history_len,
+        });
+
         Ok(Self {
             idx: 0,
             difficulty,
@@ -123,8 +151,51 @@ impl TaikoGradualDifficulty {
             attrs,
             total_hits,
             first_combos,
+            checkpoints,
         })
     }
+
+    /// Store the state after every `interval` hits so that seeking backwards
+    /// with [`seek`] has to process at most `interval` hits.
+    ///
+    /// Checkpoints are stored while hit objects are being processed. Each one
+    /// only contains the current state of the skills and the lengths of their
+    /// strain histories so its size does not grow with the amount of processed
+    /// hit objects.
+    ///
+    /// [`seek`]: TaikoGradualDifficulty::seek
+    #[must_use]
+    pub fn checkpoints(mut self, interval: usize) -> Self {
+        self.checkpoints.set_interval(interval);
+
+        self
+    }
+
+    /// Move to the hit at index `idx`, which may be before or after the
+    /// current one, and return the difficulty attributes after processing
+    /// it.
+    ///
+    /// The attributes are the same as for `nth(idx)` on a new iterator and
+    /// [`Iterator::next`] will continue with the hit at `idx + 1`.
+    ///
+    /// Seeking backwards restores the latest checkpoint before `idx` or,
+    /// without [`checkpoints`], processes all hits from the start again.
+    /// Seeking forwards always processes all hits between the current one and
+    /// `idx` because restoring a checkpoint discards the strain history after
+    /// it.
+    ///
+    /// Returns `None` if `idx` is out of bounds.
+    ///
+    /// [`checkpoints`]: TaikoGradualDifficulty::checkpoints
+    pub fn seek(&mut self, idx: usize) -> Option<TaikoDifficultyAttributes> {
+        if idx >= self.idx + self.len() {
+            return None;
+        }
+
+        self.seek_to(idx);
+
+        self.next()
+    }
 }
 
 fn extend_lifetime(
@@ -138,6 +209,8 @@ impl Iterator for TaikoGradualDifficulty {
     type Item = TaikoDifficultyAttributes;
 
     fn next(&mut self) -> Option<Self::Item> {
+        self.record_checkpoint();
+
         // The first difficulty object belongs to the third note since each
         // difficulty object requires the current, the last, and the second to
         // last note. Hence, if we're still on the first or second object, we
@@ -187,8 +260,57 @@ impl Iterator for TaikoGradualDifficulty {
     }
 
     fn nth(&mut self, n: usize) -> Option<Self::Item> {
-        let mut take = cmp::min(n, self.len().saturating_sub(1));
+        self.process(cmp::min(n, self.len().saturating_sub(1)));
+
+        self.next()
+    }
+}
+
+impl Seek for TaikoGradualDifficulty {
+    type Snapshot = TaikoCheckpoint;
+
+    const RESTORES_FORWARD: bool = false;
+
+    fn idx(&self) -> usize {
+        self.idx
+    }
+
+    fn stored_checkpoints(&self) -> &Checkpoints<Self::Snapshot> {
+        &self.checkpoints
+    }
 
+    fn stored_checkpoints_mut(&mut self) -> &mut Checkpoints<Self::Snapshot> {
+        &mut self.checkpoints
+    }
+
+    fn snapshot(&mut self) -> Self::Snapshot {
+        let (skills, history_len) = self.skills.clone_without_history();
+
+        TaikoCheckpoint {
+            idx: self.idx,
+            attrs: self.attrs.clone(),
+            diff_objects_iter: self.diff_objects_iter.clone(),
+            skills,
+            history_len,
+        }
+    }
+
+    fn restore(&mut self, snapshot: Self::Snapshot) {
+        let TaikoCheckpoint {
+            idx,
+            attrs,
+            diff_objects_iter,
+            skills,
+            history_len,
+        } = snapshot;
+
+        self.idx = idx;
+        self.attrs = attrs;
+        self.diff_objects_iter = diff_objects_iter;
+        self.skills.restore_with_history(skills, history_len);
+    }
+
+    fn process(&mut self, mut take: usize) {
         // The first two notes have no difficulty object but might add to combo
         match (take, self.idx) {
             (_, 2..) | (0, _) => {}
@@ -235,7 +357,9 @@ impl Iterator for TaikoGradualDifficulty {
 
         for _ in 0..take {
             loop {
-                let curr = self.diff_objects_iter.next()?;
+                let Some(curr) = self.diff_objects_iter.next() else {
+                    return;
+                };
                 let borrowed = curr.get();
                 rhythm.process(&borrowed);
                 color.process(&borrowed);
@@ -250,8 +374,6 @@ impl Iterator for TaikoGradualDifficulty {
                 }
             }
         }
-
-        self.next()
     }
 }
 
@@ -315,4 +437,31 @@ mod tests {
             assert_eq!(next_gradual, expected);
         }
     }
+
+    #[test]
+    fn seek() {
+        let map = Beatmap::from_path("./resources/1028484.osu").unwrap();
+
+        let difficulty = Difficulty::new().mods(64); // DT
+
+        let expected: Vec<_> = TaikoGradualDifficulty::new(difficulty.clone(), &map)
+            .unwrap()
+            .collect();
+
+        let mut gradual = TaikoGradualDifficulty::new(difficulty, &map)
+            .unwrap()
+            .checkpoints(50);
+
+        let last = expected.len() - 1;
+
+        for idx in [200, 10, 0, 1, 199, 201, last, 2, 100, 51, 50, 49] {
+            assert_eq!(gradual.seek(idx).as_ref(), Some(&expected[idx]), "{idx}");
+        }
+
+        assert!(gradual.seek(last + 1).is_none());
+
+        gradual.seek(20);
+        assert_eq!(gradual.next().as_ref(), Some(&expected[21]));
+        assert_eq!(gradual.len(), last - 21);
+    }
 }
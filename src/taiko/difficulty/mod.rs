@@ -49,6 +49,7 @@ pub fn difficulty(
         ok_hit_window: od_ok.unwrap_or(0.0),
         max_combo,
         is_convert: map.is_convert,
+        is_relax: difficulty.get_mods().rx(),
         ..Default::default()
     };
 
@@ -57,7 +58,12 @@ pub fn difficulty(
     Ok(attrs)
 }
 
-fn combined_difficulty_value(color: Color, rhythm: Rhythm, stamina: Stamina) -> f64 {
+fn combined_difficulty_value(
+    color: Color,
+    rhythm: Rhythm,
+    stamina: Stamina,
+    is_relax: bool,
+) -> f64 {
     fn norm(p: f64, values: [f64; 2]) -> f64 {
         values
             .into_iter()
@@ -81,7 +87,11 @@ fn combined_difficulty_value(color: Color, rhythm: Rhythm, stamina: Stamina) ->
         .zip(stamina_peaks.iter());
 
     for ((mut color_peak, mut rhythm_peak), mut stamina_peak) in iter {
-        color_peak *= COLOR_SKILL_MULTIPLIER;
+        color_peak *= if is_relax {
+            0.0
+        } else {
+            COLOR_SKILL_MULTIPLIER
+        };
         rhythm_peak *= RHYTHM_SKILL_MULTIPLIER;
         stamina_peak *= STAMINA_SKILL_MULTIPLIER;
 
@@ -161,7 +171,7 @@ impl DifficultyValues {
     }
 
     pub fn eval(attrs: &mut TaikoDifficultyAttributes, skills: TaikoSkills) {
-        let color_rating = skills.color.as_difficulty_value() * COLOR_SKILL_MULTIPLIER;
+        let mut color_rating = skills.color.as_difficulty_value() * COLOR_SKILL_MULTIPLIER;
         let rhythm_rating = skills.rhythm.as_difficulty_value() * RHYTHM_SKILL_MULTIPLIER;
         let stamina_rating = skills.stamina.as_difficulty_value() * STAMINA_SKILL_MULTIPLIER;
         let mono_stamina_rating =
@@ -172,7 +182,7 @@ impl DifficultyValues {
             1.0
         };
         let combined_rating =
-            combined_difficulty_value(skills.color, skills.rhythm, skills.stamina);
+            combined_difficulty_value(skills.color, skills.rhythm, skills.stamina, attrs.is_relax);
 
         let mut star_rating = rescale(combined_rating * 1.4);
 
@@ -185,6 +195,11 @@ impl DifficultyValues {
             }
         }
 
+        // Colors are irrelevant on relax
+        if attrs.is_relax {
+            color_rating = 0.0;
+        }
+
         attrs.stamina = stamina_rating;
         attrs.rhythm = rhythm_rating;
         attrs.color = color_rating;
@@ -3,7 +3,7 @@ use std::f64::consts::E;
 use crate::{
     any::difficulty::{
         object::IDifficultyObject,
-        skills::{strain_decay, ISkill, Skill, StrainDecaySkill},
+        skills::{strain_decay, ISkill, Skill, StrainDecaySkill, StrainPeaksSkill},
     },
     taiko::difficulty::{
         color::{
@@ -61,6 +61,12 @@ impl ISkill for Color {
     type DifficultyObjects<'a> = TaikoDifficultyObjects;
 }
 
+impl StrainPeaksSkill for Color {
+    fn strain_peaks_mut(&mut self) -> &mut StrainsVec {
+        &mut self.inner.inner.strain_peaks
+    }
+}
+
 impl Skill<'_, Color> {
     fn calculate_initial_strain(&mut self, time: f64, curr: &TaikoDifficultyObject) -> f64 {
         let prev_start_time = curr
@@ -1,3 +1,5 @@
+use crate::any::difficulty::skills::StrainPeaksSkill;
+
 use self::{color::Color, rhythm::Rhythm, stamina::Stamina};
 
 pub mod color;
@@ -21,4 +23,58 @@ impl TaikoSkills {
             single_color_stamina: Stamina::new(true),
         }
     }
+
+    /// Clone the skills without their strain peaks and return their amounts
+    /// alongside.
+    pub fn clone_without_history(&mut self) -> (Self, TaikoSkillsHistoryLen) {
+        let (rhythm, rhythm_len) = self.rhythm.clone_without_history();
+        let (color, color_len) = self.color.clone_without_history();
+        let (stamina, stamina_len) = self.stamina.clone_without_history();
+        let (single_color_stamina, single_color_stamina_len) =
+            self.single_color_stamina.clone_without_history();
+
+        let skills = Self {
+            rhythm,
+            color,
+            stamina,
+            single_color_stamina,
+        };
+
+        let len = TaikoSkillsHistoryLen {
+            rhythm: rhythm_len,
+            color: color_len,
+            stamina: stamina_len,
+            single_color_stamina: single_color_stamina_len,
+        };
+
+        (skills, len)
+    }
+
+    /// Replace the skills with clones of [`clone_without_history`] whose
+    /// strain peaks are the current ones truncated to `len`.
+    ///
+    /// [`clone_without_history`]: TaikoSkills::clone_without_history
+    pub fn restore_with_history(&mut self, skills: Self, len: TaikoSkillsHistoryLen) {
+        let Self {
+            rhythm,
+            color,
+            stamina,
+            single_color_stamina,
+        } = skills;
+
+        self.rhythm.restore_with_history(rhythm, len.rhythm);
+        self.color.restore_with_history(color, len.color);
+        self.stamina.restore_with_history(stamina, len.stamina);
+        self.single_color_stamina
+            .restore_with_history(single_color_stamina, len.single_color_stamina);
+    }
+}
+
+/// The amounts of strain peaks of [`TaikoSkills`].
+#[derive(Copy, Clone)]
+pub struct TaikoSkillsHistoryLen {
+    rhythm: usize,
+    color: usize,
+    stamina: usize,
+    single_color_stamina: usize,
 }
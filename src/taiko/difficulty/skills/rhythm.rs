@@ -3,7 +3,7 @@ use std::cmp;
 use crate::{
     any::difficulty::{
         object::IDifficultyObject,
-        skills::{strain_decay, ISkill, Skill, StrainDecaySkill},
+        skills::{strain_decay, ISkill, Skill, StrainDecaySkill, StrainPeaksSkill},
     },
     taiko::{
         difficulty::{
@@ -157,6 +157,12 @@ impl ISkill for Rhythm {
     type DifficultyObjects<'a> = TaikoDifficultyObjects;
 }
 
+impl StrainPeaksSkill for Rhythm {
+    fn strain_peaks_mut(&mut self) -> &mut StrainsVec {
+        &mut self.inner.inner.strain_peaks
+    }
+}
+
 impl Skill<'_, Rhythm> {
     fn calculate_initial_strain(&mut self, time: f64, curr: &TaikoDifficultyObject) -> f64 {
         let prev_start_time = curr
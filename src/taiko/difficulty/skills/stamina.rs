@@ -1,7 +1,7 @@
 use crate::{
     any::difficulty::{
         object::IDifficultyObject,
-        skills::{strain_decay, ISkill, Skill, StrainDecaySkill, StrainSkill},
+        skills::{strain_decay, ISkill, Skill, StrainDecaySkill, StrainPeaksSkill, StrainSkill},
     },
     taiko::{
         difficulty::object::{TaikoDifficultyObject, TaikoDifficultyObjects},
@@ -44,6 +44,12 @@ impl ISkill for Stamina {
     type DifficultyObjects<'a> = TaikoDifficultyObjects;
 }
 
+impl StrainPeaksSkill for Stamina {
+    fn strain_peaks_mut(&mut self) -> &mut StrainsVec {
+        &mut self.inner.strain_peaks
+    }
+}
+
 impl Skill<'_, Stamina> {
     fn calculate_initial_strain(&mut self, time: f64, curr: &TaikoDifficultyObject) -> f64 {
         if self.inner.single_color {
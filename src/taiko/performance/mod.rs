@@ -3,10 +3,14 @@ use std::cmp;
 use rosu_map::section::general::GameMode;
 
 use crate::{
-    any::{Difficulty, HitResultPriority, IntoModePerformance, IntoPerformance},
+    any::{Difficulty, HitResultPriority, IntoModePerformance, IntoPerformance, ZeroedComponents},
     model::{mode::ConvertError, mods::GameMods},
     osu::OsuPerformance,
-    util::{map_or_attrs::MapOrAttrs, special_functions},
+    util::{
+        deviation::{self, MeasuredUnstableRate},
+        map_or_attrs::MapOrAttrs,
+        search, special_functions,
+    },
     Performance,
 };
 
@@ -30,6 +34,7 @@ pub struct TaikoPerformance<'map> {
     n300: Option<u32>,
     n100: Option<u32>,
     misses: Option<u32>,
+    unstable_rate: Option<MeasuredUnstableRate>,
 }
 
 impl<'map> TaikoPerformance<'map> {
@@ -196,6 +201,29 @@ impl<'map> TaikoPerformance<'map> {
         self
     }
 
+    /// Specify the measured unstable rate of the play.
+    ///
+    /// The value must already be adjusted by the clock rate, i.e. as displayed
+    /// by osu!. If specified, it will be used instead of the unstable rate
+    /// that is estimated from the hitresults.
+    pub const fn unstable_rate(mut self, unstable_rate: f64) -> Self {
+        self.unstable_rate = Some(MeasuredUnstableRate::Adjusted(unstable_rate));
+
+        self
+    }
+
+    /// Specify the hit offsets of the play in milliseconds to measure the
+    /// unstable rate.
+    ///
+    /// The offsets must be in map time, i.e. they will still be adjusted by
+    /// the clock rate. If fewer than two offsets are given, no unstable rate
+    /// will be measured.
+    pub fn hit_offsets(mut self, hit_offsets: impl IntoIterator<Item = f64>) -> Self {
+        self.unstable_rate = MeasuredUnstableRate::from_hit_offsets(hit_offsets);
+
+        self
+    }
+
     /// Provide parameters through a [`TaikoScoreState`].
     #[allow(clippy::needless_pass_by_value)]
     pub const fn state(mut self, state: TaikoScoreState) -> Self {
@@ -306,6 +334,79 @@ impl<'map> TaikoPerformance<'map> {
         })
     }
 
+    /// Adjust the current score so that it becomes a full combo.
+    ///
+    /// Misses are turned into n300s while n100s are kept as they are. The
+    /// combo is set to the map's max combo.
+    pub fn if_fc(mut self) -> Result<Self, ConvertError> {
+        let state = self.generate_state()?;
+
+        self.n300 = Some(state.n300 + state.misses);
+        self.n100 = Some(state.n100);
+        self.misses = Some(0);
+        self.combo = None;
+        self.acc = None;
+
+        Ok(self)
+    }
+
+    /// Modify the current [`TaikoScoreState`] through the given function.
+    ///
+    /// Unspecified n300s and n100s are generated from the accuracy before the
+    /// function is called. The adjusted counts are used as is afterwards and
+    /// the accuracy is discarded.
+    pub fn what_if<F>(mut self, f: F) -> Result<Self, ConvertError>
+    where
+        F: FnOnce(&mut TaikoScoreState),
+    {
+        let mut state = self.generate_state()?;
+        f(&mut state);
+        self.acc = None;
+
+        Ok(self.state(state))
+    }
+
+    /// Find the minimal accuracy between `0.0` and `100.0` that is required
+    /// to reach the given amount of pp.
+    ///
+    /// All hitresults except for misses are generated from the accuracy while all
+    /// other parameters are kept as they are.
+    ///
+    /// Returns `None` if the pp can't be reached even with 100% accuracy.
+    pub fn accuracy_for_pp(self, pp: f64) -> Result<Option<f64>, ConvertError> {
+        let (mut perf, _) = self.with_attrs()?;
+        perf.n300 = None;
+        perf.n100 = None;
+
+        Ok(search::min_f64(0.0, 100.0, |acc| {
+            perf.clone().accuracy(acc).reaches_pp(pp)
+        }))
+    }
+
+    /// Find the maximal amount of misses for which the given amount of pp
+    /// can still be reached.
+    ///
+    /// Returns `None` if the pp can't be reached even without misses.
+    pub fn misses_for_pp(self, pp: f64) -> Result<Option<u32>, ConvertError> {
+        let (perf, attrs) = self.with_attrs()?;
+
+        Ok(search::max_u32(0, attrs.max_combo, |misses| {
+            perf.clone().misses(misses).reaches_pp(pp)
+        }))
+    }
+
+    /// Find the minimal combo that is required to reach the given amount of
+    /// pp.
+    ///
+    /// Returns `None` if the pp can't be reached even with max combo.
+    pub fn combo_for_pp(self, pp: f64) -> Result<Option<u32>, ConvertError> {
+        let (perf, attrs) = self.with_attrs()?;
+
+        Ok(search::min_u32(0, attrs.max_combo, |combo| {
+            perf.clone().combo(combo).reaches_pp(pp)
+        }))
+    }
+
     /// Calculate all performance related values, including pp and stars.
     pub fn calculate(mut self) -> Result<TaikoPerformanceAttributes, ConvertError> {
         let state = self.generate_state()?;
@@ -319,11 +420,34 @@ impl<'map> TaikoPerformance<'map> {
             mods: self.difficulty.get_mods(),
             state,
             attrs,
+            measured_unstable_rate: self
+                .unstable_rate
+                .map(|unstable_rate| unstable_rate.get(self.difficulty.get_clock_rate())),
         };
 
         Ok(inner.calculate())
     }
 
+    /// Calculate the difficulty attributes unless they're already stored.
+    ///
+    /// Returns a copy of the attributes alongside the calculator.
+    fn with_attrs(mut self) -> Result<(Self, TaikoDifficultyAttributes), ConvertError> {
+        let attrs = match self.map_or_attrs {
+            MapOrAttrs::Map(ref map) => {
+                let attrs = self.difficulty.calculate_for_mode::<Taiko>(map)?;
+
+                self.map_or_attrs.insert_attrs(attrs).clone()
+            }
+            MapOrAttrs::Attrs(ref attrs) => attrs.clone(),
+        };
+
+        Ok((self, attrs))
+    }
+
+    fn reaches_pp(self, pp: f64) -> bool {
+        self.calculate().is_ok_and(|attrs| attrs.pp >= pp)
+    }
+
     pub(crate) const fn from_map_or_attrs(map_or_attrs: MapOrAttrs<'map, Taiko>) -> Self {
         Self {
             map_or_attrs,
@@ -334,6 +458,7 @@ impl<'map> TaikoPerformance<'map> {
             n300: None,
             n100: None,
             hitresult_priority: HitResultPriority::DEFAULT,
+            unstable_rate: None,
         }
     }
 }
@@ -370,6 +495,7 @@ impl<'map> TryFrom<OsuPerformance<'map>> for TaikoPerformance<'map> {
             n50: _,
             misses,
             hitresult_priority,
+            unstable_rate,
         } = osu;
 
         Ok(Self {
@@ -381,6 +507,7 @@ impl<'map> TryFrom<OsuPerformance<'map>> for TaikoPerformance<'map> {
             n300,
             n100,
             misses,
+            unstable_rate,
         })
     }
 }
@@ -395,6 +522,7 @@ struct TaikoPerformanceInner<'mods> {
     attrs: TaikoDifficultyAttributes,
     mods: &'mods GameMods,
     state: TaikoScoreState,
+    measured_unstable_rate: Option<f64>,
 }
 
 impl TaikoPerformanceInner<'_> {
@@ -407,6 +535,8 @@ impl TaikoPerformanceInner<'_> {
             .compute_deviation_upper_bound(total_successful_hits)
             .map(|v| v * 10.0);
 
+        let unstable_rate = self.measured_unstable_rate.or(estimated_unstable_rate);
+
         let effective_miss_count = if total_successful_hits > 0 {
             (1000.0 / f64::from(total_successful_hits)).max(1.0) * f64::from(self.state.misses)
         } else {
@@ -423,9 +553,23 @@ impl TaikoPerformanceInner<'_> {
             multiplier *= 0.95;
         }
 
-        let diff_value =
-            self.compute_difficulty_value(effective_miss_count, estimated_unstable_rate);
-        let acc_value = self.compute_accuracy_value(estimated_unstable_rate);
+        let zeroed = ZeroedComponents {
+            difficulty: self.mods.ap(),
+            color: self.attrs.is_relax,
+            accuracy: self.attrs.is_relax || self.mods.ap(),
+        };
+
+        let diff_value = if zeroed.difficulty {
+            0.0
+        } else {
+            self.compute_difficulty_value(effective_miss_count, unstable_rate)
+        };
+
+        let acc_value = if zeroed.accuracy {
+            0.0
+        } else {
+            self.compute_accuracy_value(unstable_rate)
+        };
 
         let pp = (diff_value.powf(1.1) + acc_value.powf(1.1)).powf(1.0 / 1.1) * multiplier;
 
@@ -436,15 +580,17 @@ impl TaikoPerformanceInner<'_> {
             pp_difficulty: diff_value,
             effective_miss_count,
             estimated_unstable_rate,
+            measured_unstable_rate: self.measured_unstable_rate,
+            zeroed,
         }
     }
 
     fn compute_difficulty_value(
         &self,
         effective_miss_count: f64,
-        estimated_unstable_rate: Option<f64>,
+        unstable_rate: Option<f64>,
     ) -> f64 {
-        let Some(estimated_unstable_rate) = estimated_unstable_rate else {
+        let Some(unstable_rate) = unstable_rate else {
             return 0.0;
         };
 
@@ -479,23 +625,20 @@ impl TaikoPerformanceInner<'_> {
         let acc_scaling_shift = f64::from(300) - f64::from(100) * self.attrs.mono_stamina_factor;
 
         diff_value
-            * (special_functions::erf(
-                acc_scaling_shift / (2.0_f64.sqrt() * estimated_unstable_rate),
-            ))
-            .powf(acc_scaling_exp)
+            * (special_functions::erf(acc_scaling_shift / (2.0_f64.sqrt() * unstable_rate)))
+                .powf(acc_scaling_exp)
     }
 
-    fn compute_accuracy_value(&self, estimated_unstable_rate: Option<f64>) -> f64 {
+    fn compute_accuracy_value(&self, unstable_rate: Option<f64>) -> f64 {
         if self.attrs.great_hit_window <= 0.0 {
             return 0.0;
         }
 
-        let Some(estimated_unstable_rate) = estimated_unstable_rate else {
+        let Some(unstable_rate) = unstable_rate else {
             return 0.0;
         };
 
-        let mut acc_value =
-            (70.0 / estimated_unstable_rate).powf(1.1) * self.attrs.stars.powf(0.4) * 100.0;
+        let mut acc_value = (70.0 / unstable_rate).powf(1.1) * self.attrs.stars.powf(0.4) * 100.0;
 
         let len_bonus = (self.total_hits() / 1500.0).powf(0.3).min(1.15);
 
@@ -511,57 +654,13 @@ impl TaikoPerformanceInner<'_> {
     // * and the hit judgements, assuming the player's mean hit error is 0. The estimation is consistent in that
     // * two SS scores on the same map with the same settings will always return the same deviation.
     fn compute_deviation_upper_bound(&self, total_successful_hits: u32) -> Option<f64> {
-        if total_successful_hits == 0 || self.attrs.great_hit_window <= 0.0 {
-            return None;
-        }
-
-        let h300 = self.attrs.great_hit_window;
-        let h100 = self.attrs.ok_hit_window;
-        let n = self.total_hits();
-
-        #[allow(clippy::items_after_statements, clippy::unreadable_literal)]
-        // * 99% critical value for the normal distribution (one-tailed).
-        const Z: f64 = 2.32634787404;
-
-        // * The upper bound on deviation, calculated with the ratio of 300s to objects, and the great hit window.
-        let calc_deviation_great_window = || {
-            if self.state.n300 == 0 {
-                return None;
-            }
-
-            // * Proportion of greats hit.
-            let p = f64::from(self.state.n300) / n;
-
-            // * We can be 99% confident that p is at least this value.
-            let p_lower_bound = (n * p + Z * Z / 2.0) / (n + Z * Z)
-                - Z / (n + Z * Z) * (n * p * (1.0 - p) + Z * Z / 4.0).sqrt();
-
-            // * We can be 99% confident that the deviation is not higher than:
-            Some(h300 / (2.0_f64.sqrt() * special_functions::erf_inv(p_lower_bound)))
-        };
-
-        // * The upper bound on deviation, calculated with the ratio of 300s + 100s to objects, and the good hit window.
-        // * This will return a lower value than the first method when the number of 100s is high, but the miss count is low.
-        let calc_deviation_good_window = || {
-            // * Proportion of greats + goods hit.
-            let p = f64::from(total_successful_hits) / n;
-
-            // * We can be 99% confident that p is at least this value.
-            let p_lower_bound = (n * p + Z * Z / 2.0) / (n + Z * Z)
-                - Z / (n + Z * Z) * (n * p * (1.0 - p) + Z * Z / 4.0).sqrt();
-
-            // * We can be 99% confident that the deviation is not higher than:
-            h100 / (2.0_f64.sqrt() * special_functions::erf_inv(p_lower_bound))
-        };
-
-        let deviation_great_window = calc_deviation_great_window();
-        let deviation_good_window = calc_deviation_good_window();
-
-        let Some(deviation_great_window) = deviation_great_window else {
-            return Some(deviation_good_window);
-        };
-
-        Some(deviation_great_window.min(deviation_good_window))
+        deviation::deviation_upper_bound(
+            self.total_hits(),
+            self.state.n300,
+            total_successful_hits,
+            self.attrs.great_hit_window,
+            self.attrs.ok_hit_window,
+        )
     }
 
     const fn total_hits(&self) -> f64 {
@@ -770,6 +869,77 @@ mod test {
         assert_eq!(state, expected);
     }
 
+    #[test]
+    fn relax() {
+        let map = beatmap();
+
+        let nomod = TaikoPerformance::from(&map).misses(2).calculate().unwrap();
+        let relax = TaikoPerformance::from(&map)
+            .mods(128)
+            .misses(2)
+            .calculate()
+            .unwrap();
+
+        assert!(relax.is_relax());
+        assert!(!nomod.is_relax());
+        assert!(relax.difficulty.color.abs() < f64::EPSILON);
+        assert!(relax.pp_acc.abs() < f64::EPSILON);
+        assert!(relax.difficulty.stars < nomod.difficulty.stars);
+        assert!(relax.pp < nomod.pp);
+
+        let expected = ZeroedComponents {
+            difficulty: false,
+            color: true,
+            accuracy: true,
+        };
+
+        assert_eq!(relax.zeroed, expected);
+        assert_eq!(nomod.zeroed, ZeroedComponents::default());
+
+        // Relax attributes are handled the same without the relax mod
+        let from_attrs = TaikoPerformance::from(relax.difficulty.clone())
+            .misses(2)
+            .calculate()
+            .unwrap();
+
+        assert_eq!(from_attrs.zeroed, expected);
+    }
+
+    #[test]
+    fn autopilot() {
+        let map = beatmap();
+
+        let autopilot = TaikoPerformance::from(&map)
+            .mods(8192)
+            .misses(2)
+            .calculate()
+            .unwrap();
+
+        assert!(autopilot.zeroed.difficulty && autopilot.zeroed.accuracy);
+        assert!(autopilot.pp.abs() < f64::EPSILON);
+        assert!(autopilot.pp_difficulty.abs() < f64::EPSILON);
+        assert!(autopilot.difficulty.stars > 0.0);
+    }
+
+    #[test]
+    fn measured_unstable_rate() {
+        let map = beatmap();
+
+        let estimated = TaikoPerformance::from(&map).n100(20).calculate().unwrap();
+        let estimated_unstable_rate = estimated.estimated_unstable_rate.unwrap();
+
+        // The measured unstable rate replaces the estimation
+        let measured = TaikoPerformance::from(&map)
+            .n100(20)
+            .unstable_rate(estimated_unstable_rate)
+            .calculate()
+            .unwrap();
+
+        assert!((measured.pp - estimated.pp).abs() < 1e-9);
+        assert!((measured.pp_difficulty - estimated.pp_difficulty).abs() < 1e-9);
+        assert!((measured.pp_acc - estimated.pp_acc).abs() < 1e-9);
+    }
+
     #[test]
     fn create() {
         let mut map = beatmap();
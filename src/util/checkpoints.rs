@@ -0,0 +1,236 @@
+use std::cmp;
+
+/// Snapshots of a gradual calculator's state so that it can seek to any
+/// object without processing all previous objects again.
+pub struct Checkpoints<T> {
+    /// Amount of objects between two snapshots or `0` if only the initial
+    /// state is stored.
+    interval: usize,
+    /// The `i`th snapshot is the state after processing `i * interval`
+    /// objects.
+    snapshots: Vec<T>,
+}
+
+impl<T> Checkpoints<T> {
+    pub fn new(initial: T) -> Self {
+        Self {
+            interval: 0,
+            snapshots: vec![initial],
+        }
+    }
+
+    /// Store a snapshot after every `interval` objects, or only the initial
+    /// state if `interval` is `0`.
+    ///
+    /// Previously stored snapshots except for the initial one are discarded.
+    pub fn set_interval(&mut self, interval: usize) {
+        self.interval = interval;
+        self.snapshots.truncate(1);
+    }
+
+    /// Whether `idx` is the position of the next snapshot that's yet to be
+    /// stored.
+    const fn is_missing(&self, idx: usize) -> bool {
+        self.interval > 0 && idx == self.snapshots.len() * self.interval
+    }
+
+    /// The position of the next checkpoint after `idx`.
+    const fn next_after(&self, idx: usize) -> Option<usize> {
+        match idx.checked_div(self.interval) {
+            Some(i) => Some((i + 1) * self.interval),
+            None => None,
+        }
+    }
+
+    /// The latest stored snapshot at or before `idx` and its position.
+    fn latest(&self, idx: usize) -> (usize, &T) {
+        let i = match self.interval {
+            0 => 0,
+            interval => cmp::min(idx / interval, self.snapshots.len() - 1),
+        };
+
+        (i * self.interval, &self.snapshots[i])
+    }
+}
+
+/// A gradual calculator whose state can be stored in [`Checkpoints`].
+pub trait Seek {
+    type Snapshot: Clone;
+
+    /// Whether snapshots of later states can be restored.
+    ///
+    /// If `false`, snapshots are only restored when seeking backwards.
+    const RESTORES_FORWARD: bool = true;
+
+    /// The amount of processed objects.
+    fn idx(&self) -> usize;
+
+    fn stored_checkpoints(&self) -> &Checkpoints<Self::Snapshot>;
+
+    fn stored_checkpoints_mut(&mut self) -> &mut Checkpoints<Self::Snapshot>;
+
+    fn snapshot(&mut self) -> Self::Snapshot;
+
+    /// Restore a stored snapshot.
+    ///
+    /// Unless [`Seek::RESTORES_FORWARD`] is `true`, the snapshot is never of a
+    /// later state than the current one.
+    fn restore(&mut self, snapshot: Self::Snapshot);
+
+    /// Process the next `n` objects without evaluating any attributes.
+    fn process(&mut self, n: usize);
+
+    /// Store a snapshot if the current position is a checkpoint that's not
+    /// stored yet.
+    fn record_checkpoint(&mut self) {
+        if self.stored_checkpoints().is_missing(self.idx()) {
+            let snapshot = self.snapshot();
+            self.stored_checkpoints_mut().snapshots.push(snapshot);
+        }
+    }
+
+    /// Move to the state after processing `target` objects, starting from
+    /// either the current state or the latest checkpoint before `target`.
+    ///
+    /// If [`Seek::RESTORES_FORWARD`] is `false`, seeking forwards always
+    /// starts from the current state.
+    fn seek_to(&mut self, target: usize) {
+        let (checkpoint_idx, snapshot) = self.stored_checkpoints().latest(target);
+
+        if self.idx() > target || (Self::RESTORES_FORWARD && checkpoint_idx > self.idx()) {
+            let snapshot = snapshot.clone();
+            self.restore(snapshot);
+        }
+
+        while self.idx() < target {
+            let prev = self.idx();
+
+            let stop = self
+                .stored_checkpoints()
+                .next_after(prev)
+                .map_or(target, |next| cmp::min(next, target));
+
+            self.process(stop - prev);
+
+            if self.idx() == prev {
+                break;
+            }
+
+            self.record_checkpoint();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Sums up the indices of all processed objects.
+    struct Sum<const RESTORES_FORWARD: bool> {
+        idx: usize,
+        total: usize,
+        processed: usize,
+        checkpoints: Checkpoints<(usize, usize)>,
+    }
+
+    impl<const RESTORES_FORWARD: bool> Sum<RESTORES_FORWARD> {
+        fn new(interval: usize) -> Self {
+            let mut checkpoints = Checkpoints::new((0, 0));
+            checkpoints.set_interval(interval);
+
+            Self {
+                idx: 0,
+                total: 0,
+                processed: 0,
+                checkpoints,
+            }
+        }
+    }
+
+    impl<const RESTORES_FORWARD: bool> Seek for Sum<RESTORES_FORWARD> {
+        type Snapshot = (usize, usize);
+
+        const RESTORES_FORWARD: bool = RESTORES_FORWARD;
+
+        fn idx(&self) -> usize {
+            self.idx
+        }
+
+        fn stored_checkpoints(&self) -> &Checkpoints<Self::Snapshot> {
+            &self.checkpoints
+        }
+
+        fn stored_checkpoints_mut(&mut self) -> &mut Checkpoints<Self::Snapshot> {
+            &mut self.checkpoints
+        }
+
+        fn snapshot(&mut self) -> Self::Snapshot {
+            (self.idx, self.total)
+        }
+
+        fn restore(&mut self, (idx, total): Self::Snapshot) {
+            self.idx = idx;
+            self.total = total;
+        }
+
+        fn process(&mut self, n: usize) {
+            for _ in 0..n {
+                self.total += self.idx;
+                self.idx += 1;
+                self.processed += 1;
+            }
+        }
+    }
+
+    #[test]
+    fn seek_with_checkpoints() {
+        let mut sum = Sum::<true>::new(10);
+
+        sum.seek_to(95);
+        assert_eq!((sum.idx, sum.total), (95, (0..95).sum()));
+        assert_eq!(sum.checkpoints.snapshots.len(), 10);
+
+        sum.processed = 0;
+        sum.seek_to(33);
+        assert_eq!((sum.idx, sum.total), (33, (0..33).sum()));
+        assert_eq!(sum.processed, 3);
+
+        sum.processed = 0;
+        sum.seek_to(87);
+        assert_eq!((sum.idx, sum.total), (87, (0..87).sum()));
+        assert_eq!(sum.processed, 7);
+
+        sum.processed = 0;
+        sum.seek_to(89);
+        assert_eq!(sum.processed, 2);
+    }
+
+    #[test]
+    fn seek_only_restoring_backwards() {
+        let mut sum = Sum::<false>::new(10);
+
+        sum.seek_to(95);
+        sum.processed = 0;
+        sum.seek_to(33);
+        assert_eq!((sum.idx, sum.total), (33, (0..33).sum()));
+        assert_eq!(sum.processed, 3);
+
+        sum.processed = 0;
+        sum.seek_to(87);
+        assert_eq!((sum.idx, sum.total), (87, (0..87).sum()));
+        assert_eq!(sum.processed, 54);
+    }
+
+    #[test]
+    fn seek_without_checkpoints() {
+        let mut sum = Sum::<true>::new(0);
+
+        sum.seek_to(50);
+        sum.processed = 0;
+        sum.seek_to(20);
+
+        assert_eq!((sum.idx, sum.total), (20, (0..20).sum()));
+        assert_eq!(sum.processed, 20);
+        assert_eq!(sum.checkpoints.snapshots.len(), 1);
+    }
+}
@@ -0,0 +1,139 @@
+use super::special_functions;
+
+/// A measured unstable rate that was passed to a performance calculator.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum MeasuredUnstableRate {
+    /// Already adjusted by the clock rate, i.e. as displayed by osu!.
+    Adjusted(f64),
+    /// Calculated from hit offsets in map time so the clock rate still needs
+    /// to be applied.
+    Unadjusted(f64),
+}
+
+impl MeasuredUnstableRate {
+    /// Returns `None` if there are fewer than two hit offsets.
+    pub fn from_hit_offsets(offsets: impl IntoIterator<Item = f64>) -> Option<Self> {
+        unstable_rate(offsets).map(Self::Unadjusted)
+    }
+
+    /// The unstable rate in real time.
+    pub fn get(self, clock_rate: f64) -> f64 {
+        match self {
+            Self::Adjusted(unstable_rate) => unstable_rate,
+            Self::Unadjusted(unstable_rate) => unstable_rate / clock_rate,
+        }
+    }
+}
+
+/// The standard deviation of the hit offsets times 10.
+///
+/// Returns `None` if there are fewer than two hit offsets.
+pub fn unstable_rate(offsets: impl IntoIterator<Item = f64>) -> Option<f64> {
+    let mut n = 0.0;
+    let mut mean = 0.0;
+    let mut sum_squares = 0.0;
+
+    // Welford's online algorithm
+    for offset in offsets {
+        n += 1.0;
+        let delta = offset - mean;
+        mean += delta / n;
+        sum_squares += delta * (offset - mean);
+    }
+
+    (n >= 2.0).then(|| (sum_squares / n).sqrt() * 10.0)
+}
+
+/// The probability that a hit lands within `hit_window` assuming the hit
+/// offsets are normally distributed around `0.0` with the given deviation.
+pub fn hit_probability(hit_window: f64, deviation: f64) -> f64 {
+    if deviation <= 0.0 {
+        return 1.0;
+    }
+
+    special_functions::erf(hit_window / (2.0_f64.sqrt() * deviation))
+}
+
+// * Computes an upper bound on the player's tap deviation based on the OD, number of circles and sliders,
+// * and the hit judgements, assuming the player's mean hit error is 0. The estimation is consistent in that
+// * two SS scores on the same map with the same settings will always return the same deviation.
+pub fn deviation_upper_bound(
+    n: f64,
+    n_great: u32,
+    n_successful: u32,
+    great_hit_window: f64,
+    ok_hit_window: f64,
+) -> Option<f64> {
+    if n_successful == 0 || great_hit_window <= 0.0 {
+        return None;
+    }
+
+    #[allow(clippy::items_after_statements, clippy::unreadable_literal)]
+    // * 99% critical value for the normal distribution (one-tailed).
+    const Z: f64 = 2.32634787404;
+
+    // * The upper bound on deviation, calculated with the ratio of 300s to objects, and the great hit window.
+    let calc_deviation_great_window = || {
+        if n_great == 0 {
+            return None;
+        }
+
+        // * Proportion of greats hit.
+        let p = f64::from(n_great) / n;
+
+        // * We can be 99% confident that p is at least this value.
+        let p_lower_bound = (n * p + Z * Z / 2.0) / (n + Z * Z)
+            - Z / (n + Z * Z) * (n * p * (1.0 - p) + Z * Z / 4.0).sqrt();
+
+        // * We can be 99% confident that the deviation is not higher than:
+        Some(great_hit_window / (2.0_f64.sqrt() * special_functions::erf_inv(p_lower_bound)))
+    };
+
+    // * The upper bound on deviation, calculated with the ratio of 300s + 100s to objects, and the good hit window.
+    // * This will return a lower value than the first method when the number of 100s is high, but the miss count is low.
+    let calc_deviation_good_window = || {
+        // * Proportion of greats + goods hit.
+        let p = f64::from(n_successful) / n;
+
+        // * We can be 99% confident that p is at least this value.
+        let p_lower_bound = (n * p + Z * Z / 2.0) / (n + Z * Z)
+            - Z / (n + Z * Z) * (n * p * (1.0 - p) + Z * Z / 4.0).sqrt();
+
+        // * We can be 99% confident that the deviation is not higher than:
+        ok_hit_window / (2.0_f64.sqrt() * special_functions::erf_inv(p_lower_bound))
+    };
+
+    let deviation_great_window = calc_deviation_great_window();
+    let deviation_good_window = calc_deviation_good_window();
+
+    let Some(deviation_great_window) = deviation_great_window else {
+        return Some(deviation_good_window);
+    };
+
+    Some(deviation_great_window.min(deviation_good_window))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unstable_rate_of_offsets() {
+        assert_eq!(unstable_rate([]), None);
+        assert_eq!(unstable_rate([5.0]), None);
+
+        let ur = unstable_rate([-10.0, 10.0, -10.0, 10.0]).unwrap();
+        assert!((ur - 100.0).abs() < 1e-9, "{ur}");
+
+        let ur = MeasuredUnstableRate::from_hit_offsets([-15.0, 15.0]).unwrap();
+        assert!((ur.get(1.5) - 100.0).abs() < 1e-9);
+        assert!((MeasuredUnstableRate::Adjusted(80.0).get(1.5) - 80.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn hit_probability_bounds() {
+        assert!((hit_probability(50.0, 0.0) - 1.0).abs() < f64::EPSILON);
+        assert!(hit_probability(50.0, 1.0) > 0.999);
+        assert!(hit_probability(1.0, 1000.0) < 0.01);
+    }
+}
@@ -1,7 +1,10 @@
+pub mod checkpoints;
+pub mod deviation;
 pub mod float_ext;
 pub mod limited_queue;
 pub mod map_or_attrs;
 pub mod random;
+pub mod search;
 pub mod sort;
 pub mod special_functions;
 pub mod strains_vec;
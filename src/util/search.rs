@@ -0,0 +1,106 @@
+/// Maximum difference between the bounds when bisecting floats.
+const F64_PRECISION: f64 = 1e-4;
+
+/// Find the smallest value in `min..=max` for which `f` returns `true`.
+///
+/// `f` is assumed to be monotonically increasing, i.e. if it returns `true`
+/// for some value then it also returns `true` for all larger values.
+///
+/// Returns `None` if `f` does not return `true` for `max`.
+pub fn min_f64(min: f64, max: f64, mut f: impl FnMut(f64) -> bool) -> Option<f64> {
+    if !f(max) {
+        return None;
+    } else if f(min) {
+        return Some(min);
+    }
+
+    let mut lo = min;
+    let mut hi = max;
+
+    while hi - lo > F64_PRECISION {
+        let mid = lo + (hi - lo) / 2.0;
+
+        if f(mid) {
+            hi = mid;
+        } else {
+            lo = mid;
+        }
+    }
+
+    Some(hi)
+}
+
+/// Find the smallest value in `min..=max` for which `f` returns `true`.
+///
+/// `f` is assumed to be monotonically increasing, i.e. if it returns `true`
+/// for some value then it also returns `true` for all larger values.
+///
+/// Returns `None` if `f` does not return `true` for `max`.
+pub fn min_u32(min: u32, max: u32, mut f: impl FnMut(u32) -> bool) -> Option<u32> {
+    if !f(max) {
+        return None;
+    }
+
+    let mut lo = min;
+    let mut hi = max;
+
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+
+        if f(mid) {
+            hi = mid;
+        } else {
+            lo = mid + 1;
+        }
+    }
+
+    Some(hi)
+}
+
+/// Find the largest value in `min..=max` for which `f` returns `true`.
+///
+/// `f` is assumed to be monotonically decreasing, i.e. if it returns `true`
+/// for some value then it also returns `true` for all smaller values.
+///
+/// Returns `None` if `f` does not return `true` for `min`.
+pub fn max_u32(min: u32, max: u32, mut f: impl FnMut(u32) -> bool) -> Option<u32> {
+    if !f(min) {
+        return None;
+    }
+
+    let mut lo = min;
+    let mut hi = max;
+
+    while lo < hi {
+        let mid = hi - (hi - lo) / 2;
+
+        if f(mid) {
+            lo = mid;
+        } else {
+            hi = mid - 1;
+        }
+    }
+
+    Some(lo)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounds() {
+        assert_eq!(min_u32(0, 100, |n| n >= 42), Some(42));
+        assert_eq!(min_u32(0, 100, |_| true), Some(0));
+        assert_eq!(min_u32(0, 100, |_| false), None);
+
+        assert_eq!(max_u32(0, 100, |n| n <= 42), Some(42));
+        assert_eq!(max_u32(0, 100, |_| true), Some(100));
+        assert_eq!(max_u32(0, 100, |_| false), None);
+
+        let found = min_f64(0.0, 100.0, |n| n >= 42.5).unwrap();
+        assert!((found - 42.5).abs() <= F64_PRECISION);
+        assert_eq!(min_f64(0.0, 100.0, |_| true), Some(0.0));
+        assert_eq!(min_f64(0.0, 100.0, |_| false), None);
+    }
+}
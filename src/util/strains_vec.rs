@@ -58,6 +58,38 @@ mod inner {
             self.len += 1;
         }
 
+        /// Shortens the vector to the first `len` elements.
+        ///
+        /// Does nothing if `len` is not smaller than the current length.
+        pub fn truncate(&mut self, len: usize) {
+            while self.len > len {
+                let Some(last) = self.inner.last_mut() else {
+                    break;
+                };
+
+                if last.is_value() {
+                    self.inner.pop();
+                    self.len -= 1;
+
+                    continue;
+                }
+
+                let excess = self.len - len;
+                let zero_count = last.zero_count() as usize;
+
+                if zero_count <= excess {
+                    self.inner.pop();
+                    self.len -= zero_count;
+                } else {
+                    for _ in 0..excess {
+                        last.decr_zero_count();
+                    }
+
+                    self.len = len;
+                }
+            }
+        }
+
         /// Sorts the entries in descending order.
         pub fn sort_desc(&mut self) {
             #[cfg(debug_assertions)]
@@ -268,6 +300,8 @@ mod inner {
 
     #[cfg(test)]
     mod tests {
+        use std::cmp;
+
         use proptest::prelude::*;
 
         use crate::util::float_ext::FloatExt;
@@ -314,6 +348,22 @@ mod inner {
 
                 assert!(vec.sorted_non_zero_iter().eq(values.into_iter().flatten()));
             }
+
+            #[test]
+            fn truncate(values in prop::collection::vec(prop::option::of(0.0..1_000.0), 0..1_000), len in 0..1_000_usize) {
+                let mut vec = StrainsVec::with_capacity(values.len());
+
+                for opt in values.iter().copied() {
+                    vec.push(opt.unwrap_or(0.0));
+                }
+
+                vec.truncate(len);
+
+                let expected = values.iter().copied().take(len).map(|opt| opt.unwrap_or(0.0));
+
+                assert_eq!(vec.len(), cmp::min(len, values.len()));
+                assert!(vec.iter().eq(expected));
+            }
         }
     }
 }
@@ -347,6 +397,10 @@ mod inner {
             self.inner.push(value);
         }
 
+        pub fn truncate(&mut self, len: usize) {
+            self.inner.truncate(len);
+        }
+
         pub fn sort_desc(&mut self) {
             self.inner.sort_by(|a, b| b.total_cmp(a));
         }
@@ -54,10 +54,13 @@ macro_rules! test_cases {
             aim: $aim,
             speed: $speed,
             flashlight: $flashlight,
+            autopilot: 0.0,
+            relax_stream_aim: 0.0,
             slider_factor: $slider_factor,
             speed_note_count: $speed_note_count,
             aim_difficult_strain_count: $aim_difficult_strain_count,
             speed_difficult_strain_count: $speed_difficult_strain_count,
+            autopilot_difficult_strain_count: 0.0,
             ar: $ar,
             od: $od,
             hp: $hp,
@@ -92,6 +95,7 @@ macro_rules! test_cases {
             stars: $stars,
             max_combo: $max_combo,
             is_convert: $is_convert,
+            is_relax: false,
         }
     };
     ( @Catch {
@@ -109,6 +113,7 @@ macro_rules! test_cases {
             n_droplets: $n_droplets,
             n_tiny_droplets: $n_tiny_droplets,
             is_convert: $is_convert,
+            is_relax: false,
         }
     };
     ( @Mania {
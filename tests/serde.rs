@@ -0,0 +1,115 @@
+#![cfg(feature = "serde")]
+
+use akatsuki_pp::{
+    any::{
+        DifficultyAttributes, PerformanceAttributes, ScoreState, Strains, Variant, SERDE_VERSION,
+    },
+    osu::{OsuDifficultyAttributes, OsuScoreState},
+    Beatmap, Difficulty, Performance,
+};
+use serde::{de::DeserializeOwned, Serialize};
+
+use self::common::assert_eq_float;
+
+mod common;
+
+fn roundtrip<T>(value: &T)
+where
+    T: Serialize + DeserializeOwned + PartialEq + std::fmt::Debug,
+{
+    let json = serde_json::to_string(value).unwrap();
+    let deserialized: T = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(&deserialized, value);
+}
+
+#[test]
+fn attributes() {
+    for path in [common::OSU, common::TAIKO, common::CATCH, common::MANIA] {
+        let map = Beatmap::from_path(path).unwrap();
+
+        let difficulty = Difficulty::new().calculate(&map);
+        roundtrip(&difficulty);
+
+        let performance = Performance::new(difficulty).misses(1).calculate();
+        roundtrip(&performance);
+
+        roundtrip(&Difficulty::new().strains(&map));
+        roundtrip(&map.attributes().build());
+    }
+}
+
+#[test]
+fn osu_2019() {
+    let map = Beatmap::from_path(common::OSU).unwrap();
+    let difficulty = Difficulty::new().variant(Variant::Relax2019);
+
+    let attrs = difficulty.calculate(&map);
+    assert!(matches!(attrs, DifficultyAttributes::Osu2019(_)));
+    roundtrip(&attrs);
+
+    let perf = Performance::new(&map)
+        .difficulty(difficulty.clone())
+        .calculate();
+    assert!(matches!(perf, PerformanceAttributes::Osu2019(_)));
+    roundtrip(&perf);
+
+    let strains = difficulty.strains(&map);
+    assert!(matches!(strains, Strains::Osu2019(_)));
+    roundtrip(&strains);
+}
+
+#[test]
+fn score_state() {
+    let state = ScoreState {
+        max_combo: 500,
+        n300: 400,
+        n100: 20,
+        misses: 3,
+        ..Default::default()
+    };
+
+    roundtrip(&state);
+    roundtrip(&OsuScoreState::from(state));
+}
+
+#[test]
+fn tagged_by_mode() {
+    let attrs = DifficultyAttributes::Osu(OsuDifficultyAttributes::default());
+    let json = serde_json::to_value(&attrs).unwrap();
+
+    assert_eq!(json["mode"], "Osu");
+}
+
+#[test]
+fn versioned() {
+    let map = Beatmap::from_path(common::OSU).unwrap();
+    let attrs = Difficulty::new().calculate(&map);
+
+    let mut json = serde_json::to_value(&attrs).unwrap();
+    assert_eq!(json["version"], SERDE_VERSION);
+
+    let deserialized: DifficultyAttributes = serde_json::from_value(json.clone()).unwrap();
+    assert_eq_float(deserialized.stars(), attrs.stars());
+
+    json["version"] = (SERDE_VERSION + 1).into();
+    let err = serde_json::from_value::<DifficultyAttributes>(json).unwrap_err();
+    assert!(err.to_string().contains("unsupported version"), "{err}");
+
+    let strains = serde_json::to_value(Difficulty::new().strains(&map)).unwrap();
+    assert_eq!(strains["version"], SERDE_VERSION);
+}
+
+#[test]
+fn missing_fields_use_default() {
+    let json = r#"{ "mode": "Osu", "stars": 5.5, "max_combo": 1000 }"#;
+    let attrs: DifficultyAttributes = serde_json::from_str(json).unwrap();
+
+    let expected = DifficultyAttributes::Osu(OsuDifficultyAttributes {
+        stars: 5.5,
+        max_combo: 1000,
+        ..Default::default()
+    });
+
+    assert_eq!(attrs, expected);
+}